@@ -11,7 +11,7 @@
 use rustc_errors::ErrorGuaranteed;
 use rustc_feature::{find_gated_cfg, is_builtin_attr_name, Features, GatedCfg};
 use rustc_macros::{Decodable, Encodable, HashStable_Generic};
-use rustc_session::config::ExpectedValues;
+use rustc_session::config::{ExpectedValues, OptLevel};
 use rustc_session::lint::builtin::UNEXPECTED_CFGS;
 use rustc_session::lint::BuiltinLintDiag;
 use rustc_session::parse::feature_err;
@@ -58,6 +58,16 @@ pub enum OptimizeAttr {
     None,
     Speed,
     Size,
+    /// `#[optimize(level = "...")]`, the optimization level of the function, like that of
+    /// `-C opt-level` for the whole crate.
+    Level(OptLevel),
+}
+
+impl OptimizeAttr {
+    /// Returns `true` if the function must not be optimized, with `#[optimize(level = "0")]`.
+    pub fn do_not_optimize(&self) -> bool {
+        matches!(self, OptimizeAttr::Level(OptLevel::No))
+    }
 }
 
 /// Represents the following attributes:
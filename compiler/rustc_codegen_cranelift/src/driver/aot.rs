@@ -10,6 +10,7 @@
 use rustc_codegen_ssa::assert_module_sources::CguReuse;
 use rustc_codegen_ssa::back::link::ensure_removed;
 use rustc_codegen_ssa::back::metadata::create_compressed_metadata_file;
+use rustc_codegen_ssa::back::write::emit_opt_remarks;
 use rustc_codegen_ssa::base::determine_cgu_reuse;
 use rustc_codegen_ssa::{
     errors as ssa_errors, CodegenResults, CompiledModule, CrateInfo, ModuleKind,
@@ -223,7 +224,19 @@ fn produce_final_output_artifacts(
                 user_wants_objects = true;
                 copy_if_one_unit(OutputType::Object, true);
             }
-            OutputType::Mir | OutputType::Metadata | OutputType::Exe | OutputType::DepInfo => {}
+            OutputType::Mir
+            | OutputType::Metadata
+            | OutputType::Exe
+            | OutputType::DepInfo
+            | OutputType::UnusedDeps
+            | OutputType::ApiSurface
+            | OutputType::MonoReport
+            | OutputType::SizeMap
+            | OutputType::UnsafeReport => {}
+            OutputType::OptRemarks => {
+                // Only the remarks of the MIR optimizations, Cranelift has none.
+                emit_opt_remarks(sess, codegen_results.crate_info.local_crate_name, crate_output);
+            }
         }
     }
 
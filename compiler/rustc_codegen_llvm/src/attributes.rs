@@ -344,14 +344,36 @@ pub fn from_fn_attrs<'ll, 'tcx>(
             to_add.push(llvm::AttributeKind::OptimizeForSize.create_attr(cx.llcx));
         }
         OptimizeAttr::Speed => {}
-    }
-
-    let inline =
-        if codegen_fn_attrs.inline == InlineAttr::None && instance.def.requires_inline(cx.tcx) {
-            InlineAttr::Hint
-        } else {
-            codegen_fn_attrs.inline
-        };
+        OptimizeAttr::Level(level) => match level {
+            OptLevel::No => {
+                // `optnone` requires `noinline`, whatever the inline attribute of the function.
+                to_add.push(llvm::AttributeKind::OptimizeNone.create_attr(cx.llcx));
+                to_add.push(llvm::AttributeKind::NoInline.create_attr(cx.llcx));
+            }
+            OptLevel::Size => {
+                to_add.push(llvm::AttributeKind::OptimizeForSize.create_attr(cx.llcx));
+            }
+            OptLevel::SizeMin => {
+                to_add.push(llvm::AttributeKind::MinSize.create_attr(cx.llcx));
+                to_add.push(llvm::AttributeKind::OptimizeForSize.create_attr(cx.llcx));
+            }
+            // LLVM has no optimization levels for functions: their MIR is optimized at their
+            // level, see `run_optimization_passes`, and their code at the level of the crate,
+            // see `backend_optimization_level`.
+            OptLevel::Less | OptLevel::Default | OptLevel::Aggressive => {}
+        },
+    }
+
+    let inline = if codegen_fn_attrs.optimize.do_not_optimize() {
+        InlineAttr::None
+    } else if let ty::InstanceKind::ColdPathShim { .. } = instance.def {
+        // Inlining a cold path back into the functions calling it would undo its outlining.
+        InlineAttr::Never
+    } else if codegen_fn_attrs.inline == InlineAttr::None && instance.def.requires_inline(cx.tcx) {
+        InlineAttr::Hint
+    } else {
+        codegen_fn_attrs.inline
+    };
     to_add.extend(inline_attr(cx, inline));
 
     // The `uwtable` attribute according to LLVM is:
@@ -390,7 +412,9 @@ pub fn from_fn_attrs<'ll, 'tcx>(
         to_add.push(llvm::CreateAttrString(cx.llcx, "no-builtins"));
     }
 
-    if codegen_fn_attrs.flags.contains(CodegenFnAttrFlags::COLD) {
+    if codegen_fn_attrs.flags.contains(CodegenFnAttrFlags::COLD)
+        || matches!(instance.def, ty::InstanceKind::ColdPathShim { .. })
+    {
         to_add.push(AttributeKind::Cold.create_attr(cx.llcx));
     }
     if codegen_fn_attrs.flags.contains(CodegenFnAttrFlags::FFI_PURE) {
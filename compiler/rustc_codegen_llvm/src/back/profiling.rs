@@ -4,16 +4,21 @@
 
 use measureme::event_id::SEPARATOR_BYTE;
 use measureme::{EventId, StringComponent, StringId};
-use rustc_data_structures::profiling::{SelfProfiler, TimingGuard};
+use rustc_data_structures::profiling::{
+    SelfProfiler, TimeReport, TimeReportGuard, TimeReportItem, TimingGuard,
+};
 
-fn llvm_args_to_string_id(profiler: &SelfProfiler, pass_name: &str, ir_name: &str) -> EventId {
-    let pass_name = profiler.get_or_alloc_cached_string(pass_name);
-    let mut components = vec![StringComponent::Ref(pass_name)];
+fn demangled_ir_names(ir_name: &str) -> impl Iterator<Item = String> + '_ {
     // handle that LazyCallGraph::SCC is a comma separated list within parentheses
     let parentheses: &[_] = &['(', ')'];
     let trimmed = ir_name.trim_matches(parentheses);
-    for part in trimmed.split(", ") {
-        let demangled_ir_name = rustc_demangle::demangle(part).to_string();
+    trimmed.split(", ").map(|part| rustc_demangle::demangle(part).to_string())
+}
+
+fn llvm_args_to_string_id(profiler: &SelfProfiler, pass_name: &str, ir_name: &str) -> EventId {
+    let pass_name = profiler.get_or_alloc_cached_string(pass_name);
+    let mut components = vec![StringComponent::Ref(pass_name)];
+    for demangled_ir_name in demangled_ir_names(ir_name) {
         let ir_name = profiler.get_or_alloc_cached_string(demangled_ir_name);
         components.push(StringComponent::Value(SEPARATOR_BYTE));
         components.push(StringComponent::Ref(ir_name));
@@ -21,25 +26,39 @@ fn llvm_args_to_string_id(profiler: &SelfProfiler, pass_name: &str, ir_name: &st
     EventId::from_label(profiler.alloc_string(components.as_slice()))
 }
 
+/// Records the LLVM passes in the self-profile and in the `-Z time-report`, whichever of
+/// them are enabled.
 pub struct LlvmSelfProfiler<'a> {
-    profiler: Arc<SelfProfiler>,
+    profiler: Option<(Arc<SelfProfiler>, StringId)>,
     stack: Vec<TimingGuard<'a>>,
-    llvm_pass_event_kind: StringId,
+    time_report: Option<&'a TimeReport>,
+    time_report_stack: Vec<TimeReportGuard<'a>>,
 }
 
 impl<'a> LlvmSelfProfiler<'a> {
-    pub fn new(profiler: Arc<SelfProfiler>) -> Self {
-        let llvm_pass_event_kind = profiler.alloc_string("LLVM Pass");
-        Self { profiler, stack: Vec::default(), llvm_pass_event_kind }
+    pub fn new(profiler: Option<Arc<SelfProfiler>>, time_report: Option<&'a TimeReport>) -> Self {
+        let profiler = profiler.map(|profiler| {
+            let llvm_pass_event_kind = profiler.alloc_string("LLVM Pass");
+            (profiler, llvm_pass_event_kind)
+        });
+        Self { profiler, stack: Vec::default(), time_report, time_report_stack: Vec::default() }
     }
 
     fn before_pass_callback(&'a mut self, pass_name: &str, ir_name: &str) {
-        let event_id = llvm_args_to_string_id(&self.profiler, pass_name, ir_name);
+        if let Some((profiler, llvm_pass_event_kind)) = &self.profiler {
+            let event_id = llvm_args_to_string_id(profiler, pass_name, ir_name);
 
-        self.stack.push(TimingGuard::start(&self.profiler, self.llvm_pass_event_kind, event_id));
+            self.stack.push(TimingGuard::start(profiler, *llvm_pass_event_kind, event_id));
+        }
+        if let Some(time_report) = self.time_report {
+            let ir_names = demangled_ir_names(ir_name).collect::<Vec<_>>().join(", ");
+            let item = TimeReportItem::Named(format!("{pass_name} on {ir_names}"));
+            self.time_report_stack.push(time_report.start("LLVM_pass", item));
+        }
     }
     fn after_pass_callback(&mut self) {
         self.stack.pop();
+        self.time_report_stack.pop();
     }
 }
 
@@ -15,7 +15,7 @@
 };
 use rustc_codegen_ssa::traits::*;
 use rustc_codegen_ssa::{CompiledModule, ModuleCodegen};
-use rustc_data_structures::profiling::SelfProfilerRef;
+use rustc_data_structures::profiling::{SelfProfilerRef, TimeReportItem};
 use rustc_data_structures::small_c_str::SmallCStr;
 use rustc_errors::{DiagCtxtHandle, FatalError, Level};
 use rustc_fs_util::{link_or_copy, path_to_c_string};
@@ -23,6 +23,7 @@
 use rustc_session::config::{
     self, Lto, OutputType, Passes, RemapPathScopeComponents, SplitDwarfKind, SwitchWithOptPath,
 };
+use rustc_session::opt_remarks::{OptRemark, OptRemarkKind, OptRemarkLocation, OptRemarkOrigin};
 use rustc_session::Session;
 use rustc_span::symbol::sym;
 use rustc_span::InnerSpan;
@@ -343,15 +344,17 @@ pub fn new(
         let remark_passes_all: bool;
         let remark_passes: Vec<CString>;
         match &cgcx.remark {
-            Passes::All => {
-                remark_passes_all = true;
-                remark_passes = Vec::new();
-            }
-            Passes::Some(passes) => {
+            Passes::Some(passes) if !emits_opt_remarks(cgcx) => {
                 remark_passes_all = false;
                 remark_passes =
                     passes.iter().map(|name| CString::new(name.as_str()).unwrap()).collect();
             }
+            // All the remarks are needed for `--emit=opt-remarks`, those of the passes that were
+            // not requested with `-C remark` are not printed by the handler.
+            Passes::Some(_) | Passes::All => {
+                remark_passes_all = true;
+                remark_passes = Vec::new();
+            }
         };
         let remark_passes: Vec<*const c_char> =
             remark_passes.iter().map(|name: &CString| name.as_ptr()).collect();
@@ -399,6 +402,12 @@ fn drop(&mut self) {
     }
 }
 
+/// Whether the remarks of LLVM are recorded for `--emit=opt-remarks`, which is incompatible with
+/// `-Z remark-dir`.
+fn emits_opt_remarks(cgcx: &CodegenContext<LlvmCodegenBackend>) -> bool {
+    cgcx.opts.output_types.contains_key(&OutputType::OptRemarks)
+}
+
 fn report_inline_asm(
     cgcx: &CodegenContext<LlvmCodegenBackend>,
     msg: String,
@@ -433,22 +442,46 @@ fn report_inline_asm(
         }
 
         llvm::diagnostic::Optimization(opt) => {
-            dcx.emit_note(FromLlvmOptimizationDiag {
-                filename: &opt.filename,
-                line: opt.line,
-                column: opt.column,
-                pass_name: &opt.pass_name,
-                kind: match opt.kind {
-                    OptimizationDiagnosticKind::OptimizationRemark => "success",
-                    OptimizationDiagnosticKind::OptimizationMissed
-                    | OptimizationDiagnosticKind::OptimizationFailure => "missed",
-                    OptimizationDiagnosticKind::OptimizationAnalysis
-                    | OptimizationDiagnosticKind::OptimizationAnalysisFPCommute
-                    | OptimizationDiagnosticKind::OptimizationAnalysisAliasing => "analysis",
-                    OptimizationDiagnosticKind::OptimizationRemarkOther => "other",
-                },
-                message: &opt.message,
-            });
+            let kind = match opt.kind {
+                OptimizationDiagnosticKind::OptimizationRemark => OptRemarkKind::Success,
+                OptimizationDiagnosticKind::OptimizationMissed
+                | OptimizationDiagnosticKind::OptimizationFailure => OptRemarkKind::Missed,
+                OptimizationDiagnosticKind::OptimizationAnalysis
+                | OptimizationDiagnosticKind::OptimizationAnalysisFPCommute
+                | OptimizationDiagnosticKind::OptimizationAnalysisAliasing => {
+                    OptRemarkKind::Analysis
+                }
+                OptimizationDiagnosticKind::OptimizationRemarkOther => OptRemarkKind::Other,
+            };
+            if cgcx.remark.contains(&opt.pass_name) {
+                dcx.emit_note(FromLlvmOptimizationDiag {
+                    filename: &opt.filename,
+                    line: opt.line,
+                    column: opt.column,
+                    pass_name: &opt.pass_name,
+                    kind: kind.as_str(),
+                    message: &opt.message,
+                });
+            }
+            if emits_opt_remarks(cgcx) {
+                let function = String::from_utf8_lossy(llvm::get_value_name(opt.function));
+                cgcx.diag_emitter.opt_remark(OptRemark {
+                    // Without debuginfo, LLVM doesn't know the location.
+                    location: (opt.line != 0).then(|| OptRemarkLocation {
+                        file: opt.filename,
+                        line: opt.line,
+                        column: opt.column,
+                    }),
+                    origin: OptRemarkOrigin::Llvm,
+                    pass: opt.pass_name,
+                    kind,
+                    function: match rustc_demangle::try_demangle(&function) {
+                        Ok(demangled) => format!("{demangled:#}"),
+                        Err(_) => function.into_owned(),
+                    },
+                    message: opt.message,
+                });
+            }
         }
         llvm::diagnostic::PGO(diagnostic_ref) | llvm::diagnostic::Linker(diagnostic_ref) => {
             let message = llvm::build_string(|s| unsafe {
@@ -549,10 +582,11 @@ pub(crate) unsafe fn llvm_optimize(
         None
     };
 
-    let mut llvm_profiler = cgcx
-        .prof
-        .llvm_recording_enabled()
-        .then(|| LlvmSelfProfiler::new(cgcx.prof.get_self_profiler().unwrap()));
+    let self_profiler =
+        cgcx.prof.llvm_recording_enabled().then(|| cgcx.prof.get_self_profiler().unwrap());
+    let time_report = cgcx.prof.time_report();
+    let mut llvm_profiler = (self_profiler.is_some() || time_report.is_some())
+        .then(|| LlvmSelfProfiler::new(self_profiler, time_report));
 
     let llvm_selfprofiler =
         llvm_profiler.as_mut().map(|s| s as *mut _ as *mut c_void).unwrap_or(std::ptr::null_mut());
@@ -605,6 +639,9 @@ pub(crate) unsafe fn optimize(
     config: &ModuleConfig,
 ) -> Result<(), FatalError> {
     let _timer = cgcx.prof.generic_activity_with_arg("LLVM_module_optimize", &*module.name);
+    let _time_report_guard = cgcx.prof.time_report_activity("LLVM_module_optimize", || {
+        TimeReportItem::Named(module.name.clone())
+    });
 
     let llmod = module.module_llvm.llmod();
     let llcx = &*module.module_llvm.llcx;
@@ -661,6 +698,9 @@ pub(crate) unsafe fn codegen(
     config: &ModuleConfig,
 ) -> Result<CompiledModule, FatalError> {
     let _timer = cgcx.prof.generic_activity_with_arg("LLVM_module_codegen", &*module.name);
+    let _time_report_guard = cgcx
+        .prof
+        .time_report_activity("LLVM_module_codegen", || TimeReportItem::Named(module.name.clone()));
     {
         let llmod = module.module_llvm.llmod();
         let llcx = &*module.module_llvm.llcx;
@@ -17,6 +17,7 @@
 use rustc_codegen_ssa::mono_item::MonoItemExt;
 use rustc_codegen_ssa::traits::*;
 use rustc_codegen_ssa::{ModuleCodegen, ModuleKind};
+use rustc_data_structures::profiling::TimeReportItem;
 use rustc_data_structures::small_c_str::SmallCStr;
 use rustc_middle::dep_graph;
 use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrs;
@@ -77,6 +78,9 @@ fn module_codegen(tcx: TyCtxt<'_>, cgu_name: Symbol) -> ModuleCodegen<ModuleLlvm
                 recorder.record_arg(cgu_name.to_string());
                 recorder.record_arg(cgu.size_estimate().to_string());
             });
+        let _time_report_guard = tcx
+            .prof
+            .time_report_activity("codegen_module", || TimeReportItem::Named(cgu_name.to_string()));
         // Instantiate monomorphizations without filling out definitions yet...
         let llvm_module = ModuleLlvm::new(tcx, cgu_name.as_str());
         {
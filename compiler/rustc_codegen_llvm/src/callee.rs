@@ -146,6 +146,10 @@ pub fn get_fn<'ll, 'tcx>(cx: &CodegenCx<'ll, 'tcx>, instance: Instance<'tcx>) ->
                     // crate and have hidden visibility
                     llvm::LLVMRustSetVisibility(llfn, llvm::Visibility::Hidden);
                 }
+            } else if let ty::InstanceKind::ColdPathShim { .. } = instance.def {
+                // This is a cold path outlined from the functions of the local
+                // crate, which is always hidden.
+                llvm::LLVMRustSetVisibility(llfn, llvm::Visibility::Hidden);
             } else {
                 // This is a non-generic function
                 if cx.tcx.is_codegened_item(instance_def_id) {
@@ -149,12 +149,21 @@ codegen_ssa_lib_def_write_failure = failed to write lib.def file: {$error}
 
 codegen_ssa_link_exe_unexpected_error = `link.exe` returned an unexpected error
 
+codegen_ssa_link_reproducer_write_failure = failed to write the reproducer of the linker invocation to {$path}: {$error}
+
+codegen_ssa_link_reproducer_written = the linker invocation can be replayed by running `{$script}` in `{$path}`
+
 codegen_ssa_link_script_unavailable = can only use link script when linking with GNU-like linker
 
 codegen_ssa_link_script_write_failure = failed to write link script to {$path}: {$error}
 
 codegen_ssa_linker_file_stem = couldn't extract file stem from specified linker
 
+codegen_ssa_linker_implementation_not_found = the `{$implementation}` linker implementation was selected, but `{$program}` was not found
+    .note = install it, or select another one with `-C linker-implementation`
+
+codegen_ssa_linker_implementation_unsupported = the `{$implementation}` linker implementation is not supported when linking for target `{$target}`
+
 codegen_ssa_linker_not_found = linker `{$linker_path}` not found
     .note = {$error}
 
@@ -195,6 +204,8 @@ codegen_ssa_processing_dymutil_failed = processing debug info with `dsymutil` fa
 
 codegen_ssa_read_file = failed to read file: {$message}
 
+codegen_ssa_read_size_map = failed to read the symbols of `{$path}` for the size map: {$error}
+
 codegen_ssa_repair_vs_build_tools = the Visual Studio build tools may need to be repaired using the Visual Studio installer
 
 codegen_ssa_rlib_archive_build_failure = failed to build archive from rlib at `{$path}`: {$error}
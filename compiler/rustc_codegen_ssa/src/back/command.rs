@@ -119,6 +119,14 @@ pub fn take_args(&mut self) -> Vec<OsString> {
         mem::take(&mut self.args)
     }
 
+    /// Returns the value the environment variable `key` is set to for the command, if it is set.
+    pub fn get_env(&self, key: &OsStr) -> Option<&OsStr> {
+        if self.env_remove.iter().any(|k| k == key) {
+            return None;
+        }
+        self.env.iter().rev().find(|(k, _)| k == key).map(|(_, v)| v.as_os_str())
+    }
+
     /// Returns a `true` if we're pretty sure that this'll blow OS spawn limits,
     /// or `false` if we should attempt to spawn and see what the OS says.
     pub fn very_likely_to_exceed_some_spawn_limit(&self) -> bool {
@@ -1,5 +1,5 @@
 use std::collections::BTreeSet;
-use std::ffi::OsString;
+use std::ffi::{OsStr, OsString};
 use std::fs::{read, File, OpenOptions};
 use std::io::{BufWriter, Write};
 use std::ops::Deref;
@@ -25,8 +25,8 @@
 use rustc_middle::middle::dependency_format::Linkage;
 use rustc_middle::middle::exported_symbols::SymbolExportKind;
 use rustc_session::config::{
-    self, CFGuard, CrateType, DebugInfo, LinkerFeaturesCli, OutFileName, OutputFilenames,
-    OutputType, PrintKind, SplitDwarfKind, Strip,
+    self, CFGuard, CrateType, DebugInfo, LinkerFeaturesCli, LinkerImplementation, OutFileName,
+    OutputFilenames, OutputType, PrintKind, SplitDwarfKind, Strip,
 };
 use rustc_session::cstore::DllImport;
 use rustc_session::output::{check_file_is_writeable, invalid_output_for_target, out_filename};
@@ -47,9 +47,11 @@
 
 use super::archive::{ArchiveBuilder, ArchiveBuilderBuilder};
 use super::command::Command;
+use super::link_reproducer;
 use super::linker::{self, Linker};
 use super::metadata::{create_wrapper_file, MetadataPosition};
 use super::rpath::{self, RPathConfig};
+use super::size_map;
 use crate::{
     errors, looks_like_rust_object_file, CodegenResults, CompiledModule, CrateInfo, NativeLib,
 };
@@ -73,6 +75,7 @@ pub fn link_binary(
     let _timer = sess.timer("link_binary");
     let output_metadata = sess.opts.output_types.contains_key(&OutputType::Metadata);
     let mut tempfiles_for_stdout_output: Vec<PathBuf> = Vec::new();
+    let mut artifact_sizes = Vec::new();
     for &crate_type in &codegen_results.crate_info.crate_types {
         // Ignore executable crates if we have -Z no-codegen, as they will error.
         if (sess.opts.unstable_opts.no_codegen || !sess.opts.output_types.should_codegen())
@@ -142,6 +145,15 @@ pub fn link_binary(
                         codegen_results,
                         path.as_ref(),
                     )?;
+                    if sess.opts.output_types.contains_key(&OutputType::SizeMap) {
+                        match size_map::read_artifact_sizes(&out_filename, crate_type) {
+                            Ok(sizes) => artifact_sizes.push(sizes),
+                            Err(error) => {
+                                sess.dcx()
+                                    .emit_err(errors::ReadSizeMap { path: &out_filename, error });
+                            }
+                        }
+                    }
                 }
             }
             if sess.opts.json_artifact_notifications {
@@ -174,6 +186,10 @@ pub fn link_binary(
         }
     }
 
+    if sess.opts.output_types.contains_key(&OutputType::SizeMap) {
+        size_map::emit_size_map(sess, outputs, &artifact_sizes);
+    }
+
     // Remove the temporary object file and metadata if we aren't saving temps.
     sess.time("link_binary_remove_temps", || {
         // If the user requests that temporaries are saved, don't delete any.
@@ -748,6 +764,8 @@ fn link_natively(
         cmd.env_remove(k.as_ref());
     }
 
+    check_linker_implementation(sess, &cmd, flavor);
+
     for print in &sess.opts.prints {
         if print.kind == PrintKind::LinkArgs {
             let content = format!("{cmd:?}\n");
@@ -932,6 +950,16 @@ fn is_illegal_instruction(_status: &ExitStatus) -> bool {
         }
     }
 
+    // The reproducer has the arguments of the last invocation of the linker, and is written
+    // before the temporary directory holding some of its input files is removed.
+    let reproducer = sess.opts.unstable_opts.link_reproducer.as_deref().and_then(|dir| {
+        link_reproducer::write_link_reproducer(sess, dir, &cmd, flavor, out_filename)
+            .map_err(|error| {
+                sess.dcx().emit_err(errors::LinkReproducerWriteFailure { path: dir, error })
+            })
+            .ok()
+    });
+
     match prog {
         Ok(prog) => {
             if !prog.status.success() {
@@ -946,6 +974,12 @@ fn is_illegal_instruction(_status: &ExitStatus) -> bool {
                     escaped_output,
                 };
                 sess.dcx().emit_err(err);
+                if let Some(path) = &reproducer {
+                    sess.dcx().emit_note(errors::LinkReproducerWritten {
+                        path,
+                        script: link_reproducer::SCRIPT,
+                    });
+                }
                 // If MSVC's `link.exe` was expected but the return code
                 // is not a Microsoft LNK error then suggest a way to fix or
                 // install the Visual Studio build tools.
@@ -1433,20 +1467,131 @@ fn adjust_flavor_to_features(
             .linker_flavor
             .map(|flavor| sess.target.linker_flavor.with_cli_hints(flavor)),
     };
-    if let Some(ret) = infer_from(sess, sess.opts.cg.linker.clone(), linker_flavor, features) {
-        return ret;
+    let (linker, flavor) = infer_from(sess, sess.opts.cg.linker.clone(), linker_flavor, features)
+        .or_else(|| {
+            infer_from(
+                sess,
+                sess.target.linker.as_deref().map(PathBuf::from),
+                Some(sess.target.linker_flavor),
+                features,
+            )
+        })
+        .unwrap_or_else(|| {
+            bug!("Not enough information provided to determine how to invoke the linker")
+        });
+
+    match sess.opts.cg.linker_implementation {
+        Some(implementation) => adjust_to_implementation(sess, linker, flavor, implementation),
+        None => (linker, flavor),
     }
+}
 
-    if let Some(ret) = infer_from(
-        sess,
-        sess.target.linker.as_deref().map(PathBuf::from),
-        Some(sess.target.linker_flavor),
-        features,
-    ) {
-        return ret;
+/// Adjusts the linker and its flavor to use the linker implementation selected with
+/// `-C linker-implementation`.
+///
+/// Flavors using a C/C++ compiler keep it as the linker, it is asked to use the implementation
+/// with `-fuse-ld` in `add_linker_implementation_args` (and `add_lld_args` for lld). For the
+/// other flavors, the implementation is invoked directly, unless `-C linker` was also given.
+fn adjust_to_implementation(
+    sess: &Session,
+    linker: PathBuf,
+    flavor: LinkerFlavor,
+    implementation: LinkerImplementation,
+) -> (PathBuf, LinkerFlavor) {
+    let flavor = match implementation {
+        LinkerImplementation::Lld => flavor.with_lld_enabled(),
+        LinkerImplementation::Bfd | LinkerImplementation::Gold | LinkerImplementation::Mold => {
+            flavor.with_lld_disabled()
+        }
+    };
+    let Some(program) = linker_implementation_program(flavor, implementation) else {
+        sess.dcx().emit_fatal(errors::LinkerImplementationUnsupported {
+            implementation: implementation.desc(),
+            target: sess.opts.target_triple.to_string(),
+        })
+    };
+
+    if flavor.uses_cc() {
+        (linker, flavor)
+    } else if let Some(linker) = &sess.opts.cg.linker {
+        // The user knows better where the linker is, and may have named it differently.
+        (linker.clone(), flavor)
+    } else {
+        (PathBuf::from(program), flavor)
     }
+}
 
-    bug!("Not enough information provided to determine how to invoke the linker");
+/// Returns the name of the program implementing the linker flavor `flavor` with `implementation`.
+fn linker_implementation_program(
+    flavor: LinkerFlavor,
+    implementation: LinkerImplementation,
+) -> Option<&'static str> {
+    Some(match (flavor, implementation) {
+        (LinkerFlavor::Gnu(..), LinkerImplementation::Bfd) => "ld.bfd",
+        (LinkerFlavor::Gnu(..), LinkerImplementation::Gold) => "ld.gold",
+        (LinkerFlavor::Gnu(..), LinkerImplementation::Lld) => "ld.lld",
+        (LinkerFlavor::Gnu(..), LinkerImplementation::Mold) => "ld.mold",
+        (LinkerFlavor::Darwin(..), LinkerImplementation::Lld) => "ld64.lld",
+        (LinkerFlavor::Msvc(..), LinkerImplementation::Lld) => "lld-link",
+        (LinkerFlavor::WasmLld(..), LinkerImplementation::Lld) => "wasm-ld",
+        _ => return None,
+    })
+}
+
+/// Checks that the linker implementation selected with `-C linker-implementation` is installed,
+/// by looking for it in the directories the linker command `cmd` will search.
+fn check_linker_implementation(sess: &Session, cmd: &Command, flavor: LinkerFlavor) {
+    let Some(implementation) = sess.opts.cg.linker_implementation else {
+        return;
+    };
+    if !flavor.uses_cc() && sess.opts.cg.linker.is_some() {
+        // The implementation is the linker given by the user.
+        return;
+    }
+    let Some(program) = linker_implementation_program(flavor, implementation) else {
+        return;
+    };
+
+    let path = cmd.get_env(OsStr::new("PATH")).map(OsStr::to_owned).or_else(|| env::var_os("PATH"));
+    let found = linker_implementation_search_dirs(cmd.get_args(), flavor, path.as_deref())
+        .into_iter()
+        .any(|dir| {
+            let path = dir.join(program);
+            path.is_file() || path.with_extension(env::consts::EXE_EXTENSION).is_file()
+        });
+    if !found {
+        sess.dcx().emit_fatal(errors::LinkerImplementationNotFound {
+            implementation: implementation.desc(),
+            program,
+        });
+    }
+}
+
+/// Returns the directories searched for the linker implementation by a linker command with the
+/// arguments `args` and the `PATH` environment variable `path`.
+///
+/// A linker implementation invoked directly is looked up in the `PATH`, which `get_linker` has
+/// already extended with the tools of the sysroot. A C/C++ compiler first looks into the
+/// directories given with `-B`, like the ones of the self-contained linker in `add_lld_args`,
+/// then into its own installation directories, which we don't know, and then into the `PATH`.
+fn linker_implementation_search_dirs(
+    args: &[OsString],
+    flavor: LinkerFlavor,
+    path: Option<&OsStr>,
+) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if flavor.uses_cc() {
+        dirs.extend(args.iter().filter_map(|arg| {
+            arg.as_encoded_bytes().strip_prefix(b"-B").map(|dir| {
+                // SAFETY: the prefix is ASCII, so the rest of the argument is still valid.
+                PathBuf::from(unsafe { OsStr::from_encoded_bytes_unchecked(dir) })
+            })
+        }));
+    }
+    if let Some(path) = path {
+        dirs.extend(env::split_paths(path));
+    }
+    dirs
 }
 
 /// Returns a pair of boolean indicating whether we should preserve the object and
@@ -1627,32 +1772,8 @@ fn exec_linker(
 
     info!("falling back to passing arguments to linker via an @-file");
     let mut cmd2 = cmd.clone();
-    let mut args = String::new();
-    for arg in cmd2.take_args() {
-        args.push_str(
-            &Escape {
-                arg: arg.to_str().unwrap(),
-                // LLD also uses MSVC-like parsing for @-files by default when running on windows hosts
-                is_like_msvc: sess.target.is_like_msvc || (cfg!(windows) && flavor.uses_lld()),
-            }
-            .to_string(),
-        );
-        args.push('\n');
-    }
     let file = tmpdir.join("linker-arguments");
-    let bytes = if sess.target.is_like_msvc {
-        let mut out = Vec::with_capacity((1 + args.len()) * 2);
-        // start the stream with a UTF-16 BOM
-        for c in std::iter::once(0xFEFF).chain(args.encode_utf16()) {
-            // encode in little endian
-            out.push(c as u8);
-            out.push((c >> 8) as u8);
-        }
-        out
-    } else {
-        args.into_bytes()
-    };
-    fs::write(&file, &bytes)?;
+    fs::write(&file, linker_arguments_file(sess, flavor, &cmd2.take_args())?)?;
     cmd2.arg(format!("@{}", file.display()));
     info!("invoking linker {:?}", cmd2);
     let output = cmd2.output();
@@ -1703,50 +1824,89 @@ fn command_line_too_big(err: &io::Error) -> bool {
     fn command_line_too_big(_: &io::Error) -> bool {
         false
     }
+}
 
-    struct Escape<'a> {
-        arg: &'a str,
-        is_like_msvc: bool,
-    }
-
-    impl<'a> fmt::Display for Escape<'a> {
-        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-            if self.is_like_msvc {
-                // This is "documented" at
-                // https://docs.microsoft.com/en-us/cpp/build/reference/at-specify-a-linker-response-file
-                //
-                // Unfortunately there's not a great specification of the
-                // syntax I could find online (at least) but some local
-                // testing showed that this seemed sufficient-ish to catch
-                // at least a few edge cases.
-                write!(f, "\"")?;
-                for c in self.arg.chars() {
-                    match c {
-                        '"' => write!(f, "\\{c}")?,
-                        c => write!(f, "{c}")?,
-                    }
+/// Returns the contents of an `@`-file passing `args` to the linker, which is accepted by all
+/// linkers when the arguments don't fit on the command line.
+pub(super) fn linker_arguments_file(
+    sess: &Session,
+    flavor: LinkerFlavor,
+    args: &[OsString],
+) -> io::Result<Vec<u8>> {
+    let mut contents = String::new();
+    for arg in args {
+        let Some(arg) = arg.to_str() else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("linker argument {arg:?} is not valid UTF-8"),
+            ));
+        };
+        contents.push_str(
+            &Escape {
+                arg,
+                // LLD also uses MSVC-like parsing for @-files by default when running on windows hosts
+                is_like_msvc: sess.target.is_like_msvc || (cfg!(windows) && flavor.uses_lld()),
+            }
+            .to_string(),
+        );
+        contents.push('\n');
+    }
+    if sess.target.is_like_msvc {
+        let mut out = Vec::with_capacity((1 + contents.len()) * 2);
+        // start the stream with a UTF-16 BOM
+        for c in std::iter::once(0xFEFF).chain(contents.encode_utf16()) {
+            // encode in little endian
+            out.push(c as u8);
+            out.push((c >> 8) as u8);
+        }
+        Ok(out)
+    } else {
+        Ok(contents.into_bytes())
+    }
+}
+
+struct Escape<'a> {
+    arg: &'a str,
+    is_like_msvc: bool,
+}
+
+impl<'a> fmt::Display for Escape<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_like_msvc {
+            // This is "documented" at
+            // https://docs.microsoft.com/en-us/cpp/build/reference/at-specify-a-linker-response-file
+            //
+            // Unfortunately there's not a great specification of the
+            // syntax I could find online (at least) but some local
+            // testing showed that this seemed sufficient-ish to catch
+            // at least a few edge cases.
+            write!(f, "\"")?;
+            for c in self.arg.chars() {
+                match c {
+                    '"' => write!(f, "\\{c}")?,
+                    c => write!(f, "{c}")?,
                 }
-                write!(f, "\"")?;
-            } else {
-                // This is documented at https://linux.die.net/man/1/ld, namely:
-                //
-                // > Options in file are separated by whitespace. A whitespace
-                // > character may be included in an option by surrounding the
-                // > entire option in either single or double quotes. Any
-                // > character (including a backslash) may be included by
-                // > prefixing the character to be included with a backslash.
-                //
-                // We put an argument on each line, so all we need to do is
-                // ensure the line is interpreted as one whole argument.
-                for c in self.arg.chars() {
-                    match c {
-                        '\\' | ' ' => write!(f, "\\{c}")?,
-                        c => write!(f, "{c}")?,
-                    }
+            }
+            write!(f, "\"")?;
+        } else {
+            // This is documented at https://linux.die.net/man/1/ld, namely:
+            //
+            // > Options in file are separated by whitespace. A whitespace
+            // > character may be included in an option by surrounding the
+            // > entire option in either single or double quotes. Any
+            // > character (including a backslash) may be included by
+            // > prefixing the character to be included with a backslash.
+            //
+            // We put an argument on each line, so all we need to do is
+            // ensure the line is interpreted as one whole argument.
+            for c in self.arg.chars() {
+                match c {
+                    '\\' | ' ' => write!(f, "\\{c}")?,
+                    c => write!(f, "{c}")?,
                 }
             }
-            Ok(())
         }
+        Ok(())
     }
 }
 
@@ -2367,6 +2527,9 @@ fn add_order_independent_options(
     // Take care of the flavors and CLI options requesting the `lld` linker.
     add_lld_args(cmd, sess, flavor, self_contained_components);
 
+    // Take care of the other linker implementations requested on the CLI.
+    add_linker_implementation_args(cmd, sess, flavor);
+
     add_apple_sdk(cmd, sess, flavor);
 
     add_link_script(cmd, sess, tmpdir, crate_type);
@@ -3119,7 +3282,11 @@ fn add_lld_args(
     // can still override this default when needed (e.g. for tests).
     let uses_llvm_backend =
         matches!(sess.opts.unstable_opts.codegen_backend.as_deref(), None | Some("llvm"));
-    if !uses_llvm_backend && !self_contained_cli && sess.opts.cg.linker_flavor.is_none() {
+    if !uses_llvm_backend
+        && !self_contained_cli
+        && sess.opts.cg.linker_flavor.is_none()
+        && sess.opts.cg.linker_implementation.is_none()
+    {
         // We bail if we're not using llvm and lld was not explicitly requested on the CLI.
         return;
     }
@@ -3178,3 +3345,19 @@ fn add_lld_args(
         }
     }
 }
+
+/// When using a C/C++ compiler to invoke the linker, asks it to use the linker implementation
+/// selected with `-C linker-implementation`. `lld` is handled by `add_lld_args`, the others are
+/// only supported by GNU-like linkers.
+fn add_linker_implementation_args(cmd: &mut dyn Linker, sess: &Session, flavor: LinkerFlavor) {
+    if !flavor.uses_cc() {
+        return;
+    }
+    let arg = match sess.opts.cg.linker_implementation {
+        Some(LinkerImplementation::Bfd) => "-fuse-ld=bfd",
+        Some(LinkerImplementation::Gold) => "-fuse-ld=gold",
+        Some(LinkerImplementation::Mold) => "-fuse-ld=mold",
+        Some(LinkerImplementation::Lld) | None => return,
+    };
+    cmd.cc_arg(arg);
+}
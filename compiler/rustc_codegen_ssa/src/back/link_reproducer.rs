@@ -0,0 +1,191 @@
+//! Reproducers of linker invocations, written with `-Z link-reproducer`.
+//!
+//! The reproducer of a link is a directory holding copies of the files that were passed to the
+//! linker, like object files, rlibs, archives and linker scripts, a response file with the
+//! arguments of the linker referring to these copies, and a script invoking the linker with that
+//! response file and the environment variables rustc set for it. The replayed link writes its
+//! output to the `output` directory of the reproducer, instead of where rustc wrote it.
+//!
+//! Only the files passed to the linker are copied: the libraries it finds in its search paths,
+//! like the system libraries, are looked up in the same directories when replaying the link.
+
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_session::Session;
+use rustc_target::spec::LinkerFlavor;
+
+use super::command::Command;
+use super::link::linker_arguments_file;
+
+/// The name of the script replaying the link in the reproducer.
+pub(super) const SCRIPT: &str = if cfg!(windows) { "link.bat" } else { "link.sh" };
+
+/// The name of the empty file marking a directory as a reproducer, which may be replaced.
+const MARKER: &str = ".rustc-link-reproducer";
+
+/// Writes the reproducer of linking `out_filename` with `cmd` to the `<output>.link-reproducer`
+/// directory in `dir`, replacing any previous reproducer of the same output, and returns its path.
+/// A directory of that name which isn't a reproducer is left alone, and an error is returned.
+pub(super) fn write_link_reproducer(
+    sess: &Session,
+    dir: &Path,
+    cmd: &Command,
+    flavor: LinkerFlavor,
+    out_filename: &Path,
+) -> io::Result<PathBuf> {
+    let name = out_filename.file_name().unwrap_or(OsStr::new("output"));
+    let mut reproducer_name = name.to_owned();
+    reproducer_name.push(".link-reproducer");
+    let reproducer = dir.join(reproducer_name);
+    if reproducer.exists() {
+        if !reproducer.join(MARKER).is_file() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("`{}` exists and is not a reproducer", reproducer.display()),
+            ));
+        }
+        fs::remove_dir_all(&reproducer)?;
+    }
+    fs::create_dir_all(reproducer.join("inputs"))?;
+    fs::create_dir_all(reproducer.join("output"))?;
+    fs::write(reproducer.join(MARKER), "")?;
+
+    let mut inputs = Inputs {
+        reproducer: &reproducer,
+        copies: FxHashMap::default(),
+        names: FxHashSet::default(),
+    };
+    let output = Path::new("output").join(name);
+    let args = cmd
+        .get_args()
+        .iter()
+        .map(|arg| rewrite_arg(arg, out_filename, &output, &mut inputs))
+        .collect::<io::Result<Vec<_>>>()?;
+    fs::write(reproducer.join("linker-arguments"), linker_arguments_file(sess, flavor, &args)?)?;
+
+    write_script(&reproducer, cmd)?;
+    Ok(reproducer)
+}
+
+/// The input files copied to the reproducer.
+struct Inputs<'a> {
+    reproducer: &'a Path,
+    /// The path of the copy of each input file, relative to the reproducer.
+    copies: FxHashMap<PathBuf, PathBuf>,
+    names: FxHashSet<OsString>,
+}
+
+impl Inputs<'_> {
+    /// Copies the file at `path` to the `inputs` directory, once, and returns the path of the copy.
+    fn copy(&mut self, path: &Path) -> io::Result<PathBuf> {
+        if let Some(copy) = self.copies.get(path) {
+            return Ok(copy.clone());
+        }
+        // Files from different directories can have the same name, like the `lib.rmeta` of rlibs
+        // or the objects of different crates, they are prefixed with a counter.
+        let file_name = path.file_name().unwrap_or(OsStr::new("input"));
+        let mut name = file_name.to_owned();
+        let mut counter = 1;
+        while !self.names.insert(name.clone()) {
+            name = OsString::from(format!("{counter}-"));
+            name.push(file_name);
+            counter += 1;
+        }
+        let copy = Path::new("inputs").join(name);
+        fs::copy(path, self.reproducer.join(&copy))?;
+        self.copies.insert(path.to_owned(), copy.clone());
+        Ok(copy)
+    }
+}
+
+/// Returns `arg` with the path of the output and the paths of the input files it refers to
+/// replaced by paths relative to the reproducer. Paths are either the whole argument, or follow a
+/// `,`, `=` or `:` separator, like in `-Wl,--version-script=<path>` or `/OUT:<path>`.
+fn rewrite_arg(
+    arg: &OsStr,
+    out_filename: &Path,
+    output: &Path,
+    inputs: &mut Inputs<'_>,
+) -> io::Result<OsString> {
+    let Some(arg_str) = arg.to_str() else {
+        return Ok(arg.to_owned());
+    };
+    let starts =
+        std::iter::once(0).chain(arg_str.match_indices([',', '=', ':']).map(|(i, _)| i + 1));
+    for start in starts {
+        let (prefix, path) = arg_str.split_at(start);
+        let path = Path::new(path);
+        let replacement = if path == out_filename {
+            output.to_owned()
+        } else if path.is_file() {
+            inputs.copy(path)?
+        } else {
+            continue;
+        };
+        let mut rewritten = OsString::from(prefix);
+        rewritten.push(replacement);
+        return Ok(rewritten);
+    }
+    Ok(arg.to_owned())
+}
+
+/// Writes the script replaying the link from the reproducer, which can be run from any directory.
+fn write_script(reproducer: &Path, cmd: &Command) -> io::Result<()> {
+    let command = cmd.command();
+    // Some linkers are invoked through another program, or with leading arguments selecting
+    // their flavor, these are kept on the command line.
+    let leading_args = command.get_args().len() - cmd.get_args().len();
+    let mut program = PathBuf::from(command.get_program());
+    if program.components().count() > 1 {
+        // A relative path to the linker doesn't work from the reproducer.
+        program = std::path::absolute(&program)?;
+    }
+    let program = program.into_os_string();
+    let invocation: Vec<&OsStr> = std::iter::once(&*program)
+        .chain(command.get_args().take(leading_args))
+        .chain([OsStr::new("@linker-arguments")])
+        .collect();
+
+    let path = reproducer.join(SCRIPT);
+    if cfg!(windows) {
+        let mut script = String::from("@echo off\r\ncd /d \"%~dp0\"\r\n");
+        for (key, value) in command.get_envs() {
+            let key = key.to_string_lossy();
+            match value {
+                Some(value) => script += &format!("set \"{key}={}\"\r\n", value.to_string_lossy()),
+                None => script += &format!("set {key}=\r\n"),
+            }
+        }
+        let invocation: Vec<_> =
+            invocation.iter().map(|arg| format!("\"{}\"", arg.to_string_lossy())).collect();
+        script += &invocation.join(" ");
+        script += "\r\n";
+        fs::write(&path, script)?;
+    } else {
+        let mut script = String::from("#!/bin/sh\ncd \"$(dirname \"$0\")\" || exit 1\n");
+        for (key, value) in command.get_envs() {
+            let key = key.to_string_lossy();
+            match value {
+                Some(value) => script += &format!("export {key}={}\n", shell_quote(value)),
+                None => script += &format!("unset {key}\n"),
+            }
+        }
+        let invocation: Vec<_> = invocation.iter().map(|arg| shell_quote(arg)).collect();
+        script += &format!("exec {}\n", invocation.join(" "));
+        fs::write(&path, script)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o755))?;
+        }
+    }
+    Ok(())
+}
+
+/// Quotes `arg` for a POSIX shell.
+fn shell_quote(arg: &OsStr) -> String {
+    format!("'{}'", arg.to_string_lossy().replace('\'', r"'\''"))
+}
@@ -1,9 +1,11 @@
 pub mod archive;
 pub mod command;
 pub mod link;
+mod link_reproducer;
 pub mod linker;
 pub mod lto;
 pub mod metadata;
 pub mod rpath;
+mod size_map;
 pub mod symbol_export;
 pub mod write;
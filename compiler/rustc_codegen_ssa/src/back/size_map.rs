@@ -0,0 +1,179 @@
+//! Attribution of the bytes of linked artifacts to Rust items, emitted with `--emit=size-map`.
+//!
+//! After linking, the symbols of the artifact are read back and each defined symbol is reported
+//! with its size, the section it is in, its demangled path and the crate of that path. The
+//! crate is the first segment of the path, or of the self type or trait of qualified paths like
+//! `<u8 as core::fmt::Debug>::fmt`, so generic code is attributed to the crate defining it
+//! rather than to the crate instantiating it. Symbols that aren't Rust symbols, like those of C
+//! libraries, have no demangled path and no crate.
+//!
+//! Formats that don't record the size of symbols, like Mach-O and COFF, get the distance to the
+//! next symbol of their section instead. Symbols at the same address as another one are
+//! aliases, only the first one is reported so that the sizes add up.
+
+use std::fs;
+use std::path::Path;
+
+use object::{Object, ObjectSection, ObjectSymbol, SectionIndex, SymbolSection};
+use rustc_data_structures::fx::FxIndexMap;
+use rustc_session::config::{CrateType, OutputFilenames, OutputType};
+use rustc_session::Session;
+use serde::Serialize;
+
+#[cfg(test)]
+mod tests;
+
+/// The sizes of a linked artifact.
+#[derive(Serialize)]
+pub(super) struct ArtifactSizes {
+    path: String,
+    crate_type: String,
+    sections: Vec<SectionSize>,
+    crates: Vec<CrateSize>,
+    symbols: Vec<SymbolSize>,
+}
+
+#[derive(Serialize)]
+struct SectionSize {
+    name: String,
+    size: u64,
+}
+
+#[derive(Serialize)]
+struct CrateSize {
+    name: Option<String>,
+    size: u64,
+}
+
+#[derive(Serialize)]
+struct SymbolSize {
+    name: String,
+    demangled: Option<String>,
+    #[serde(rename = "crate")]
+    krate: Option<String>,
+    section: String,
+    size: u64,
+}
+
+/// Reads the symbols of the artifact at `path`, of the given crate type.
+pub(super) fn read_artifact_sizes(
+    path: &Path,
+    crate_type: CrateType,
+) -> Result<ArtifactSizes, String> {
+    let data = fs::read(path).map_err(|err| err.to_string())?;
+    let file = object::File::parse(&*data).map_err(|err| err.to_string())?;
+
+    let section_name = |index: SectionIndex| {
+        file.section_by_index(index)
+            .and_then(|section| section.name().map(str::to_owned))
+            .unwrap_or_default()
+    };
+    let section_end = |index: SectionIndex| {
+        file.section_by_index(index).map_or(0, |section| section.address() + section.size())
+    };
+
+    let mut definitions: Vec<_> = file
+        .symbols()
+        .filter(|symbol| symbol.is_definition())
+        .filter_map(|symbol| {
+            let SymbolSection::Section(section) = symbol.section() else {
+                return None;
+            };
+            let name = symbol.name().ok().filter(|name| !name.is_empty())?;
+            Some((section, symbol.address(), symbol.size(), name))
+        })
+        .collect();
+    // The largest symbol at each address comes first, the others are aliases of it.
+    definitions.sort_by_key(|&(section, address, size, name)| {
+        (section.0, address, std::cmp::Reverse(size), name)
+    });
+    definitions.dedup_by_key(|&mut (section, address, ..)| (section, address));
+
+    let mut symbols = Vec::with_capacity(definitions.len());
+    let mut crate_sizes: FxIndexMap<Option<String>, u64> = FxIndexMap::default();
+    for (i, &(section, address, size, name)) in definitions.iter().enumerate() {
+        let size = if size > 0 {
+            size
+        } else {
+            match definitions.get(i + 1) {
+                Some(&(next_section, next_address, ..)) if next_section == section => {
+                    next_address - address
+                }
+                _ => section_end(section).saturating_sub(address),
+            }
+        };
+        let demangled = rustc_demangle::try_demangle(name).ok().map(|path| format!("{path:#}"));
+        let krate = demangled.as_deref().and_then(crate_of).map(str::to_owned);
+        *crate_sizes.entry(krate.clone()).or_default() += size;
+        symbols.push(SymbolSize {
+            name: name.to_owned(),
+            demangled,
+            krate,
+            section: section_name(section),
+            size,
+        });
+    }
+    symbols.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.name.cmp(&b.name)));
+
+    let mut crates: Vec<_> =
+        crate_sizes.into_iter().map(|(name, size)| CrateSize { name, size }).collect();
+    crates.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.name.cmp(&b.name)));
+
+    let sections = file
+        .sections()
+        .filter(|section| section.size() > 0)
+        .map(|section| SectionSize {
+            name: section.name().unwrap_or_default().to_owned(),
+            size: section.size(),
+        })
+        .collect();
+
+    Ok(ArtifactSizes {
+        path: path.display().to_string(),
+        crate_type: crate_type.to_string(),
+        sections,
+        crates,
+        symbols,
+    })
+}
+
+/// Writes the sizes of the artifacts that were linked.
+pub(super) fn emit_size_map(
+    sess: &Session,
+    outputs: &OutputFilenames,
+    artifacts: &[ArtifactSizes],
+) {
+    #[derive(Serialize)]
+    struct SizeMap<'a> {
+        artifacts: &'a [ArtifactSizes],
+    }
+
+    let mut json = serde_json::to_string(&SizeMap { artifacts }).unwrap();
+    json.push('\n');
+    outputs.path(OutputType::SizeMap).overwrite(&json, sess);
+}
+
+/// Returns the crate of `path`, a demangled symbol without its hash: the first segment of the
+/// path, or of the self type or the trait of a qualified path.
+fn crate_of(path: &str) -> Option<&str> {
+    let mut rest = path;
+    loop {
+        // Skip the punctuation and keywords of the self type.
+        loop {
+            let trimmed = rest.trim_start_matches(['<', '&', '*', '[', '(', ' ']);
+            let trimmed = ["mut ", "const ", "dyn ", "impl "]
+                .iter()
+                .fold(trimmed, |rest, prefix| rest.strip_prefix(prefix).unwrap_or(rest));
+            if trimmed.len() == rest.len() {
+                break;
+            }
+            rest = trimmed;
+        }
+        let end = rest.find(|c: char| !(c.is_alphanumeric() || c == '_')).unwrap_or(rest.len());
+        if end > 0 && rest[end..].starts_with("::") {
+            return Some(&rest[..end]);
+        }
+        // The self type is not a path, like a primitive type, try the trait.
+        rest = &rest[rest.find(" as ")? + " as ".len()..];
+    }
+}
@@ -0,0 +1,13 @@
+use super::crate_of;
+
+#[test]
+fn test_crate_of() {
+    assert_eq!(crate_of("core::fmt::write"), Some("core"));
+    assert_eq!(crate_of("foo::main::{{closure}}"), Some("foo"));
+    assert_eq!(crate_of("<alloc::vec::Vec<u8> as core::fmt::Debug>::fmt"), Some("alloc"));
+    assert_eq!(crate_of("<&mut dyn std::io::Write as core::fmt::Debug>::fmt"), Some("std"));
+    assert_eq!(crate_of("<[foo::Bar] as core::fmt::Debug>::fmt"), Some("foo"));
+    assert_eq!(crate_of("<u8 as core::fmt::Display>::fmt"), Some("core"));
+    assert_eq!(crate_of("<*const u8 as core::fmt::Pointer>::fmt"), Some("core"));
+    assert_eq!(crate_of("main"), None);
+}
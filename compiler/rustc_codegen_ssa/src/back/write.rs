@@ -31,9 +31,10 @@
 use rustc_session::config::{
     self, CrateType, Lto, OutFileName, OutputFilenames, OutputType, Passes, SwitchWithOptPath,
 };
+use rustc_session::opt_remarks::OptRemark;
 use rustc_session::Session;
 use rustc_span::source_map::SourceMap;
-use rustc_span::symbol::sym;
+use rustc_span::symbol::{sym, Symbol};
 use rustc_span::{BytePos, FileName, InnerSpan, Pos, Span};
 use rustc_target::spec::{MergeFunctions, SanitizerSet};
 use tracing::debug;
@@ -555,6 +556,21 @@ fn copy_all_cgu_workproducts_to_incr_comp_cache_dir(
     work_products
 }
 
+/// Writes the optimization remarks recorded during the compilation of the crate, merging those of
+/// all codegen units.
+pub fn emit_opt_remarks(sess: &Session, crate_name: Symbol, crate_output: &OutputFilenames) {
+    #[derive(serde::Serialize)]
+    struct OptRemarks<'a> {
+        crate_name: &'a str,
+        remarks: Vec<OptRemark>,
+    }
+
+    let remarks = OptRemarks { crate_name: crate_name.as_str(), remarks: sess.opt_remarks.take() };
+    let mut json = serde_json::to_string(&remarks).unwrap();
+    json.push('\n');
+    crate_output.path(OutputType::OptRemarks).overwrite(&json, sess);
+}
+
 fn produce_final_output_artifacts(
     sess: &Session,
     compiled_modules: &CompiledModules,
@@ -644,7 +660,16 @@ fn produce_final_output_artifacts(
                 user_wants_objects = true;
                 copy_if_one_unit(OutputType::Object, true);
             }
-            OutputType::Mir | OutputType::Metadata | OutputType::Exe | OutputType::DepInfo => {}
+            OutputType::Mir
+            | OutputType::Metadata
+            | OutputType::Exe
+            | OutputType::DepInfo
+            | OutputType::UnusedDeps
+            | OutputType::ApiSurface
+            | OutputType::MonoReport
+            | OutputType::SizeMap
+            | OutputType::OptRemarks
+            | OutputType::UnsafeReport => {}
         }
     }
 
@@ -1851,6 +1876,7 @@ fn drop(&mut self) {
 enum SharedEmitterMessage {
     Diagnostic(Diagnostic),
     InlineAsmError(u32, String, Level, Option<(String, Vec<InnerSpan>)>),
+    OptRemark(OptRemark),
     Fatal(String),
 }
 
@@ -1880,6 +1906,12 @@ pub fn inline_asm_error(
         drop(self.sender.send(SharedEmitterMessage::InlineAsmError(cookie, msg, level, source)));
     }
 
+    /// Records a remark of the codegen backend for `--emit=opt-remarks`. Its file path is
+    /// remapped on the main thread.
+    pub fn opt_remark(&self, remark: OptRemark) {
+        drop(self.sender.send(SharedEmitterMessage::OptRemark(remark)));
+    }
+
     pub fn fatal(&self, msg: &str) {
         drop(self.sender.send(SharedEmitterMessage::Fatal(msg.to_string())));
     }
@@ -1993,6 +2025,15 @@ pub fn check(&self, sess: &Session, blocking: bool) {
 
                     err.emit();
                 }
+                Ok(SharedEmitterMessage::OptRemark(mut remark)) => {
+                    if let Some(location) = &mut remark.location {
+                        let path_mapping = sess.source_map().path_mapping();
+                        if let (path, true) = path_mapping.map_prefix(Path::new(&location.file)) {
+                            location.file = path.to_string_lossy().into_owned();
+                        }
+                    }
+                    sess.opt_remarks.record(remark);
+                }
                 Ok(SharedEmitterMessage::Fatal(msg)) => {
                     sess.dcx().fatal(msg);
                 }
@@ -2060,6 +2101,9 @@ pub fn join(self, sess: &Session) -> (CodegenResults, FxIndexMap<WorkProductId,
         let work_products =
             copy_all_cgu_workproducts_to_incr_comp_cache_dir(sess, &compiled_modules);
         produce_final_output_artifacts(sess, &compiled_modules, &self.output_filenames);
+        if self.output_filenames.outputs.contains_key(&OutputType::OptRemarks) {
+            emit_opt_remarks(sess, self.crate_info.local_crate_name, &self.output_filenames);
+        }
 
         // FIXME: time_llvm_passes support - does this use a global context or
         // something?
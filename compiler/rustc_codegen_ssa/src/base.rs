@@ -1026,6 +1026,12 @@ pub fn provide(providers: &mut Providers) {
             match optimize {
                 attr::OptimizeAttr::None | attr::OptimizeAttr::Size => false,
                 attr::OptimizeAttr::Speed => true,
+                attr::OptimizeAttr::Level(level) => matches!(
+                    level,
+                    config::OptLevel::Less
+                        | config::OptLevel::Default
+                        | config::OptLevel::Aggressive
+                ),
             }
         });
 
@@ -13,9 +13,10 @@
 use rustc_middle::mir::mono::Linkage;
 use rustc_middle::query::Providers;
 use rustc_middle::ty::{self as ty, TyCtxt};
+use rustc_session::config::OptLevel;
 use rustc_session::lint;
 use rustc_session::parse::feature_err;
-use rustc_span::symbol::Ident;
+use rustc_span::symbol::{Ident, Symbol};
 use rustc_span::{sym, Span};
 use rustc_target::spec::{abi, SanitizerSet};
 
@@ -578,6 +579,28 @@ fn emit_error_with_label(
                     OptimizeAttr::Size
                 } else if list_contains_name(items, sym::speed) {
                     OptimizeAttr::Speed
+                } else if let Some(item) = items[0].meta_item()
+                    && item.has_name(sym::level)
+                {
+                    let level = match item.value_str().as_ref().map(Symbol::as_str) {
+                        Some("0") => Some(OptLevel::No),
+                        Some("1") => Some(OptLevel::Less),
+                        Some("2") => Some(OptLevel::Default),
+                        Some("3") => Some(OptLevel::Aggressive),
+                        Some("s") => Some(OptLevel::Size),
+                        Some("z") => Some(OptLevel::SizeMin),
+                        _ => None,
+                    };
+                    match level {
+                        Some(level) => OptimizeAttr::Level(level),
+                        None => {
+                            err(
+                                item.span,
+                                "invalid optimization level, expected 0, 1, 2, 3, s or z",
+                            );
+                            OptimizeAttr::None
+                        }
+                    }
                 } else {
                     err(items[0].span(), "invalid argument");
                     OptimizeAttr::None
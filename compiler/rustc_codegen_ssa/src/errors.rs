@@ -462,6 +462,21 @@ pub struct UnableToRun<'a> {
 #[diag(codegen_ssa_linker_file_stem)]
 pub struct LinkerFileStem;
 
+#[derive(Diagnostic)]
+#[diag(codegen_ssa_linker_implementation_not_found)]
+#[note]
+pub struct LinkerImplementationNotFound {
+    pub implementation: &'static str,
+    pub program: &'static str,
+}
+
+#[derive(Diagnostic)]
+#[diag(codegen_ssa_linker_implementation_unsupported)]
+pub struct LinkerImplementationUnsupported {
+    pub implementation: &'static str,
+    pub target: String,
+}
+
 #[derive(Diagnostic)]
 #[diag(codegen_ssa_static_library_native_artifacts)]
 pub struct StaticLibraryNativeArtifacts;
@@ -483,6 +498,20 @@ pub struct LinkScriptWriteFailure {
     pub error: Error,
 }
 
+#[derive(Diagnostic)]
+#[diag(codegen_ssa_link_reproducer_write_failure)]
+pub struct LinkReproducerWriteFailure<'a> {
+    pub path: &'a Path,
+    pub error: Error,
+}
+
+#[derive(Diagnostic)]
+#[diag(codegen_ssa_link_reproducer_written)]
+pub struct LinkReproducerWritten<'a> {
+    pub path: &'a Path,
+    pub script: &'static str,
+}
+
 #[derive(Diagnostic)]
 #[diag(codegen_ssa_failed_to_write)]
 pub struct FailedToWrite {
@@ -544,6 +573,13 @@ pub enum AppleSdkRootError<'a> {
     SdkPath { sdk_name: &'a str, error: Error },
 }
 
+#[derive(Diagnostic)]
+#[diag(codegen_ssa_read_size_map)]
+pub struct ReadSizeMap<'a> {
+    pub path: &'a Path,
+    pub error: String,
+}
+
 #[derive(Diagnostic)]
 #[diag(codegen_ssa_read_file)]
 pub struct ReadFileError {
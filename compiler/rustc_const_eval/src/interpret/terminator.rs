@@ -615,6 +615,8 @@ pub(crate) fn eval_fn_call(
             | ty::InstanceKind::FnPtrAddrShim(..)
             | ty::InstanceKind::ThreadLocalShim(..)
             | ty::InstanceKind::AsyncDropGlueCtorShim(..)
+            | ty::InstanceKind::ConstArgsShim(..)
+            | ty::InstanceKind::ColdPathShim { .. }
             | ty::InstanceKind::Item(_) => {
                 // We need MIR for this fn
                 let Some((body, instance)) = M::find_mir_or_eval_fn(
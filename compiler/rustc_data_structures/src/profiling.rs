@@ -79,17 +79,25 @@
 //! invocation) and allocate the corresponding strings together with a mapping
 //! for `DepNodeIndex as StringId`.
 //!
+//! ## Time Reports
+//!
+//! Independently of `measureme`, `-Z time-report` aggregates in memory how
+//! much time was spent per query, MIR pass and LLVM pass, attributed to the item
+//! (function, codegen unit, ...) it was run for, and writes the totals to a
+//! single JSON file at the end of the compilation. See `TimeReport`.
+//!
 //! [mm]: https://github.com/rust-lang/measureme/
 
 use std::borrow::Borrow;
+use std::cell::Cell;
 use std::collections::hash_map::Entry;
 use std::error::Error;
-use std::fmt::Display;
+use std::fmt::{Display, Write as _};
 use std::intrinsics::unlikely;
 use std::path::Path;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use std::{fs, process};
+use std::{fs, io, mem, process};
 
 pub use measureme::EventId;
 use measureme::{EventIdBuilder, Profiler, SerializableString, StringId};
@@ -97,8 +105,10 @@
 use smallvec::SmallVec;
 use tracing::warn;
 
+use crate::fingerprint::Fingerprint;
 use crate::fx::FxHashMap;
 use crate::outline;
+use crate::sync::Lock;
 
 bitflags::bitflags! {
     #[derive(Clone, Copy)]
@@ -171,19 +181,28 @@ pub struct SelfProfilerRef {
 
     // Print verbose generic activities to stderr.
     print_verbose_generic_activities: Option<TimePassesFormat>,
+
+    // This field is `None` unless `-Z time-report` is used.
+    time_report: Option<Arc<TimeReport>>,
 }
 
 impl SelfProfilerRef {
     pub fn new(
         profiler: Option<Arc<SelfProfiler>>,
         print_verbose_generic_activities: Option<TimePassesFormat>,
+        time_report: Option<Arc<TimeReport>>,
     ) -> SelfProfilerRef {
         // If there is no SelfProfiler then the filter mask is set to NONE,
         // ensuring that nothing ever tries to actually access it.
         let event_filter_mask =
             profiler.as_ref().map_or(EventFilter::empty(), |p| p.event_filter_mask);
 
-        SelfProfilerRef { profiler, event_filter_mask, print_verbose_generic_activities }
+        SelfProfilerRef {
+            profiler,
+            event_filter_mask,
+            print_verbose_generic_activities,
+            time_report,
+        }
     }
 
     /// This shim makes sure that calls only get executed if the filter mask
@@ -502,6 +521,23 @@ pub fn llvm_recording_enabled(&self) -> bool {
     pub fn get_self_profiler(&self) -> Option<Arc<SelfProfiler>> {
         self.profiler.clone()
     }
+
+    /// Start attributing time to `event_label` and to the item returned by `item` in the
+    /// `-Z time-report`. Timing continues until the returned guard is dropped. `item` is
+    /// only called if a time report is being collected.
+    #[inline(always)]
+    pub fn time_report_activity(
+        &self,
+        event_label: &'static str,
+        item: impl FnOnce() -> TimeReportItem,
+    ) -> Option<TimeReportGuard<'_>> {
+        self.time_report.as_deref().map(|report| report.start(event_label, item()))
+    }
+
+    #[inline]
+    pub fn time_report(&self) -> Option<&TimeReport> {
+        self.time_report.as_deref()
+    }
 }
 
 /// A helper for recording costly arguments to self-profiling events. Used with
@@ -764,6 +800,162 @@ fn drop(&mut self) {
     }
 }
 
+/// What the time of an activity is attributed to in a `TimeReport`.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum TimeReportItem {
+    /// The time is only attributed to the activity, e.g. for queries whose key is not an item.
+    None,
+    /// The `DefPathHash` of the item a query was run for. It is replaced by the path of the
+    /// item in `TimeReport::resolve_def_path_hashes`, while it can still be resolved.
+    DefPathHash(Fingerprint),
+    /// An item that already has a name, e.g. an LLVM function or a codegen unit.
+    Named(String),
+}
+
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+struct TimeReportEntry {
+    count: u64,
+    /// The time spent in the activity, including nested activities.
+    time: Duration,
+    /// The time spent in the activity, excluding nested activities.
+    self_time: Duration,
+}
+
+impl TimeReportEntry {
+    fn merge(&mut self, other: TimeReportEntry) {
+        self.count += other.count;
+        self.time += other.time;
+        self.self_time += other.self_time;
+    }
+}
+
+thread_local! {
+    /// The time spent in nested activities of the innermost activity timed on this thread.
+    static NESTED_TIME: Cell<Duration> = const { Cell::new(Duration::ZERO) };
+}
+
+/// Collects the data of `-Z time-report`: the number of times each activity ran for each
+/// item and how long it took, with and without the activities nested in it.
+///
+/// Nesting is tracked per thread, so the self time of queries that wait on other threads
+/// with `-Z threads` also contains the time spent waiting.
+#[derive(Default)]
+pub struct TimeReport {
+    entries: Lock<FxHashMap<(&'static str, TimeReportItem), TimeReportEntry>>,
+}
+
+impl TimeReport {
+    pub fn start(&self, event_label: &'static str, item: TimeReportItem) -> TimeReportGuard<'_> {
+        let outer_nested_time = NESTED_TIME.replace(Duration::ZERO);
+        TimeReportGuard {
+            report: self,
+            event_label,
+            item: Some(item),
+            start: Instant::now(),
+            outer_nested_time,
+        }
+    }
+
+    fn record(&self, event_label: &'static str, item: TimeReportItem, entry: TimeReportEntry) {
+        self.entries.lock().entry((event_label, item)).or_default().merge(entry);
+    }
+
+    /// Gives a name to the items that were recorded as `TimeReportItem::DefPathHash`.
+    /// Items `name_item` cannot name are only attributed to their activity.
+    pub fn resolve_def_path_hashes(
+        &self,
+        mut name_item: impl FnMut(Fingerprint) -> Option<String>,
+    ) {
+        // `name_item` may run queries which are recorded in the report too, so the entries
+        // must not be locked while calling it.
+        let entries = mem::take(&mut *self.entries.lock());
+        for ((event_label, item), entry) in entries {
+            let item = match item {
+                TimeReportItem::DefPathHash(hash) => {
+                    name_item(hash).map_or(TimeReportItem::None, TimeReportItem::Named)
+                }
+                item => item,
+            };
+            self.record(event_label, item, entry);
+        }
+    }
+
+    /// Renders the report as a JSON object, with the entries sorted by decreasing self time.
+    pub fn to_json(&self) -> String {
+        let entries = self.entries.lock();
+        let mut entries: Vec<_> = entries.iter().collect();
+        entries.sort_by(|(a_key, a), (b_key, b)| {
+            b.self_time.cmp(&a.self_time).then_with(|| {
+                let key = |&(label, ref item): &(&'static str, TimeReportItem)| {
+                    (label, format!("{item:?}"))
+                };
+                key(a_key).cmp(&key(b_key))
+            })
+        });
+
+        let mut json = String::from("{\"entries\":[");
+        for (i, ((event_label, item), entry)) in entries.into_iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            json.push_str("\n{\"activity\":");
+            write_json_string(&mut json, event_label);
+            json.push_str(",\"item\":");
+            match item {
+                TimeReportItem::None => json.push_str("null"),
+                TimeReportItem::DefPathHash(hash) => write_json_string(&mut json, &hash.to_hex()),
+                TimeReportItem::Named(name) => write_json_string(&mut json, name),
+            }
+            let TimeReportEntry { count, time, self_time } = entry;
+            write!(
+                json,
+                ",\"count\":{count},\"time\":{},\"self_time\":{}}}",
+                time.as_secs_f64(),
+                self_time.as_secs_f64()
+            )
+            .unwrap();
+        }
+        json.push_str("\n]}\n");
+        json
+    }
+
+    pub fn write_json(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_json())
+    }
+}
+
+fn write_json_string(json: &mut String, s: &str) {
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            c if c.is_control() => write!(json, "\\u{:04x}", c as u32).unwrap(),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+#[must_use]
+pub struct TimeReportGuard<'a> {
+    report: &'a TimeReport,
+    event_label: &'static str,
+    item: Option<TimeReportItem>,
+    start: Instant,
+    outer_nested_time: Duration,
+}
+
+impl Drop for TimeReportGuard<'_> {
+    fn drop(&mut self) {
+        let time = self.start.elapsed();
+        let nested_time = NESTED_TIME.replace(self.outer_nested_time + time);
+        let self_time = time.saturating_sub(nested_time);
+        let entry = TimeReportEntry { count: 1, time, self_time };
+        self.report.record(self.event_label, self.item.take().unwrap(), entry);
+    }
+}
+
 struct JsonTimePassesEntry<'a> {
     pass: &'a str,
     time: f64,
@@ -1,4 +1,7 @@
-use super::JsonTimePassesEntry;
+use std::time::Duration;
+
+use super::{JsonTimePassesEntry, TimeReport, TimeReportEntry, TimeReportItem};
+use crate::fingerprint::Fingerprint;
 
 #[test]
 fn with_rss() {
@@ -17,3 +20,56 @@ fn no_rss() {
         r#"{"pass":"typeck","time":56.1,"rss_start":null,"rss_end":null}"#
     )
 }
+
+fn entry(count: u64, time: u64, self_time: u64) -> TimeReportEntry {
+    TimeReportEntry {
+        count,
+        time: Duration::from_millis(time),
+        self_time: Duration::from_millis(self_time),
+    }
+}
+
+#[test]
+fn time_report_json() {
+    let report = TimeReport::default();
+    report.record("typeck", TimeReportItem::Named("main".to_owned()), entry(1, 500, 250));
+    report.record("typeck", TimeReportItem::Named("main".to_owned()), entry(1, 500, 250));
+    report.record("LLVM_pass", TimeReportItem::Named("a\"b".to_owned()), entry(2, 1000, 1000));
+    report.record("hir_crate", TimeReportItem::None, entry(1, 250, 125));
+
+    assert_eq!(
+        report.to_json(),
+        concat!(
+            "{\"entries\":[\n",
+            r#"{"activity":"LLVM_pass","item":"a\"b","count":2,"time":1,"self_time":1},"#,
+            "\n",
+            r#"{"activity":"typeck","item":"main","count":2,"time":1,"self_time":0.5},"#,
+            "\n",
+            r#"{"activity":"hir_crate","item":null,"count":1,"time":0.25,"self_time":0.125}"#,
+            "\n]}\n",
+        )
+    );
+}
+
+#[test]
+fn time_report_resolve_def_path_hashes() {
+    let known = Fingerprint::new(1u64, 2u64);
+    let unknown = Fingerprint::new(3u64, 4u64);
+
+    let report = TimeReport::default();
+    report.record("typeck", TimeReportItem::DefPathHash(known), entry(1, 500, 500));
+    report.record("typeck", TimeReportItem::DefPathHash(unknown), entry(1, 250, 250));
+    report.record("typeck", TimeReportItem::None, entry(1, 250, 250));
+    report.resolve_def_path_hashes(|hash| (hash == known).then(|| "krate::main".to_owned()));
+
+    assert_eq!(
+        report.to_json(),
+        concat!(
+            "{\"entries\":[\n",
+            r#"{"activity":"typeck","item":"krate::main","count":1,"time":0.5,"self_time":0.5},"#,
+            "\n",
+            r#"{"activity":"typeck","item":null,"count":2,"time":0.5,"self_time":0.5}"#,
+            "\n]}\n",
+        )
+    );
+}
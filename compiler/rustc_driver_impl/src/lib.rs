@@ -835,6 +835,25 @@ fn print_crate_info(
                     println_info!("{check_cfg}");
                 }
             }
+            IncrementalCacheUsage => {
+                let incr_dir = sess.opts.incremental.as_ref().unwrap();
+                let usage =
+                    rustc_incremental::incremental_cache_usage(incr_dir).unwrap_or_else(|err| {
+                        #[allow(rustc::diagnostic_outside_of_impl)]
+                        sess.dcx().fatal(format!(
+                            "failed to read the incremental compilation directory `{}`: {err}",
+                            incr_dir.display()
+                        ))
+                    });
+                for crate_usage in &usage {
+                    let last_used = OffsetDateTime::from(crate_usage.last_used)
+                        .format(&time::format_description::well_known::Rfc3339)
+                        .unwrap_or_default();
+                    println_info!("{}\t{last_used}\t{}", crate_usage.size, crate_usage.name);
+                }
+                let total: u64 = usage.iter().map(|crate_usage| crate_usage.size).sum();
+                println_info!("{total}\ttotal");
+            }
             CallingConventions => {
                 let mut calling_conventions = rustc_target::spec::abi::all_names();
                 calling_conventions.sort_unstable();
@@ -0,0 +1,266 @@
+//! Applying suggestions to the source files, used by `-Z apply-suggestions`.
+//!
+//! This does what `cargo fix` does with the JSON output of rustc, for build systems that can't
+//! use it. The machine-applicable suggestions of all emitted diagnostics are collected, and at
+//! the end of the compilation they are applied to the files they point to, or printed as a
+//! unified diff in dry-run mode.
+//!
+//! Only suggestions with a single substitution, entirely in one local source file and outside
+//! of macro expansions, are applied. A suggestion that overlaps one that was emitted before it
+//! is skipped, compiling again applies it if it is still relevant then.
+
+use std::fmt::Write as _;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use rustc_data_structures::fx::{FxHashSet, FxIndexMap};
+use rustc_data_structures::sync::Lrc;
+use rustc_span::source_map::SourceMap;
+use rustc_span::{FileName, Pos, SourceFile, SourceFileHash};
+
+use crate::{Applicability, DiagInner, Substitution};
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct Replacement {
+    /// The replaced bytes of the file, as it is on disk.
+    range: Range<usize>,
+    snippet: String,
+}
+
+impl Replacement {
+    fn conflicts_with(&self, other: &Replacement) -> bool {
+        if self == other {
+            return false;
+        }
+        let (a, b) = (&self.range, &other.range);
+        // Two insertions at the same position, or an insertion at the start of a replaced range,
+        // conflict because their order is ambiguous.
+        (a.start < b.end && b.start < a.end)
+            || (a.start == b.start && (a.is_empty() || b.is_empty()))
+    }
+}
+
+struct FileSuggestions {
+    /// The hash of the file when it was compiled, to leave it alone if it changed since then.
+    src_hash: SourceFileHash,
+    /// The replacements of each suggestion, in the order they were emitted.
+    suggestions: Vec<Vec<Replacement>>,
+}
+
+/// The result of applying the suggestions to a file.
+pub struct FixedFile {
+    pub fixed: String,
+    /// The fixed parts of the file, as a unified diff.
+    pub diff: String,
+    pub applied: usize,
+    /// The number of suggestions that overlapped another suggestion.
+    pub skipped: usize,
+}
+
+pub struct SuggestionApplier {
+    /// The lints whose suggestions are applied, or `None` to apply the suggestions of all
+    /// diagnostics.
+    lints: Option<FxHashSet<String>>,
+    files: FxIndexMap<PathBuf, FileSuggestions>,
+}
+
+impl SuggestionApplier {
+    /// Creates an applier for the suggestions of the given lints, or of all diagnostics if
+    /// `lints` is empty.
+    pub fn new(lints: &[String]) -> SuggestionApplier {
+        let lints = (!lints.is_empty()).then(|| lints.iter().cloned().collect());
+        SuggestionApplier { lints, files: FxIndexMap::default() }
+    }
+
+    pub(crate) fn collect(&mut self, diag: &DiagInner, sm: &SourceMap) {
+        if let Some(lints) = &self.lints
+            && !diag.is_lint.as_ref().is_some_and(|lint| lints.contains(&lint.name))
+        {
+            return;
+        }
+        let Ok(suggestions) = &diag.suggestions else {
+            return;
+        };
+        for suggestion in suggestions {
+            if suggestion.applicability != Applicability::MachineApplicable {
+                continue;
+            }
+            // Several substitutions are alternatives the user has to choose from.
+            if let [substitution] = &suggestion.substitutions[..] {
+                self.collect_substitution(substitution, sm);
+            }
+        }
+    }
+
+    fn collect_substitution(&mut self, substitution: &Substitution, sm: &SourceMap) -> Option<()> {
+        let mut file: Option<Lrc<SourceFile>> = None;
+        let mut replacements = Vec::with_capacity(substitution.parts.len());
+        for part in &substitution.parts {
+            if part.span.from_expansion() {
+                return None;
+            }
+            let sf = sm.lookup_source_file(part.span.lo());
+            if file.as_ref().is_some_and(|file| !Lrc::ptr_eq(file, &sf))
+                || part.span.hi() > sf.end_position()
+            {
+                return None;
+            }
+            let lo = sf.original_relative_byte_pos(part.span.lo()).to_usize();
+            let hi = sf.original_relative_byte_pos(part.span.hi()).to_usize();
+            replacements.push(Replacement { range: lo..hi, snippet: part.snippet.clone() });
+            file = Some(sf);
+        }
+
+        // Only the files of the local crate that were read from disk can be rewritten.
+        let sf = file?;
+        let FileName::Real(name) = &sf.name else {
+            return None;
+        };
+        let path = name.local_path()?;
+        sf.src.as_ref()?;
+
+        replacements.sort_by_key(|replacement| (replacement.range.start, replacement.range.end));
+        self.files
+            .entry(path.to_owned())
+            .or_insert_with(|| FileSuggestions { src_hash: sf.src_hash, suggestions: Vec::new() })
+            .suggestions
+            .push(replacements);
+        Some(())
+    }
+
+    /// The files suggestions were collected for.
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.files.keys().map(|path| &**path)
+    }
+
+    /// Applies the suggestions for `path` to `src`, the current contents of the file. Returns
+    /// `None` if the file changed since it was compiled.
+    pub fn fix(&self, path: &Path, src: &str) -> Option<FixedFile> {
+        let file = &self.files[path];
+        if !file.src_hash.matches(src) {
+            return None;
+        }
+
+        let mut accepted: Vec<Replacement> = Vec::new();
+        let mut applied = 0;
+        let mut skipped = 0;
+        for suggestion in &file.suggestions {
+            if suggestion.iter().all(|replacement| accepted.contains(replacement)) {
+                // The same suggestion was emitted twice.
+                continue;
+            }
+            if suggestion.iter().any(|replacement| {
+                accepted.iter().any(|accepted| replacement.conflicts_with(accepted))
+            }) {
+                skipped += 1;
+                continue;
+            }
+            for replacement in suggestion {
+                if !accepted.contains(replacement) {
+                    accepted.push(replacement.clone());
+                }
+            }
+            applied += 1;
+        }
+        accepted.sort_by_key(|replacement| replacement.range.start);
+
+        let fixed = apply_replacements(src, 0, &accepted);
+        let diff = unified_diff(path, src, &accepted);
+        Some(FixedFile { fixed, diff, applied, skipped })
+    }
+}
+
+/// Applies the sorted, non-overlapping `replacements` to `src`, which starts at byte `offset` of
+/// the file the replacements refer to.
+fn apply_replacements(src: &str, offset: usize, replacements: &[Replacement]) -> String {
+    let mut fixed = String::with_capacity(src.len());
+    let mut pos = offset;
+    for Replacement { range, snippet } in replacements {
+        fixed.push_str(&src[pos - offset..range.start - offset]);
+        fixed.push_str(snippet);
+        pos = range.end;
+    }
+    fixed.push_str(&src[pos - offset..]);
+    fixed
+}
+
+/// Renders the changes the sorted, non-overlapping `replacements` make to `src` as a unified
+/// diff, with three lines of context.
+fn unified_diff(path: &Path, src: &str, replacements: &[Replacement]) -> String {
+    const CONTEXT: usize = 3;
+
+    // An empty file still has one (empty) line that can be inserted into.
+    let lines: Vec<&str> =
+        if src.is_empty() { vec![""] } else { src.split_inclusive('\n').collect() };
+    let mut line_starts = Vec::with_capacity(lines.len() + 1);
+    let mut start = 0;
+    for line in &lines {
+        line_starts.push(start);
+        start += line.len();
+    }
+    line_starts.push(src.len());
+    let line_of = |pos: usize| {
+        (line_starts.partition_point(|&start| start <= pos) - 1).min(lines.len().saturating_sub(1))
+    };
+
+    // Group the replacements into the lines they change, and those into hunks, merging hunks
+    // whose context would overlap.
+    let mut hunks: Vec<Vec<(Range<usize>, Vec<Replacement>)>> = Vec::new();
+    for replacement in replacements {
+        let first = line_of(replacement.range.start);
+        let last = line_of(replacement.range.end.saturating_sub(1).max(replacement.range.start));
+        match hunks.last_mut().and_then(|hunk| hunk.last_mut()) {
+            Some((changed, group)) if first < changed.end => {
+                changed.end = changed.end.max(last + 1);
+                group.push(replacement.clone());
+                continue;
+            }
+            Some((changed, _)) if first <= changed.end + 2 * CONTEXT => {
+                hunks.last_mut().unwrap().push((first..last + 1, vec![replacement.clone()]));
+            }
+            _ => hunks.push(vec![(first..last + 1, vec![replacement.clone()])]),
+        }
+    }
+
+    let path = path.display();
+    let mut diff = format!("--- a/{path}\n+++ b/{path}\n");
+    let mut delta = 0isize;
+    for hunk in hunks {
+        let start = hunk[0].0.start.saturating_sub(CONTEXT);
+        let end = (hunk[hunk.len() - 1].0.end + CONTEXT).min(lines.len());
+        let mut body = String::new();
+        let (mut old_len, mut new_len) = (0, 0);
+        let mut push_line = |prefix: char, line: &str| {
+            body.push(prefix);
+            body.push_str(line);
+            if !line.ends_with('\n') {
+                body.push_str("\n\\ No newline at end of file\n");
+            }
+        };
+        let mut pos = start;
+        for (changed, group) in &hunk {
+            lines[pos..changed.start].iter().for_each(|line| push_line(' ', line));
+            let old = &src[line_starts[changed.start]..line_starts[changed.end]];
+            let new = apply_replacements(old, line_starts[changed.start], group);
+            old.split_inclusive('\n').for_each(|line| push_line('-', line));
+            new.split_inclusive('\n').for_each(|line| push_line('+', line));
+            old_len += changed.start - pos + old.split_inclusive('\n').count();
+            new_len += changed.start - pos + new.split_inclusive('\n').count();
+            pos = changed.end;
+        }
+        lines[pos..end].iter().for_each(|line| push_line(' ', line));
+        old_len += end - pos;
+        new_len += end - pos;
+
+        // Empty ranges are numbered by the line before them.
+        let old_start = start + usize::from(old_len > 0);
+        let new_start = start as isize + delta + isize::from(new_len > 0);
+        writeln!(diff, "@@ -{old_start},{old_len} +{new_start},{new_len} @@").unwrap();
+        diff.push_str(&body);
+        delta += new_len as isize - old_len as isize;
+    }
+    diff
+}
@@ -0,0 +1,166 @@
+use std::path::Path;
+
+use rustc_span::source_map::FilePathMapping;
+use rustc_span::{BytePos, SourceFileHashAlgorithm, Span};
+
+use super::*;
+use crate::emitter::Emitter;
+use crate::translation::Translate;
+use crate::{DiagCtxt, FluentBundle, LazyFallbackBundle};
+
+/// Drops the diagnostics, only the suggestion applier sees them.
+struct NullEmitter {
+    sm: Lrc<SourceMap>,
+    fallback_bundle: LazyFallbackBundle,
+}
+
+impl Translate for NullEmitter {
+    fn fluent_bundle(&self) -> Option<&Lrc<FluentBundle>> {
+        None
+    }
+
+    fn fallback_fluent_bundle(&self) -> &FluentBundle {
+        &self.fallback_bundle
+    }
+}
+
+impl Emitter for NullEmitter {
+    fn emit_diagnostic(&mut self, _: DiagInner) {}
+
+    fn source_map(&self) -> Option<&Lrc<SourceMap>> {
+        Some(&self.sm)
+    }
+}
+
+/// Emits an `unused_mut` warning with a suggestion of the given applicability for each of the
+/// `suggestions` of `code`, and returns the applier the suggestions were collected by.
+fn collect(
+    code: &str,
+    lints: &[String],
+    suggestions: &[(u32, u32, &str, Applicability)],
+) -> SuggestionApplier {
+    rustc_span::create_default_session_globals_then(|| {
+        let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+        sm.new_source_file(Path::new("test.rs").to_owned().into(), code.to_owned());
+        let fallback_bundle =
+            crate::fallback_fluent_bundle(vec![crate::DEFAULT_LOCALE_RESOURCE], false);
+
+        let emitter = NullEmitter { sm, fallback_bundle };
+        let dcx =
+            DiagCtxt::new(Box::new(emitter)).with_suggestion_applier(SuggestionApplier::new(lints));
+        for &(lo, hi, snippet, applicability) in suggestions {
+            let span = Span::with_root_ctxt(BytePos(lo), BytePos(hi));
+            let mut diag =
+                dcx.handle().struct_span_warn(span, "variable does not need to be mutable");
+            diag.is_lint("unused_mut".to_owned(), false);
+            diag.span_suggestion(span, "remove this `mut`", snippet, applicability);
+            diag.emit();
+        }
+        dcx.take_suggestion_applier().unwrap()
+    })
+}
+
+/// Creates an applier with the given suggestions for `test.rs`, as if it contained `src`.
+fn applier(src: &str, suggestions: &[&[(Range<usize>, &str)]]) -> SuggestionApplier {
+    let mut applier = SuggestionApplier::new(&[]);
+    let suggestions = suggestions
+        .iter()
+        .map(|replacements| {
+            replacements
+                .iter()
+                .map(|(range, snippet)| Replacement {
+                    range: range.clone(),
+                    snippet: snippet.to_string(),
+                })
+                .collect()
+        })
+        .collect();
+    let src_hash = SourceFileHash::new(SourceFileHashAlgorithm::Sha256, src);
+    applier.files.insert(PathBuf::from("test.rs"), FileSuggestions { src_hash, suggestions });
+    applier
+}
+
+const CODE: &str = "fn main() {\n    let mut x = 1;\n    let mut y = 2;\n}\n";
+
+#[test]
+fn machine_applicable_suggestions() {
+    let applier = collect(
+        CODE,
+        &[],
+        &[
+            (20, 24, "", Applicability::MachineApplicable),
+            (39, 43, "", Applicability::MaybeIncorrect),
+        ],
+    );
+    let fixed = applier.fix(Path::new("test.rs"), CODE).unwrap();
+    assert_eq!(fixed.fixed, "fn main() {\n    let x = 1;\n    let mut y = 2;\n}\n");
+    assert_eq!(fixed.applied, 1);
+    assert_eq!(fixed.skipped, 0);
+}
+
+#[test]
+fn lint_filter() {
+    let suggestions = &[(20, 24, "", Applicability::MachineApplicable)];
+    let applier = collect(CODE, &["unused_mut".to_owned()], suggestions);
+    assert_eq!(applier.paths().collect::<Vec<_>>(), [Path::new("test.rs")]);
+    let applier = collect(CODE, &["unused_variables".to_owned()], suggestions);
+    assert_eq!(applier.paths().count(), 0);
+}
+
+#[test]
+fn changed_file() {
+    let applier = collect(CODE, &[], &[(20, 24, "", Applicability::MachineApplicable)]);
+    assert!(applier.fix(Path::new("test.rs"), "fn main() {}\n").is_none());
+}
+
+#[test]
+fn overlapping_suggestions() {
+    let src = "let mut x = 1;\n";
+    let applier = applier(
+        src,
+        &[
+            &[(4..8, "")],
+            &[(4..9, "_x")],
+            &[(8..8, "_")],
+            &[(12..13, "2"), (4..4, "mut ")],
+            &[(12..13, "3")],
+        ],
+    );
+    let fixed = applier.fix(Path::new("test.rs"), src).unwrap();
+    assert_eq!(fixed.fixed, "let _x = 3;\n");
+    assert_eq!(fixed.applied, 3);
+    assert_eq!(fixed.skipped, 2);
+}
+
+#[test]
+fn duplicate_suggestions() {
+    let src = "let mut x = 1;\n";
+    let applier = applier(src, &[&[(4..8, "")], &[(4..8, "")], &[(12..13, "2"), (4..8, "")]]);
+    let fixed = applier.fix(Path::new("test.rs"), src).unwrap();
+    assert_eq!(fixed.fixed, "let x = 2;\n");
+    assert_eq!(fixed.applied, 2);
+    assert_eq!(fixed.skipped, 0);
+}
+
+#[test]
+fn diff_of_empty_file() {
+    let applier = applier("", &[&[(0..0, "fn main() {}\n")]]);
+    let fixed = applier.fix(Path::new("test.rs"), "").unwrap();
+    assert_eq!(fixed.diff, "--- a/test.rs\n+++ b/test.rs\n@@ -0,0 +1,1 @@\n+fn main() {}\n");
+}
+
+#[test]
+fn diff() {
+    let src = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\nk\nl\nm\nn\no\np";
+    let applier =
+        applier(src, &[&[(2..3, "B")], &[(6..8, "")], &[(28..28, "x\n")], &[(30..31, "P")]]);
+    let fixed = applier.fix(Path::new("test.rs"), src).unwrap();
+    assert_eq!(fixed.fixed, "a\nB\nc\ne\nf\ng\nh\ni\nj\nk\nl\nm\nn\nx\no\nP");
+    assert_eq!(
+        fixed.diff,
+        "--- a/test.rs\n+++ b/test.rs\n\
+         @@ -1,7 +1,6 @@\n a\n-b\n+B\n c\n-d\n e\n f\n g\n\
+         @@ -12,5 +11,6 @@\n l\n m\n n\n-o\n+x\n+o\n-p\n\\ No newline at end of file\n\
+         +P\n\\ No newline at end of file\n"
+    );
+}
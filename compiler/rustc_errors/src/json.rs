@@ -6,8 +6,9 @@
 //!
 //! The format of the JSON output should be considered *unstable*. For now the
 //! structs at the end of this file (Diagnostic*) specify the error format.
-
-// FIXME: spec the JSON output properly.
+//!
+//! With `-Z json-diagnostic-version`, diagnostics carry a `$version` field and their
+//! shape is described by the JSON Schema derived from these structs in the `schema` module.
 
 use std::error::Report;
 use std::io::{self, Write};
@@ -36,9 +37,13 @@
     CodeSuggestion, FluentBundle, LazyFallbackBundle, MultiSpan, SpanLabel, Subdiag, TerminalUrl,
 };
 
+mod schema;
 #[cfg(test)]
 mod tests;
 
+use schema::{json_schema_structs, one_based};
+pub use schema::{diagnostic_schema, JSON_DIAGNOSTIC_VERSIONS};
+
 #[derive(Setters)]
 pub struct JsonEmitter {
     #[setters(skip)]
@@ -59,6 +64,8 @@ pub struct JsonEmitter {
     macro_backtrace: bool,
     track_diagnostics: bool,
     terminal_url: TerminalUrl,
+    /// The version of the diagnostic schema to emit, see `JSON_DIAGNOSTIC_VERSIONS`.
+    diagnostic_version: Option<u32>,
 }
 
 impl JsonEmitter {
@@ -83,6 +90,7 @@ pub fn new(
             macro_backtrace: false,
             track_diagnostics: false,
             terminal_url: TerminalUrl::No,
+            diagnostic_version: None,
         }
     }
 
@@ -100,7 +108,13 @@ fn emit(&mut self, val: EmitTyped<'_>) -> io::Result<()> {
 #[derive(Serialize)]
 #[serde(tag = "$message_type", rename_all = "snake_case")]
 enum EmitTyped<'a> {
-    Diagnostic(Diagnostic),
+    Diagnostic {
+        /// The version of the diagnostic schema, if one was requested.
+        #[serde(rename = "$version", skip_serializing_if = "Option::is_none")]
+        version: Option<u32>,
+        #[serde(flatten)]
+        diagnostic: Diagnostic,
+    },
     Artifact(ArtifactNotification<'a>),
     FutureIncompat(FutureIncompatReport<'a>),
     UnusedExtern(UnusedExterns<'a>),
@@ -118,8 +132,9 @@ fn fallback_fluent_bundle(&self) -> &FluentBundle {
 
 impl Emitter for JsonEmitter {
     fn emit_diagnostic(&mut self, diag: crate::DiagInner) {
-        let data = Diagnostic::from_errors_diagnostic(diag, self);
-        let result = self.emit(EmitTyped::Diagnostic(data));
+        let diagnostic = Diagnostic::from_errors_diagnostic(diag, self);
+        let version = self.diagnostic_version;
+        let result = self.emit(EmitTyped::Diagnostic { version, diagnostic });
         if let Err(e) = result {
             panic!("failed to print diagnostics: {e:?}");
         }
@@ -146,9 +161,10 @@ fn emit_future_breakage_report(&mut self, diags: Vec<crate::DiagInner>) {
                     diag.level = crate::Level::Warning;
                 }
                 FutureBreakageItem {
-                    diagnostic: EmitTyped::Diagnostic(Diagnostic::from_errors_diagnostic(
-                        diag, self,
-                    )),
+                    diagnostic: EmitTyped::Diagnostic {
+                        version: None,
+                        diagnostic: Diagnostic::from_errors_diagnostic(diag, self),
+                    },
                 }
             })
             .collect();
@@ -179,78 +195,85 @@ fn should_show_explain(&self) -> bool {
 
 // The following data types are provided just for serialisation.
 
-#[derive(Serialize)]
-struct Diagnostic {
-    /// The primary error message.
-    message: String,
-    code: Option<DiagnosticCode>,
-    /// "error: internal compiler error", "error", "warning", "note", "help".
-    level: &'static str,
-    spans: Vec<DiagnosticSpan>,
-    /// Associated diagnostic messages.
-    children: Vec<Diagnostic>,
-    /// The message as rustc would render it.
-    rendered: Option<String>,
-}
+json_schema_structs! {
+    #[derive(Serialize)]
+    struct Diagnostic {
+        /// The primary error message.
+        message: String,
+        code: Option<DiagnosticCode>,
+        /// "error: internal compiler error", "error", "warning", "note", "help".
+        level: &'static str => serde_json::json!({
+            "enum": [
+                "error: internal compiler error", "error",
+                "warning", "note", "help", "failure-note",
+            ],
+        }),
+        spans: Vec<DiagnosticSpan>,
+        /// Associated diagnostic messages.
+        children: Vec<Diagnostic>,
+        /// The message as rustc would render it.
+        rendered: Option<String>,
+    }
 
-#[derive(Serialize)]
-struct DiagnosticSpan {
-    file_name: String,
-    byte_start: u32,
-    byte_end: u32,
-    /// 1-based.
-    line_start: usize,
-    line_end: usize,
-    /// 1-based, character offset.
-    column_start: usize,
-    column_end: usize,
-    /// Is this a "primary" span -- meaning the point, or one of the points,
-    /// where the error occurred?
-    is_primary: bool,
-    /// Source text from the start of line_start to the end of line_end.
-    text: Vec<DiagnosticSpanLine>,
-    /// Label that should be placed at this location (if any)
-    label: Option<String>,
-    /// If we are suggesting a replacement, this will contain text
-    /// that should be sliced in atop this span.
-    suggested_replacement: Option<String>,
-    /// If the suggestion is approximate
-    suggestion_applicability: Option<Applicability>,
-    /// Macro invocations that created the code at this span, if any.
-    expansion: Option<Box<DiagnosticSpanMacroExpansion>>,
-}
+    #[derive(Serialize)]
+    struct DiagnosticSpan {
+        file_name: String,
+        byte_start: u32,
+        byte_end: u32,
+        /// 1-based.
+        line_start: usize => one_based(),
+        line_end: usize => one_based(),
+        /// 1-based, character offset.
+        column_start: usize => one_based(),
+        column_end: usize => one_based(),
+        /// Is this a "primary" span -- meaning the point, or one of the points,
+        /// where the error occurred?
+        is_primary: bool,
+        /// Source text from the start of line_start to the end of line_end.
+        text: Vec<DiagnosticSpanLine>,
+        /// Label that should be placed at this location (if any)
+        label: Option<String>,
+        /// If we are suggesting a replacement, this will contain text
+        /// that should be sliced in atop this span.
+        suggested_replacement: Option<String>,
+        /// If the suggestion is approximate
+        suggestion_applicability: Option<Applicability>,
+        /// Macro invocations that created the code at this span, if any.
+        expansion: Option<Box<DiagnosticSpanMacroExpansion>>,
+    }
 
-#[derive(Serialize)]
-struct DiagnosticSpanLine {
-    text: String,
+    #[derive(Serialize)]
+    struct DiagnosticSpanLine {
+        text: String,
 
-    /// 1-based, character offset in self.text.
-    highlight_start: usize,
+        /// 1-based, character offset in self.text.
+        highlight_start: usize => one_based(),
 
-    highlight_end: usize,
-}
+        highlight_end: usize => one_based(),
+    }
 
-#[derive(Serialize)]
-struct DiagnosticSpanMacroExpansion {
-    /// span where macro was applied to generate this code; note that
-    /// this may itself derive from a macro (if
-    /// `span.expansion.is_some()`)
-    span: DiagnosticSpan,
+    #[derive(Serialize)]
+    struct DiagnosticSpanMacroExpansion {
+        /// span where macro was applied to generate this code; note that
+        /// this may itself derive from a macro (if
+        /// `span.expansion.is_some()`)
+        span: DiagnosticSpan,
 
-    /// name of macro that was applied (e.g., "foo!" or "#[derive(Eq)]")
-    macro_decl_name: String,
+        /// name of macro that was applied (e.g., "foo!" or "#[derive(Eq)]")
+        macro_decl_name: String,
 
-    /// span where macro was defined (if known)
-    def_site_span: DiagnosticSpan,
-}
+        /// span where macro was defined (if known)
+        def_site_span: DiagnosticSpan,
+    }
 
-#[derive(Serialize)]
-struct DiagnosticCode {
-    /// The error code (e.g. "E1234"), if the diagnostic has one. Or the lint
-    /// name, if it's a lint without an error code.
-    code: String,
-    /// An explanation for the code.
-    explanation: Option<&'static str>,
+    #[derive(Serialize)]
+    struct DiagnosticCode {
+        /// The error code (e.g. "E1234"), if the diagnostic has one. Or the lint
+        /// name, if it's a lint without an error code.
+        code: String,
+        /// An explanation for the code.
+        explanation: Option<&'static str>,
+    }
 }
 
 #[derive(Serialize)]
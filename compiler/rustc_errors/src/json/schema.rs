@@ -0,0 +1,192 @@
+//! The JSON Schema of the diagnostics emitted with `-Z json-diagnostic-version`.
+//!
+//! The schema is derived from the `Diagnostic*` structs of the parent module, which are declared
+//! with the `json_schema_structs!` macro below: every field becomes a property whose schema is
+//! that of its type, unless the field overrides it with `=> schema` to constrain its values
+//! further. The schema is checked in next to this file as `diagnostic-v{version}.schema.json`,
+//! and the tests in `json/tests.rs` make sure that the checked-in file is up to date. Run the
+//! tests with `--bless` after changing the structs; changing the shape of an existing version is
+//! not allowed, add a new version instead.
+
+use rustc_lint_defs::Applicability;
+use serde_json::{json, Map, Value};
+
+use super::Diagnostic;
+
+/// The versions of the JSON diagnostic schema that `-Z json-diagnostic-version` accepts.
+pub const JSON_DIAGNOSTIC_VERSIONS: &[u32] = &[1];
+
+/// Returns the JSON Schema of top-level diagnostics in the given `version`.
+pub fn diagnostic_schema(version: u32) -> Value {
+    assert!(JSON_DIAGNOSTIC_VERSIONS.contains(&version), "unknown diagnostic version {version}");
+
+    let mut defs = Map::new();
+    Diagnostic::add_defs(&mut defs);
+
+    // Top-level diagnostics are the only ones with a message type and a version.
+    let mut schema = defs["Diagnostic"].clone();
+    schema["properties"]["$message_type"] = json!({ "const": "diagnostic" });
+    schema["properties"]["$version"] = json!({ "const": version });
+    let Value::Array(required) = &mut schema["required"] else { unreachable!() };
+    required.splice(0..0, [json!("$message_type"), json!("$version")]);
+
+    schema["$schema"] = json!("https://json-schema.org/draft/2020-12/schema");
+    schema["title"] = json!(format!("rustc JSON diagnostic, version {version}"));
+    schema["$defs"] = Value::Object(defs);
+    schema
+}
+
+/// A type whose values are serialized as described by a JSON Schema.
+pub(super) trait JsonSchema {
+    /// The schema of the values, referring to the schemas of structs in `$defs`.
+    fn schema() -> Value;
+
+    /// Adds the schemas of the structs that the schema of the values refers to to `defs`.
+    fn add_defs(_defs: &mut Map<String, Value>) {}
+}
+
+/// Declares structs and derives their schema, in which all fields are required. The fields must
+/// be serialized under their own name.
+macro_rules! json_schema_structs {
+    (@field $ty:ty) => {
+        <$ty as $crate::json::schema::JsonSchema>::schema()
+    };
+    (@field $ty:ty => $schema:expr) => {
+        $schema
+    };
+    ($(
+        $(#[$attr:meta])*
+        struct $name:ident {
+            $(
+                $(#[$field_attr:meta])*
+                $field:ident: $ty:ty $(=> $schema:expr)?,
+            )*
+        }
+    )*) => {$(
+        $(#[$attr])*
+        struct $name {
+            $(
+                $(#[$field_attr])*
+                $field: $ty,
+            )*
+        }
+
+        impl $crate::json::schema::JsonSchema for $name {
+            fn schema() -> ::serde_json::Value {
+                $crate::json::schema::def(stringify!($name))
+            }
+
+            fn add_defs(defs: &mut ::serde_json::Map<String, ::serde_json::Value>) {
+                // Recursive structs refer to their own definition.
+                if defs.contains_key(stringify!($name)) {
+                    return;
+                }
+                defs.insert(stringify!($name).to_owned(), ::serde_json::Value::Null);
+
+                let mut properties = ::serde_json::Map::new();
+                $(
+                    <$ty as $crate::json::schema::JsonSchema>::add_defs(defs);
+                    properties.insert(
+                        stringify!($field).to_owned(),
+                        $crate::json::schema::json_schema_structs!(@field $ty $(=> $schema)?),
+                    );
+                )*
+                let schema = ::serde_json::json!({
+                    "type": "object",
+                    "required": [$(stringify!($field)),*],
+                    "additionalProperties": false,
+                    "properties": properties,
+                });
+                defs.insert(stringify!($name).to_owned(), schema);
+            }
+        }
+    )*};
+}
+
+pub(super) use json_schema_structs;
+
+pub(super) fn def(name: &str) -> Value {
+    json!({ "$ref": format!("#/$defs/{name}") })
+}
+
+/// The schema of integers that are at least 1, like line and column numbers.
+pub(super) fn one_based() -> Value {
+    json!({ "type": "integer", "minimum": 1 })
+}
+
+impl JsonSchema for String {
+    fn schema() -> Value {
+        json!({ "type": "string" })
+    }
+}
+
+impl JsonSchema for &str {
+    fn schema() -> Value {
+        json!({ "type": "string" })
+    }
+}
+
+impl JsonSchema for bool {
+    fn schema() -> Value {
+        json!({ "type": "boolean" })
+    }
+}
+
+impl JsonSchema for u32 {
+    fn schema() -> Value {
+        json!({ "type": "integer", "minimum": 0 })
+    }
+}
+
+impl JsonSchema for usize {
+    fn schema() -> Value {
+        json!({ "type": "integer", "minimum": 0 })
+    }
+}
+
+impl<T: JsonSchema> JsonSchema for Option<T> {
+    fn schema() -> Value {
+        json!({ "oneOf": [{ "type": "null" }, T::schema()] })
+    }
+
+    fn add_defs(defs: &mut Map<String, Value>) {
+        T::add_defs(defs)
+    }
+}
+
+impl<T: JsonSchema> JsonSchema for Vec<T> {
+    fn schema() -> Value {
+        json!({ "type": "array", "items": T::schema() })
+    }
+
+    fn add_defs(defs: &mut Map<String, Value>) {
+        T::add_defs(defs)
+    }
+}
+
+impl<T: JsonSchema> JsonSchema for Box<T> {
+    fn schema() -> Value {
+        T::schema()
+    }
+
+    fn add_defs(defs: &mut Map<String, Value>) {
+        T::add_defs(defs)
+    }
+}
+
+impl JsonSchema for Applicability {
+    fn schema() -> Value {
+        use Applicability::*;
+
+        // The names are the serialized ones, and the exhaustive `match` makes sure that a new
+        // variant is listed here as well.
+        let variants = [MachineApplicable, MaybeIncorrect, HasPlaceholders, Unspecified].map(
+            |applicability| match applicability {
+                MachineApplicable | MaybeIncorrect | HasPlaceholders | Unspecified => {
+                    serde_json::to_value(applicability).unwrap()
+                }
+            },
+        );
+        json!({ "enum": variants })
+    }
+}
@@ -1,8 +1,10 @@
+use std::path::PathBuf;
 use std::str;
 
 use rustc_span::source_map::FilePathMapping;
 use rustc_span::BytePos;
 use serde::Deserialize;
+use serde_json::Value;
 
 use super::*;
 use crate::DiagCtxt;
@@ -193,3 +195,205 @@ fn span_after_crlf() {
         },
     )
 }
+
+/// Checks `value` against the subset of JSON Schema used by `diagnostic_schema`.
+fn validate(schema: &Value, defs: &Value, value: &Value, path: &str) -> Result<(), String> {
+    if let Some(reference) = schema.get("$ref") {
+        let name = reference.as_str().unwrap().strip_prefix("#/$defs/").unwrap();
+        return validate(&defs[name], defs, value, path);
+    }
+    if let Some(alternatives) = schema.get("oneOf") {
+        let alternatives = alternatives.as_array().unwrap();
+        let matching = alternatives.iter().filter(|s| validate(s, defs, value, path).is_ok());
+        return match matching.count() {
+            1 => Ok(()),
+            n => Err(format!("{path}: {value} matches {n} alternatives of `oneOf`")),
+        };
+    }
+    if let Some(expected) = schema.get("const")
+        && value != expected
+    {
+        return Err(format!("{path}: expected {expected}, found {value}"));
+    }
+    if let Some(variants) = schema.get("enum")
+        && !variants.as_array().unwrap().contains(value)
+    {
+        return Err(format!("{path}: unexpected {value}"));
+    }
+    let matches_type = match schema.get("type").and_then(Value::as_str) {
+        None => true,
+        Some("null") => value.is_null(),
+        Some("string") => value.is_string(),
+        Some("boolean") => value.is_boolean(),
+        Some("integer") => value.as_u64().is_some_and(|n| {
+            schema.get("minimum").map_or(true, |minimum| n >= minimum.as_u64().unwrap())
+        }),
+        Some("array") => {
+            let Some(items) = value.as_array() else {
+                return Err(format!("{path}: expected array, found {value}"));
+            };
+            for (i, item) in items.iter().enumerate() {
+                validate(&schema["items"], defs, item, &format!("{path}[{i}]"))?;
+            }
+            true
+        }
+        Some("object") => {
+            let Some(object) = value.as_object() else {
+                return Err(format!("{path}: expected object, found {value}"));
+            };
+            for required in schema["required"].as_array().unwrap() {
+                let required = required.as_str().unwrap();
+                if !object.contains_key(required) {
+                    return Err(format!("{path}: missing field `{required}`"));
+                }
+            }
+            for (key, field) in object {
+                let Some(field_schema) = schema["properties"].get(key) else {
+                    return Err(format!("{path}: field `{key}` is not in the schema"));
+                };
+                validate(field_schema, defs, field, &format!("{path}.{key}"))?;
+            }
+            true
+        }
+        Some(ty) => panic!("unsupported schema type `{ty}`"),
+    };
+    if matches_type { Ok(()) } else { Err(format!("{path}: {value} does not match {schema}")) }
+}
+
+#[test]
+fn schema_is_up_to_date() {
+    // Capture `--bless` when run via ./x
+    let bless = std::env::var_os("RUSTC_BLESS").is_some_and(|v| v != "0");
+    for &version in JSON_DIAGNOSTIC_VERSIONS {
+        let mut path = PathBuf::new();
+        path.extend([env!("CARGO_MANIFEST_DIR"), "src", "json"]);
+        path.push(format!("diagnostic-v{version}.schema.json"));
+        let schema = diagnostic_schema(version);
+
+        if bless {
+            let mut contents = serde_json::to_string_pretty(&schema).unwrap();
+            contents.push('\n');
+            std::fs::write(&path, contents).unwrap();
+            eprintln!("blessed schema at {}", path.display());
+        } else {
+            let checked_in: Value =
+                serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+            assert!(
+                checked_in == schema,
+                "{} is out of date, rerun the tests with `--bless`",
+                path.display()
+            );
+        }
+    }
+}
+
+#[test]
+fn diagnostic_conforms_to_schema() {
+    rustc_span::create_default_session_globals_then(|| {
+        let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+        sm.new_source_file(Path::new("test.rs").to_owned().into(), "fn main() {}\n".to_owned());
+        let fallback_bundle =
+            crate::fallback_fluent_bundle(vec![crate::DEFAULT_LOCALE_RESOURCE], false);
+
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let je = JsonEmitter::new(
+            Box::new(Shared { data: output.clone() }),
+            sm,
+            fallback_bundle,
+            false, // pretty
+            HumanReadableErrorType::Short(ColorConfig::Never),
+        )
+        .diagnostic_version(Some(1));
+
+        let span = Span::with_root_ctxt(BytePos(3), BytePos(7));
+        DiagCtxt::new(Box::new(je))
+            .handle()
+            .struct_span_err(span, "foo")
+            .with_span_label(span, "bar")
+            .with_span_note(span, "baz")
+            .with_span_suggestion(span, "qux", "main", Applicability::MachineApplicable)
+            .emit();
+
+        let bytes = output.lock().unwrap();
+        let diagnostic: Value = serde_json::from_str(str::from_utf8(&bytes).unwrap()).unwrap();
+        assert_eq!(diagnostic["$version"], 1);
+        let schema = diagnostic_schema(1);
+        if let Err(e) = validate(&schema, &schema["$defs"], &diagnostic, "$") {
+            panic!("emitted diagnostic does not conform to the schema: {e}");
+        }
+    })
+}
+
+/// Checks a diagnostic in which every `Option` is `Some` and every `Vec` is non-empty, so that
+/// every serialized field of every `Diagnostic*` struct is compared with the derived schema, in
+/// case serde serializes one differently from what its type says.
+#[test]
+fn schema_covers_every_field() {
+    fn span(
+        applicability: Applicability,
+        expansion: Option<Box<DiagnosticSpanMacroExpansion>>,
+    ) -> DiagnosticSpan {
+        DiagnosticSpan {
+            file_name: "test.rs".to_owned(),
+            byte_start: 3,
+            byte_end: 7,
+            line_start: 1,
+            line_end: 1,
+            column_start: 4,
+            column_end: 8,
+            is_primary: true,
+            text: vec![DiagnosticSpanLine {
+                text: "fn main() {}".to_owned(),
+                highlight_start: 4,
+                highlight_end: 8,
+            }],
+            label: Some("bar".to_owned()),
+            suggested_replacement: Some("main".to_owned()),
+            suggestion_applicability: Some(applicability),
+            expansion,
+        }
+    }
+
+    let expansion = || {
+        Some(Box::new(DiagnosticSpanMacroExpansion {
+            span: span(Applicability::Unspecified, None),
+            macro_decl_name: "foo!".to_owned(),
+            def_site_span: span(Applicability::Unspecified, None),
+        }))
+    };
+    let spans = || {
+        [
+            Applicability::MachineApplicable,
+            Applicability::MaybeIncorrect,
+            Applicability::HasPlaceholders,
+            Applicability::Unspecified,
+        ]
+        .into_iter()
+        .map(|applicability| span(applicability, expansion()))
+        .collect()
+    };
+    let code = || Some(DiagnosticCode { code: "E0001".to_owned(), explanation: Some("qux") });
+
+    let diagnostic = Diagnostic {
+        message: "foo".to_owned(),
+        code: code(),
+        level: "error",
+        spans: spans(),
+        children: vec![Diagnostic {
+            message: "baz".to_owned(),
+            code: code(),
+            level: "note",
+            spans: spans(),
+            children: vec![],
+            rendered: Some("note: baz".to_owned()),
+        }],
+        rendered: Some("error: foo".to_owned()),
+    };
+
+    let diagnostic = EmitTyped::Diagnostic { version: Some(1), diagnostic };
+    let diagnostic = serde_json::to_value(&diagnostic).unwrap();
+    let schema = diagnostic_schema(1);
+    if let Err(e) = validate(&schema, &schema["$defs"], &diagnostic, "$") {
+        panic!("the `Diagnostic*` structs do not match the schema: {e}");
+    }
+}
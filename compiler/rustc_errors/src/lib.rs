@@ -50,6 +50,7 @@
     IndicateAnonymousLifetime, SingleLabelManySpans,
 };
 pub use emitter::ColorConfig;
+use apply_suggestions::SuggestionApplier;
 use emitter::{is_case_difference, is_different, DynEmitter, Emitter};
 use registry::Registry;
 use rustc_data_structures::fx::{FxHashSet, FxIndexMap, FxIndexSet};
@@ -75,6 +76,7 @@
 use Level::*;
 
 pub mod annotate_snippet_emitter_writer;
+pub mod apply_suggestions;
 pub mod codes;
 mod diagnostic;
 mod diagnostic_impls;
@@ -84,6 +86,7 @@
 mod lock;
 pub mod markdown;
 pub mod registry;
+pub mod sarif;
 mod snippet;
 mod styled_buffer;
 #[cfg(test)]
@@ -520,6 +523,9 @@ struct DiagCtxtInner {
     /// The file where the ICE information is stored. This allows delayed_span_bug backtraces to be
     /// stored along side the main panic backtrace.
     ice_file: Option<PathBuf>,
+
+    /// Collects the suggestions to apply with `-Z apply-suggestions`.
+    suggestion_applier: Option<SuggestionApplier>,
 }
 
 /// A key denoting where from a diagnostic was stashed.
@@ -628,6 +634,11 @@ pub fn with_ice_file(mut self, ice_file: PathBuf) -> Self {
         self
     }
 
+    pub fn with_suggestion_applier(mut self, suggestion_applier: SuggestionApplier) -> Self {
+        self.inner.get_mut().suggestion_applier = Some(suggestion_applier);
+        self
+    }
+
     pub fn new(emitter: Box<DynEmitter>) -> Self {
         Self { inner: Lock::new(DiagCtxtInner::new(emitter)) }
     }
@@ -741,6 +752,7 @@ pub fn reset_err_count(&self) {
             unstable_expect_diagnostics,
             fulfilled_expectations,
             ice_file: _,
+            suggestion_applier: _,
         } = inner.deref_mut();
 
         // For the `Vec`s and `HashMap`s, we overwrite with an empty container to free the
@@ -1054,6 +1066,11 @@ pub fn emit_artifact_notification(&self, path: &Path, artifact_type: &str) {
         self.inner.borrow_mut().emitter.emit_artifact_notification(path, artifact_type);
     }
 
+    /// Takes the suggestions collected for `-Z apply-suggestions`.
+    pub fn take_suggestion_applier(&self) -> Option<SuggestionApplier> {
+        self.inner.borrow_mut().suggestion_applier.take()
+    }
+
     pub fn emit_future_breakage_report(&self) {
         let mut inner = self.inner.borrow_mut();
         let diags = std::mem::take(&mut inner.future_breakage_diagnostics);
@@ -1441,6 +1458,7 @@ fn new(emitter: Box<DynEmitter>) -> Self {
             unstable_expect_diagnostics: Vec::new(),
             fulfilled_expectations: Default::default(),
             ice_file: None,
+            suggestion_applier: None,
         }
     }
 
@@ -1619,6 +1637,11 @@ fn emit_diagnostic(
                 }
                 self.has_printed = true;
 
+                if let Some(suggestion_applier) = &mut self.suggestion_applier
+                    && let Some(sm) = self.emitter.source_map()
+                {
+                    suggestion_applier.collect(&diagnostic, sm);
+                }
                 self.emitter.emit_diagnostic(diagnostic);
             }
 
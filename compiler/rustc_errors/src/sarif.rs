@@ -0,0 +1,485 @@
+//! A SARIF emitter for errors.
+//!
+//! Unlike the JSON emitter, which writes one object per diagnostic as soon as it is
+//! emitted, this emitter collects every diagnostic of a compilation session and writes
+//! a single [SARIF 2.1.0] log when it is dropped, which is what code-scanning tools
+//! expect to consume.
+//!
+//! Error codes and lint names are reported as rules, primary spans as result locations,
+//! secondary spans and sub-diagnostics as related locations, and suggestions as fixes.
+//!
+//! [SARIF 2.1.0]: https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html
+
+use std::error::Report;
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use std::path::{self, Path};
+
+use derive_setters::Setters;
+use rustc_data_structures::fx::FxIndexMap;
+use rustc_data_structures::sync::{IntoDynSyncSend, Lrc};
+use rustc_error_messages::FluentArgs;
+use rustc_lint_defs::Applicability;
+use rustc_span::source_map::SourceMap;
+use rustc_span::Span;
+use serde::Serialize;
+
+use crate::diagnostic::IsLint;
+use crate::emitter::Emitter;
+use crate::registry::Registry;
+use crate::translation::{to_fluent_args, Translate};
+use crate::{CodeSuggestion, ErrCode, FluentBundle, LazyFallbackBundle, Level, MultiSpan, Subdiag};
+
+#[cfg(test)]
+mod tests;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+
+#[derive(Setters)]
+pub struct SarifEmitter {
+    #[setters(skip)]
+    dst: IntoDynSyncSend<Box<dyn Write + Send>>,
+    registry: Option<Registry>,
+    #[setters(skip)]
+    sm: Lrc<SourceMap>,
+    fluent_bundle: Option<Lrc<FluentBundle>>,
+    #[setters(skip)]
+    fallback_bundle: LazyFallbackBundle,
+    /// The version of the compiler, reported as the version of the tool driver.
+    tool_version: Option<String>,
+    /// Whether to write no log at all if no diagnostic was emitted, for the emitters of early
+    /// diagnostic contexts, which are followed by the one of the session.
+    skip_empty_log: bool,
+    /// Rules referenced by `results`, keyed by error code or lint name.
+    #[setters(skip)]
+    rules: FxIndexMap<String, SarifRule>,
+    #[setters(skip)]
+    results: Vec<SarifResult>,
+}
+
+impl SarifEmitter {
+    pub fn new(
+        dst: Box<dyn Write + Send>,
+        sm: Lrc<SourceMap>,
+        fallback_bundle: LazyFallbackBundle,
+    ) -> SarifEmitter {
+        SarifEmitter {
+            dst: IntoDynSyncSend(dst),
+            registry: None,
+            sm,
+            fluent_bundle: None,
+            fallback_bundle,
+            tool_version: None,
+            skip_empty_log: false,
+            rules: Default::default(),
+            results: Vec::new(),
+        }
+    }
+
+    /// Writes the SARIF log containing every result collected so far.
+    fn write_log(&mut self) -> io::Result<()> {
+        let log = SarifLog {
+            schema: SARIF_SCHEMA,
+            version: SARIF_VERSION,
+            runs: vec![SarifRun {
+                tool: SarifTool {
+                    driver: SarifDriver {
+                        name: "rustc",
+                        information_uri: "https://www.rust-lang.org/",
+                        version: self.tool_version.as_deref(),
+                        rules: self.rules.values().collect(),
+                    },
+                },
+                column_kind: "unicodeCodePoints",
+                results: &self.results,
+            }],
+        };
+        serde_json::to_writer_pretty(&mut *self.dst, &log)?;
+        self.dst.write_all(b"\n")?;
+        self.dst.flush()
+    }
+
+    /// Returns the index of the rule for `id`, registering it if this is its first use.
+    fn rule_index(&mut self, id: String, code: Option<ErrCode>) -> usize {
+        if let Some(index) = self.rules.get_index_of(&id) {
+            return index;
+        }
+        let explanation = code.and_then(|code| {
+            self.registry.as_ref().and_then(|registry| registry.try_find_description(code).ok())
+        });
+        let rule = SarifRule {
+            full_description: explanation.map(|text| SarifMessage { text: text.to_owned() }),
+            help_uri: code.map(|code| format!("https://doc.rust-lang.org/error_codes/{code}.html")),
+            id: id.clone(),
+        };
+        self.rules.insert_full(id, rule).0
+    }
+
+    fn result_from_diagnostic(&mut self, diag: crate::DiagInner) -> SarifResult {
+        let args = to_fluent_args(diag.args.iter());
+        let mut message = self.translate_messages(&diag.messages, &args).into_owned();
+
+        let (rule_id, rule_index) = if let Some(code) = diag.code {
+            let id = code.to_string();
+            (Some(id.clone()), Some(self.rule_index(id, Some(code))))
+        } else if let Some(IsLint { name, .. }) = &diag.is_lint {
+            (Some(name.clone()), Some(self.rule_index(name.clone(), None)))
+        } else {
+            (None, None)
+        };
+
+        let (locations, mut related_locations) = self.locations_from_multispan(&diag.span, &args);
+        for child in &diag.children {
+            self.add_sub_diagnostic(child, &args, &mut message, &mut related_locations);
+        }
+        let fixes = diag
+            .suggestions
+            .iter()
+            .flatten()
+            .flat_map(|sugg| self.fixes_from_suggestion(sugg, &args))
+            .collect();
+
+        SarifResult {
+            rule_id,
+            rule_index,
+            level: sarif_level(diag.level),
+            message: SarifMessage { text: message },
+            locations,
+            related_locations,
+            fixes,
+        }
+    }
+
+    /// Sub-diagnostics with spans become related locations of the parent result, the
+    /// others are appended to its message the same way the human emitter renders them.
+    fn add_sub_diagnostic(
+        &self,
+        subdiag: &Subdiag,
+        args: &FluentArgs<'_>,
+        message: &mut String,
+        related_locations: &mut Vec<SarifLocation>,
+    ) {
+        let sub_message = self.translate_messages(&subdiag.messages, args);
+        let labels = subdiag.span.span_labels();
+        if labels.is_empty() {
+            message.push_str(&format!("\n= {}: {sub_message}", subdiag.level.to_str()));
+            return;
+        }
+        for label in labels {
+            let text = match &label.label {
+                Some(label) => self.translate_message(label, args).map_err(Report::new).unwrap(),
+                None => sub_message.clone(),
+            };
+            if let Some(physical_location) = self.physical_location(label.span) {
+                related_locations.push(SarifLocation {
+                    physical_location,
+                    message: Some(SarifMessage {
+                        text: format!("{}: {text}", subdiag.level.to_str()),
+                    }),
+                });
+            }
+        }
+    }
+
+    /// Splits the labels of `msp` into primary and related locations.
+    fn locations_from_multispan(
+        &self,
+        msp: &MultiSpan,
+        args: &FluentArgs<'_>,
+    ) -> (Vec<SarifLocation>, Vec<SarifLocation>) {
+        let mut locations = vec![];
+        let mut related_locations = vec![];
+        for label in msp.span_labels() {
+            let Some(physical_location) = self.physical_location(label.span) else {
+                continue;
+            };
+            let message = label.label.as_ref().map(|label| SarifMessage {
+                text: self
+                    .translate_message(label, args)
+                    .map_err(Report::new)
+                    .unwrap()
+                    .into_owned(),
+            });
+            let location = SarifLocation { physical_location, message };
+            if label.is_primary {
+                locations.push(location);
+            } else {
+                related_locations.push(location);
+            }
+        }
+        (locations, related_locations)
+    }
+
+    /// Each substitution of a suggestion is an alternative fix.
+    fn fixes_from_suggestion(&self, sugg: &CodeSuggestion, args: &FluentArgs<'_>) -> Vec<SarifFix> {
+        let description =
+            self.translate_message(&sugg.msg, args).map_err(Report::new).unwrap().into_owned();
+        sugg.substitutions
+            .iter()
+            .map(|substitution| {
+                let mut changes: FxIndexMap<String, Vec<SarifReplacement>> = Default::default();
+                for part in &substitution.parts {
+                    let Some(location) = self.physical_location(part.span) else {
+                        continue;
+                    };
+                    changes.entry(location.artifact_location.uri).or_default().push(
+                        SarifReplacement {
+                            deleted_region: location.region,
+                            inserted_content: SarifArtifactContent { text: part.snippet.clone() },
+                        },
+                    );
+                }
+                SarifFix {
+                    description: SarifMessage { text: description.clone() },
+                    artifact_changes: changes
+                        .into_iter()
+                        .map(|(uri, replacements)| SarifArtifactChange {
+                            artifact_location: SarifArtifactLocation { uri },
+                            replacements,
+                        })
+                        .collect(),
+                    properties: SarifFixProperties { applicability: sugg.applicability },
+                }
+            })
+            .collect()
+    }
+
+    fn physical_location(&self, span: Span) -> Option<SarifPhysicalLocation> {
+        if span.is_dummy() {
+            return None;
+        }
+        let start = self.sm.lookup_char_pos(span.lo());
+        let end = self.sm.lookup_char_pos(span.hi());
+        let byte_offset = start.file.original_relative_byte_pos(span.lo()).0;
+        let byte_end = start.file.original_relative_byte_pos(span.hi()).0;
+        Some(SarifPhysicalLocation {
+            artifact_location: SarifArtifactLocation {
+                uri: path_to_uri(&self.sm.filename_for_diagnostics(&start.file.name).to_string()),
+            },
+            region: SarifRegion {
+                start_line: start.line,
+                start_column: start.col.0 + 1,
+                end_line: end.line,
+                end_column: end.col.0 + 1,
+                byte_offset,
+                byte_length: byte_end - byte_offset,
+            },
+        })
+    }
+}
+
+/// Converts a path to a URI: a `file` URI if it is absolute, and a relative reference otherwise,
+/// which SARIF consumers resolve against the directory the compiler was run from.
+fn path_to_uri(path: &str) -> String {
+    let is_absolute = Path::new(path).is_absolute();
+    let path = path.replace(path::MAIN_SEPARATOR, "/");
+    let mut uri = String::new();
+    if is_absolute {
+        uri.push_str("file://");
+        // Windows paths start with a drive letter, like `C:/`.
+        if !path.starts_with('/') {
+            uri.push('/');
+        }
+    }
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                uri.push(byte as char)
+            }
+            // A colon in the first segment of a relative reference would be taken for a scheme.
+            b':' if is_absolute => uri.push(':'),
+            _ => write!(uri, "%{byte:02X}").unwrap(),
+        }
+    }
+    uri
+}
+
+fn sarif_level(level: Level) -> &'static str {
+    match level {
+        Level::Bug | Level::DelayedBug | Level::Fatal | Level::Error => "error",
+        Level::ForceWarning(_) | Level::Warning => "warning",
+        Level::Note | Level::OnceNote | Level::Help | Level::OnceHelp | Level::FailureNote => {
+            "note"
+        }
+        Level::Allow | Level::Expect(_) => "none",
+    }
+}
+
+impl Translate for SarifEmitter {
+    fn fluent_bundle(&self) -> Option<&Lrc<FluentBundle>> {
+        self.fluent_bundle.as_ref()
+    }
+
+    fn fallback_fluent_bundle(&self) -> &FluentBundle {
+        &self.fallback_bundle
+    }
+}
+
+impl Emitter for SarifEmitter {
+    fn emit_diagnostic(&mut self, diag: crate::DiagInner) {
+        // Failure notes ("aborting due to ...", "for more information about this error ...")
+        // only summarize the other diagnostics.
+        if diag.level.is_failure_note() {
+            return;
+        }
+        let result = self.result_from_diagnostic(diag);
+        self.results.push(result);
+    }
+
+    fn source_map(&self) -> Option<&Lrc<SourceMap>> {
+        Some(&self.sm)
+    }
+
+    fn should_show_explain(&self) -> bool {
+        false
+    }
+}
+
+impl Drop for SarifEmitter {
+    fn drop(&mut self) {
+        if self.skip_empty_log && self.results.is_empty() {
+            return;
+        }
+        if let Err(e) = self.write_log() {
+            if !std::thread::panicking() {
+                panic!("failed to print SARIF log: {e:?}");
+            }
+        }
+    }
+}
+
+// The following data types are provided just for serialisation.
+
+#[derive(Serialize)]
+struct SarifLog<'a> {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<SarifRun<'a>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRun<'a> {
+    tool: SarifTool<'a>,
+    /// How `startColumn` and `endColumn` are counted.
+    column_kind: &'static str,
+    results: &'a [SarifResult],
+}
+
+#[derive(Serialize)]
+struct SarifTool<'a> {
+    driver: SarifDriver<'a>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifDriver<'a> {
+    name: &'static str,
+    information_uri: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<&'a str>,
+    rules: Vec<&'a SarifRule>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRule {
+    /// The error code (e.g. "E0308") or the lint name (e.g. "unused_variables").
+    id: String,
+    /// The long explanation of an error code, as printed by `rustc --explain`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    full_description: Option<SarifMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    help_uri: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rule_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rule_index: Option<usize>,
+    /// "error", "warning", "note" or "none".
+    level: &'static str,
+    message: SarifMessage,
+    locations: Vec<SarifLocation>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    related_locations: Vec<SarifLocation>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    fixes: Vec<SarifFix>,
+}
+
+#[derive(Serialize)]
+struct SarifMessage {
+    text: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifLocation {
+    physical_location: SarifPhysicalLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<SarifMessage>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifPhysicalLocation {
+    artifact_location: SarifArtifactLocation,
+    region: SarifRegion,
+}
+
+#[derive(Serialize)]
+struct SarifArtifactLocation {
+    uri: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRegion {
+    /// 1-based.
+    start_line: usize,
+    /// 1-based, character offset.
+    start_column: usize,
+    end_line: usize,
+    end_column: usize,
+    /// 0-based, relative to the start of the original file.
+    byte_offset: u32,
+    byte_length: u32,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifFix {
+    description: SarifMessage,
+    artifact_changes: Vec<SarifArtifactChange>,
+    properties: SarifFixProperties,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifArtifactChange {
+    artifact_location: SarifArtifactLocation,
+    replacements: Vec<SarifReplacement>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifReplacement {
+    deleted_region: SarifRegion,
+    inserted_content: SarifArtifactContent,
+}
+
+#[derive(Serialize)]
+struct SarifArtifactContent {
+    text: String,
+}
+
+#[derive(Serialize)]
+struct SarifFixProperties {
+    /// Whether the fix can be applied mechanically.
+    applicability: Applicability,
+}
@@ -0,0 +1,125 @@
+use std::path::Path;
+use std::str;
+use std::sync::{Arc, Mutex};
+
+use rustc_span::source_map::FilePathMapping;
+use rustc_span::BytePos;
+use serde_json::Value;
+
+use super::*;
+use crate::DiagCtxt;
+
+struct Shared<T> {
+    data: Arc<Mutex<T>>,
+}
+
+impl<T: Write> Write for Shared<T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.data.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.data.lock().unwrap().flush()
+    }
+}
+
+/// Emits diagnostics through a `SarifEmitter` and returns its output.
+fn emit_sarif_output(code: &str, skip_empty_log: bool, f: impl FnOnce(&DiagCtxt)) -> String {
+    rustc_span::create_default_session_globals_then(|| {
+        let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+        sm.new_source_file(Path::new("test.rs").to_owned().into(), code.to_owned());
+        let fallback_bundle =
+            crate::fallback_fluent_bundle(vec![crate::DEFAULT_LOCALE_RESOURCE], false);
+
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let se = SarifEmitter::new(Box::new(Shared { data: output.clone() }), sm, fallback_bundle)
+            .skip_empty_log(skip_empty_log);
+        let dcx = DiagCtxt::new(Box::new(se));
+        f(&dcx);
+        drop(dcx);
+
+        let bytes = output.lock().unwrap();
+        str::from_utf8(&bytes).unwrap().to_owned()
+    })
+}
+
+/// Emits diagnostics through a `SarifEmitter` and returns the parsed log.
+fn emit_sarif(code: &str, f: impl FnOnce(&DiagCtxt)) -> Value {
+    serde_json::from_str(&emit_sarif_output(code, false, f)).unwrap()
+}
+
+#[test]
+fn single_log() {
+    let log = emit_sarif("fn main() {}\n", |dcx| {
+        let span = Span::with_root_ctxt(BytePos(3), BytePos(7));
+        dcx.handle().span_err(span, "foo");
+        dcx.handle().span_warn(span, "bar");
+    });
+
+    assert_eq!(log["version"], "2.1.0");
+    let runs = log["runs"].as_array().unwrap();
+    assert_eq!(runs.len(), 1);
+    assert_eq!(runs[0]["tool"]["driver"]["name"], "rustc");
+
+    let results = runs[0]["results"].as_array().unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0]["level"], "error");
+    assert_eq!(results[0]["message"]["text"], "foo");
+    assert_eq!(results[1]["level"], "warning");
+    assert_eq!(results[1]["message"]["text"], "bar");
+}
+
+#[test]
+fn region() {
+    let log = emit_sarif("fn main() {\n    let x = 1;\n}\n", |dcx| {
+        let span = Span::with_root_ctxt(BytePos(20), BytePos(21));
+        dcx.handle().span_err(span, "foo");
+    });
+
+    let location = &log["runs"][0]["results"][0]["locations"][0]["physicalLocation"];
+    assert_eq!(location["artifactLocation"]["uri"], "test.rs");
+    let region = &location["region"];
+    assert_eq!(region["startLine"], 2);
+    assert_eq!(region["startColumn"], 9);
+    assert_eq!(region["endLine"], 2);
+    assert_eq!(region["endColumn"], 10);
+    assert_eq!(region["byteOffset"], 20);
+    assert_eq!(region["byteLength"], 1);
+}
+
+#[test]
+fn rules_are_deduplicated() {
+    let log = emit_sarif("fn main() {}\n", |dcx| {
+        let span = Span::with_root_ctxt(BytePos(3), BytePos(7));
+        dcx.handle().struct_span_err(span, "foo").with_code(crate::codes::E0308).emit();
+        dcx.handle().struct_span_err(span, "bar").with_code(crate::codes::E0308).emit();
+    });
+
+    let run = &log["runs"][0];
+    let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+    assert_eq!(rules.len(), 1);
+    assert_eq!(rules[0]["id"], "E0308");
+    for result in run["results"].as_array().unwrap() {
+        assert_eq!(result["ruleId"], "E0308");
+        assert_eq!(result["ruleIndex"], 0);
+    }
+}
+
+#[test]
+fn empty_log() {
+    let log = emit_sarif("fn main() {}\n", |_| {});
+    assert_eq!(log["runs"][0]["results"].as_array().unwrap().len(), 0);
+    assert_eq!(emit_sarif_output("fn main() {}\n", true, |_| {}), "");
+}
+
+#[test]
+fn uris() {
+    assert_eq!(path_to_uri("src/main.rs"), "src/main.rs");
+    assert_eq!(path_to_uri("my crate/a:b.rs"), "my%20crate/a%3Ab.rs");
+    assert_eq!(path_to_uri("<anon>"), "%3Canon%3E");
+    if cfg!(windows) {
+        assert_eq!(path_to_uri(r"C:\src\lib.rs"), "file:///C:/src/lib.rs");
+    } else {
+        assert_eq!(path_to_uri("/home/user/src/lib r.rs"), "file:///home/user/src/lib%20r.rs");
+    }
+}
@@ -532,7 +532,7 @@ pub struct BuiltinAttribute {
     ),
     // RFC 2412
     gated!(
-        optimize, Normal, template!(List: "size|speed"), ErrorPreceding,
+        optimize, Normal, template!(List: "size|speed|level = \"0|1|2|3|s|z\""), ErrorPreceding,
         EncodeCrossCrate::No, optimize_attribute, experimental!(optimize)
     ),
 
@@ -279,6 +279,13 @@ pub struct WriteDepGraph<'a> {
     pub err: std::io::Error,
 }
 
+#[derive(Diagnostic)]
+#[diag(incremental_export_dep_graph)]
+pub struct ExportDepGraph<'a> {
+    pub path: &'a Path,
+    pub err: std::io::Error,
+}
+
 #[derive(Diagnostic)]
 #[diag(incremental_move_dep_graph)]
 pub struct MoveDepGraph<'a> {
@@ -0,0 +1,184 @@
+//! This pass exports the dependency graphs of the previous and of the current compilation
+//! session as JSON, for `-Z export-dep-graph`. It is meant to find out why a change caused
+//! more work to be redone than expected.
+//!
+//! The export is an object with the `version` of the format and two arrays of nodes,
+//! `previous` and `current`. Each node has the name of its dep kind, the hash identifying it,
+//! a human-readable label, the fingerprint of its result and the indices of the nodes it
+//! depends on in the same array. The nodes of each session refer to the same node in the other
+//! session, if there is one.
+//!
+//! Each node of the current session also has a `status`, which is one of
+//!
+//! - `green`: the node was marked green, without executing it,
+//! - `unchanged`: the node was re-executed and its result did not change,
+//! - `changed`: the node was re-executed and its result changed (or has no fingerprint),
+//! - `new`: the node did not exist in the previous session.
+//!
+//! Re-executed nodes have a `reason`: either their dep kind is `eval_always`, or one of their
+//! dependencies in the previous session changed (`dependency_changed`) or could not be
+//! recomputed (`dependency_not_recomputed`), for instance because the item it refers to was
+//! removed. Following the dependencies of the reasons leads to the changes that caused the
+//! re-execution.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use rustc_data_structures::graph::implementation::NodeIndex;
+use rustc_middle::dep_graph::{
+    DepContext, DepGraphQuery, DepNode, SerializedDepGraph, SerializedDepNodeIndex,
+};
+use rustc_middle::ty::TyCtxt;
+use serde::Serialize;
+
+use crate::errors;
+
+/// The version of the format of the export, increased when it changes.
+const EXPORT_FORMAT_VERSION: u32 = 1;
+
+#[derive(Serialize)]
+struct Node {
+    kind: String,
+    hash: String,
+    label: String,
+    fingerprint: String,
+    edges: Vec<usize>,
+}
+
+#[derive(Serialize)]
+struct PreviousNode {
+    #[serde(flatten)]
+    node: Node,
+    current: Option<usize>,
+}
+
+#[derive(Serialize)]
+struct CurrentNode {
+    #[serde(flatten)]
+    node: Node,
+    previous: Option<usize>,
+    status: Status,
+    reason: Option<Reason>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Status {
+    Green,
+    Unchanged,
+    Changed,
+    New,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum Reason {
+    EvalAlways,
+    /// `dependency` is the index of the dependency in the previous session.
+    DependencyChanged {
+        dependency: usize,
+    },
+    DependencyNotRecomputed {
+        dependency: usize,
+    },
+}
+
+pub(crate) fn export_dep_graph(tcx: TyCtxt<'_>, path: &Path) {
+    let Some(previous) = tcx.dep_graph.previous_graph() else {
+        return;
+    };
+    tcx.dep_graph.with_query(|query| {
+        if let Err(err) = write_dep_graph(tcx, previous, query, path) {
+            tcx.dcx().emit_err(errors::ExportDepGraph { path, err });
+        }
+    });
+}
+
+fn write_dep_graph(
+    tcx: TyCtxt<'_>,
+    previous: &SerializedDepGraph,
+    query: &DepGraphQuery,
+    path: &Path,
+) -> io::Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+
+    write!(file, "{{\"version\":{EXPORT_FORMAT_VERSION},\"previous\":[")?;
+    for index in 0..previous.node_count() {
+        let prev_index = SerializedDepNodeIndex::from_usize(index);
+        let dep_node = previous.index_to_node(prev_index);
+        let current = query.indices.get(&dep_node).map(|index| index.0);
+        let node = Node {
+            kind: format!("{:?}", dep_node.kind),
+            hash: dep_node.hash.to_string(),
+            // The label of nodes that still exist in the current session may mention the item
+            // they refer to, this is not possible for nodes that were removed.
+            label: match current {
+                Some(_) => format!("{dep_node:?}"),
+                None => format!("{:?}({})", dep_node.kind, dep_node.hash),
+            },
+            fingerprint: previous.fingerprint_by_index(prev_index).to_string(),
+            edges: previous.edge_targets_from(prev_index).map(|index| index.as_usize()).collect(),
+        };
+        write_separator(&mut file, index)?;
+        serde_json::to_writer(&mut file, &PreviousNode { node, current })?;
+    }
+
+    write!(file, "\n],\"current\":[")?;
+    for (index, graph_node) in query.graph.all_nodes().iter().enumerate() {
+        let dep_node = graph_node.data;
+        let previous_index = previous.node_to_index_opt(&dep_node).map(|index| index.as_usize());
+        let status = if query.promoted[index] {
+            Status::Green
+        } else if previous_index.is_none() {
+            Status::New
+        } else if tcx.dep_graph.is_red(&dep_node) {
+            Status::Changed
+        } else {
+            Status::Unchanged
+        };
+        let reason = match status {
+            Status::Unchanged | Status::Changed => reason(tcx, previous, query, &dep_node),
+            Status::Green | Status::New => None,
+        };
+        let node = Node {
+            kind: format!("{:?}", dep_node.kind),
+            hash: dep_node.hash.to_string(),
+            label: format!("{dep_node:?}"),
+            fingerprint: query.fingerprints[index].to_string(),
+            edges: query.graph.successor_nodes(NodeIndex(index)).map(|index| index.0).collect(),
+        };
+        write_separator(&mut file, index)?;
+        serde_json::to_writer(
+            &mut file,
+            &CurrentNode { node, previous: previous_index, status, reason },
+        )?;
+    }
+    writeln!(file, "\n]}}")?;
+
+    file.flush()
+}
+
+/// Why `dep_node`, which existed in the previous session, was re-executed.
+fn reason(
+    tcx: TyCtxt<'_>,
+    previous: &SerializedDepGraph,
+    query: &DepGraphQuery,
+    dep_node: &DepNode,
+) -> Option<Reason> {
+    if tcx.is_eval_always(dep_node.kind) {
+        return Some(Reason::EvalAlways);
+    }
+    let dependency_node = query.red_dependencies.get(dep_node)?;
+    let dependency = previous.node_to_index_opt(dependency_node)?.as_usize();
+    Some(if tcx.dep_graph.is_red(dependency_node) {
+        Reason::DependencyChanged { dependency }
+    } else {
+        Reason::DependencyNotRecomputed { dependency }
+    })
+}
+
+/// Writes the separator before the node at `index` of an array, one node per line.
+fn write_separator(file: &mut impl Write, index: usize) -> io::Result<()> {
+    if index > 0 { writeln!(file, ",") } else { writeln!(file) }
+}
@@ -10,12 +10,13 @@
 
 mod assert_dep_graph;
 mod errors;
+mod export_dep_graph;
 mod persist;
 
 pub use persist::{
     copy_cgu_workproduct_to_incr_comp_cache_dir, finalize_session_directory, in_incr_comp_dir,
-    in_incr_comp_dir_sess, load_query_result_cache, save_dep_graph, save_work_product_index,
-    setup_dep_graph, LoadResult,
+    in_incr_comp_dir_sess, incremental_cache_usage, load_query_result_cache, save_dep_graph,
+    save_work_product_index, setup_dep_graph, CrateCacheUsage, LoadResult,
 };
 
 rustc_fluent_macro::fluent_messages! { "../messages.ftl" }
@@ -50,6 +50,16 @@
 //! any more and will delete those. It will also delete any finalized session
 //! directories for a given crate except for the most recent one.
 //!
+//! The finalized session directories of other crates are left alone by default,
+//! so the incremental compilation directory keeps growing when it is shared by
+//! many crates, or by crates that are not compiled any more. With
+//! `-Z incremental-cache-max-age` and `-Z incremental-cache-max-size`, the
+//! compiler also deletes the finalized session directories of all crates in the
+//! incremental compilation directory that were not used for too long, and then
+//! the least recently used ones until the directory is small enough. A session
+//! directory is considered used when its session starts, which is recorded in its
+//! name. The directory of the current session is never deleted.
+//!
 //! ## Synchronization
 //!
 //! There is some synchronization needed in order for the compiler to be able to
@@ -373,6 +383,11 @@ pub fn finalize_session_directory(sess: &Session, svh: Option<Svh>) {
     }
 
     let _ = garbage_collect_session_directories(sess);
+
+    let opts = &sess.opts.unstable_opts;
+    if opts.incremental_cache_max_age.is_some() || opts.incremental_cache_max_size.is_some() {
+        let _ = garbage_collect_incremental_directory(sess);
+    }
 }
 
 pub(crate) fn delete_all_session_dir_contents(sess: &Session) -> io::Result<()> {
@@ -873,6 +888,194 @@ fn all_except_most_recent(
     }
 }
 
+/// The disk usage of the incremental compilation cache of a crate.
+pub struct CrateCacheUsage {
+    /// The name of the crate directory, `{crate-name}-{stable-crate-id}`.
+    pub name: String,
+    /// The total size of the files in the session directories of the crate, in bytes.
+    pub size: u64,
+    /// When the most recent session of the crate started.
+    pub last_used: SystemTime,
+}
+
+/// A session directory found while scanning the incremental compilation directory.
+struct SessionDirUsage {
+    path: PathBuf,
+    /// When the session started.
+    timestamp: SystemTime,
+    size: u64,
+}
+
+/// Returns the disk usage of the incremental compilation caches of all crates in `incr_dir`,
+/// sorted from the most to the least recently used.
+pub fn incremental_cache_usage(incr_dir: &Path) -> io::Result<Vec<CrateCacheUsage>> {
+    let mut usage: Vec<_> = scan_incr_comp_dir(incr_dir)?
+        .into_iter()
+        .filter_map(|(crate_dir, sessions)| {
+            let last_used = sessions.iter().map(|session| session.timestamp).max()?;
+            Some(CrateCacheUsage {
+                name: crate_dir.file_name()?.to_string_lossy().into_owned(),
+                size: sessions.iter().map(|session| session.size).sum(),
+                last_used,
+            })
+        })
+        .collect();
+    usage.sort_by(|a, b| b.last_used.cmp(&a.last_used).then_with(|| a.name.cmp(&b.name)));
+    Ok(usage)
+}
+
+/// Returns the session directories of each crate directory in `incr_dir`, with their size.
+fn scan_incr_comp_dir(incr_dir: &Path) -> io::Result<Vec<(PathBuf, Vec<SessionDirUsage>)>> {
+    let incr_dir_iterator = match incr_dir.read_dir() {
+        Ok(iterator) => iterator,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+
+    let mut crate_dirs = Vec::new();
+    for crate_dir in incr_dir_iterator {
+        let crate_dir = crate_dir?.path();
+        // Anything that is not a crate directory is ignored.
+        let Ok(crate_dir_iterator) = crate_dir.read_dir() else {
+            continue;
+        };
+        let sessions = crate_dir_iterator
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                let timestamp = extract_timestamp_from_session_dir(path.file_name()?.to_str()?);
+                Some(SessionDirUsage { timestamp: timestamp.ok()?, size: dir_size(&path), path })
+            })
+            .collect();
+        crate_dirs.push((crate_dir, sessions));
+    }
+    crate_dirs.sort_by(|(a, _), (b, _)| a.cmp(b));
+    Ok(crate_dirs)
+}
+
+/// Returns the total size of the files in `path` and its subdirectories. Errors are ignored,
+/// files that can't be inspected don't count.
+fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = path.read_dir() else {
+        return 0;
+    };
+    entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| match entry.metadata() {
+            Ok(metadata) if metadata.is_dir() => dir_size(&entry.path()),
+            Ok(metadata) => metadata.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+/// Enforces `-Z incremental-cache-max-age` and `-Z incremental-cache-max-size` by deleting the
+/// least recently used finalized session directories of all crates in the incremental
+/// compilation directory, except the one of the current session.
+fn garbage_collect_incremental_directory(sess: &Session) -> io::Result<()> {
+    let session_directory = sess.incr_comp_session_dir();
+    // The crate directory is canonicalized, its parent is the incremental compilation directory.
+    let incr_dir = session_directory.parent().unwrap().parent().unwrap();
+    debug!("garbage_collect_incremental_directory() - directory: {}", incr_dir.display());
+
+    let mut total_size = 0;
+    let mut candidates = Vec::new();
+    for (_, sessions) in scan_incr_comp_dir(incr_dir)? {
+        for session in sessions {
+            total_size += session.size;
+            let directory_name = session.path.file_name().unwrap();
+            if is_finalized(&directory_name.to_string_lossy())
+                && Some(directory_name) != session_directory.file_name()
+            {
+                candidates.push(session);
+            }
+        }
+    }
+
+    let opts = &sess.opts.unstable_opts;
+    let max_age = opts.incremental_cache_max_age.map(Duration::from_secs);
+    let evicted = evict_sessions(
+        candidates,
+        total_size,
+        max_age,
+        opts.incremental_cache_max_size,
+        SystemTime::now(),
+        |session| {
+            debug!(
+                "garbage_collect_incremental_directory() - deleting `{}`",
+                session.path.display()
+            );
+
+            // Sessions that copy from this directory hold a shared lock on it, leave it alone
+            // then.
+            let lock_file_path = lock_file_path(&session.path);
+            let Ok(lock) = flock::Lock::new(
+                &lock_file_path,
+                false, // don't wait
+                false, // don't create the lock-file
+                true,
+            ) else {
+                debug!("garbage_collect_incremental_directory() - not collecting, still in use");
+                return false;
+            };
+
+            let deleted = match safe_remove_dir_all(&session.path) {
+                Ok(()) => {
+                    delete_session_dir_lock_file(sess, &lock_file_path);
+                    true
+                }
+                Err(err) => {
+                    sess.dcx().emit_warn(errors::FinalizedGcFailed { path: &session.path, err });
+                    false
+                }
+            };
+            drop(lock);
+            deleted
+        },
+    );
+
+    if sess.opts.unstable_opts.incremental_info {
+        let evicted_count = evicted.len();
+        let evicted_size: u64 = evicted.iter().map(|session| session.size).sum();
+        eprintln!(
+            "[incremental] cache limits: deleted {evicted_count} session directories \
+             ({evicted_size} bytes)"
+        );
+    }
+
+    Ok(())
+}
+
+/// Deletes session directories with `evict`, from the least recently used, until none of the
+/// remaining ones is older than `max_age` and the total size of the incremental compilation
+/// directory, `total_size`, is at most `max_size`. `evict` returns whether it deleted the session
+/// directory; the ones it couldn't delete, e.g. because they are in use, still count towards the
+/// total size. Returns the deleted session directories.
+fn evict_sessions(
+    mut candidates: Vec<SessionDirUsage>,
+    mut total_size: u64,
+    max_age: Option<Duration>,
+    max_size: Option<u64>,
+    now: SystemTime,
+    mut evict: impl FnMut(&SessionDirUsage) -> bool,
+) -> Vec<SessionDirUsage> {
+    candidates.sort_by_key(|session| session.timestamp);
+    let mut evicted = Vec::new();
+    for session in candidates {
+        let too_old = max_age.is_some_and(|max_age| {
+            now.duration_since(session.timestamp).is_ok_and(|age| age > max_age)
+        });
+        let too_big = max_size.is_some_and(|max_size| total_size > max_size);
+        if !too_old && !too_big {
+            break;
+        }
+        if evict(&session) {
+            total_size -= session.size;
+            evicted.push(session);
+        }
+    }
+    evicted
+}
+
 /// Since paths of artifacts within session directories can get quite long, we
 /// need to support deleting files with very long paths. The regular
 /// WinApi functions only support paths up to 260 characters, however. In order
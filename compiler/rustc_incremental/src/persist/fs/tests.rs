@@ -75,3 +75,49 @@ fn test_find_source_directory_in_iter() {
         None
     );
 }
+
+#[test]
+fn test_evict_sessions() {
+    let now = UNIX_EPOCH + Duration::from_secs(100);
+    let candidates = || {
+        [(10, 4), (90, 2), (50, 3), (30, 1)]
+            .into_iter()
+            .map(|(secs, size)| SessionDirUsage {
+                path: PathBuf::from(secs.to_string()),
+                timestamp: UNIX_EPOCH + Duration::from_secs(secs),
+                size,
+            })
+            .collect::<Vec<_>>()
+    };
+    let evict = |max_age: Option<u64>, max_size: Option<u64>, in_use: &[&str]| {
+        let max_age = max_age.map(Duration::from_secs);
+        evict_sessions(candidates(), 15, max_age, max_size, now, |session| {
+            !in_use.iter().any(|path| session.path == Path::new(path))
+        })
+        .into_iter()
+        .map(|session| session.path)
+        .collect::<Vec<_>>()
+    };
+    let select = |max_age: Option<u64>, max_size: Option<u64>| evict(max_age, max_size, &[]);
+
+    // Nothing to enforce
+    assert_eq!(select(None, None), Vec::<PathBuf>::new());
+    assert_eq!(select(Some(100), Some(15)), Vec::<PathBuf>::new());
+
+    // Least recently used first
+    assert_eq!(select(Some(60), None), vec![PathBuf::from("10"), PathBuf::from("30")]);
+    assert_eq!(select(None, Some(10)), vec![PathBuf::from("10"), PathBuf::from("30")]);
+    assert_eq!(
+        select(None, Some(9)),
+        vec![PathBuf::from("10"), PathBuf::from("30"), PathBuf::from("50")]
+    );
+    assert_eq!(select(Some(80), Some(10)), vec![PathBuf::from("10"), PathBuf::from("30")]);
+
+    // The size of the sessions that can't be deleted still counts
+    assert_eq!(select(None, Some(0)).len(), 4);
+    assert_eq!(
+        evict(None, Some(10), &["10"]),
+        vec![PathBuf::from("30"), PathBuf::from("50"), PathBuf::from("90")]
+    );
+    assert_eq!(evict(Some(60), None, &["10"]), vec![PathBuf::from("30")]);
+}
@@ -17,6 +17,7 @@
 use super::{dirty_clean, file_format, work_product};
 use crate::assert_dep_graph::assert_dep_graph;
 use crate::errors;
+use crate::export_dep_graph::export_dep_graph;
 
 /// Saves and writes the [`DepGraph`] to the file system.
 ///
@@ -42,6 +43,9 @@ pub fn save_dep_graph(tcx: TyCtxt<'_>) {
         let staging_dep_graph_path = staging_dep_graph_path(sess);
 
         sess.time("assert_dep_graph", || assert_dep_graph(tcx));
+        if let Some(path) = &sess.opts.unstable_opts.export_dep_graph {
+            sess.time("export_dep_graph", || export_dep_graph(tcx, path));
+        }
         sess.time("check_dirty_clean", || dirty_clean::check_dirty_clean_annotations(tcx));
 
         if sess.opts.unstable_opts.incremental_info {
@@ -177,7 +181,8 @@ pub(crate) fn build_dep_graph(
         prev_graph,
         prev_work_products,
         encoder,
-        sess.opts.unstable_opts.query_dep_graph,
+        sess.opts.unstable_opts.query_dep_graph
+            || sess.opts.unstable_opts.export_dep_graph.is_some(),
         sess.opts.unstable_opts.incremental_info,
     ))
 }
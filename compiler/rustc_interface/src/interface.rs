@@ -27,6 +27,7 @@
 use rustc_span::FileName;
 use tracing::trace;
 
+use crate::errors::FailedWritingFile;
 use crate::util;
 
 pub type Result<T> = result::Result<T, ErrorGuaranteed>;
@@ -501,6 +502,13 @@ pub fn run_compiler<R: Send>(config: Config, f: impl FnOnce(&Compiler) -> R + Se
 
                 let res = f(&compiler);
 
+                if let Some(path) = &compiler.sess.opts.unstable_opts.time_report
+                    && let Some(time_report) = compiler.sess.prof.time_report()
+                    && let Err(error) = time_report.write_json(path)
+                {
+                    compiler.sess.dcx().emit_err(FailedWritingFile { path, error });
+                }
+
                 // If `f` doesn't panic, `finish_diagnostics` will run
                 // normally when `sess_abort_guard` is dropped.
                 drop(sess_abort_guard);
@@ -449,6 +449,9 @@ fn write_out_deps(tcx: TyCtxt<'_>, outputs: &OutputFilenames, out_filenames: &[P
         if let Some(ref profile_sample) = sess.opts.unstable_opts.profile_sample_use {
             files.push(normalize_path(profile_sample.as_path().to_path_buf()));
         }
+        if let Some(ref inline_mir_profile) = sess.opts.unstable_opts.inline_mir_profile {
+            files.push(normalize_path(inline_mir_profile.as_path().to_path_buf()));
+        }
 
         // Debugger visualizer files
         for debugger_visualizer in tcx.debugger_visualizers(LOCAL_CRATE) {
@@ -1044,6 +1047,23 @@ pub(crate) fn start_codegen<'tcx>(
         }
     }
 
+    if tcx.sess.opts.output_types.contains_key(&OutputType::ApiSurface) {
+        rustc_metadata::emit_api_surface(tcx);
+    }
+
+    if tcx.sess.opts.output_types.contains_key(&OutputType::MonoReport) {
+        rustc_monomorphize::emit_mono_report(tcx);
+    }
+
+    if tcx.sess.opts.output_types.contains_key(&OutputType::UnsafeReport) {
+        rustc_mir_build::emit_unsafe_report(tcx);
+    }
+
+    if tcx.sess.opts.output_types.contains_key(&OutputType::OptRemarks) {
+        // The remarks are emitted with those of the codegen backend, when joining codegen.
+        rustc_mir_transform::inline::record_opt_remarks(tcx);
+    }
+
     Ok(codegen)
 }
 
@@ -213,6 +213,8 @@ pub fn enter<F, T>(&self, f: F) -> T
         // since that likely means there was a parse error.
         if let Some(Ok(gcx)) = &mut *queries.gcx.result.borrow_mut() {
             let gcx = gcx.get_mut();
+            gcx.enter(rustc_query_impl::resolve_time_report_items);
+
             // We assume that no queries are run past here. If there are new queries
             // after this point, they'll show up as "<unknown>" in self-profiling data.
             {
@@ -11,12 +11,14 @@
     build_configuration, build_session_options, rustc_optgroups, BranchProtection, CFGuard, Cfg,
     CollapseMacroDebuginfo, CoverageLevel, CoverageOptions, DebugInfo, DumpMonoStatsFormat,
     ErrorOutputType, ExternEntry, ExternLocation, Externs, FunctionReturn, InliningThreshold,
-    Input, InstrumentCoverage, InstrumentXRay, LinkSelfContained, LinkerPluginLto, LocationDetail,
-    LtoCli, NextSolverConfig, OomStrategy, Options, OutFileName, OutputType, OutputTypes, PAuthKey,
-    PacRet, Passes, PatchableFunctionEntry, Polonius, ProcMacroExecutionStrategy, Strip,
-    SwitchWithOptPath, SymbolManglingVersion, WasiExecModel,
+    Input, InstrumentCoverage, InstrumentXRay, LinkSelfContained, LinkerImplementation,
+    LinkerPluginLto, LocationDetail, LtoCli, NextSolverConfig, OomStrategy, Options, OutFileName,
+    OutputType, OutputTypes, PAuthKey, PacRet, Passes, PatchableFunctionEntry, Polonius,
+    ProcMacroExecutionStrategy, RemapPathPrefix, Strip, SwitchWithOptPath, SymbolManglingVersion,
+    WasiExecModel,
 };
 use rustc_session::lint::Level;
+use rustc_session::lint_baseline::LintBaselineFile;
 use rustc_session::search_paths::SearchPath;
 use rustc_session::utils::{CanonicalizedPath, NativeLib, NativeLibKind};
 use rustc_session::{build_session, filesearch, getopts, CompilerIO, EarlyDiagCtxt, Session};
@@ -305,6 +307,34 @@ fn test_lint_cap_hash_different() {
     assert_non_crate_hash_different(&v2, &v3);
 }
 
+#[test]
+fn test_lint_baseline_hash_different() {
+    let mut v1 = Options::default();
+    let mut v2 = Options::default();
+
+    let path = PathBuf::from("baseline.txt");
+    v1.lint_baseline = Some(LintBaselineFile { path: path.clone(), contents: Some("a".into()) });
+    v2.lint_baseline = Some(LintBaselineFile { path, contents: Some("b".into()) });
+
+    // Changing the contents of the baseline invalidates the incremental compilation cache
+    assert_non_crate_hash_different(&v1, &v2);
+}
+
+#[test]
+fn test_remap_path_prefix_tracking_hash() {
+    let mut v1 = Options::default();
+    let mut v2 = Options::default();
+    let mut v3 = Options::default();
+
+    v1.remap_path_prefix = RemapPathPrefix(vec![("/home/bors/rust".into(), "/src".into())]);
+    v2.remap_path_prefix = RemapPathPrefix(vec![("/tmp/checkout".into(), "/src".into())]);
+    v3.remap_path_prefix = RemapPathPrefix(vec![("/home/bors/rust".into(), "/other".into())]);
+
+    // Only the prefix the paths are remapped to matters, not where the sources are
+    assert_same_hash(&v1, &v2);
+    assert_non_crate_hash_different(&v1, &v3);
+}
+
 #[test]
 fn test_search_paths_tracking_hash_different_order() {
     let mut v1 = Options::default();
@@ -584,6 +614,7 @@ macro_rules! untracked {
     untracked!(link_self_contained, LinkSelfContained::on());
     untracked!(linker, Some(PathBuf::from("linker")));
     untracked!(linker_flavor, Some(LinkerFlavorCli::Gcc));
+    untracked!(linker_implementation, Some(LinkerImplementation::Mold));
     untracked!(no_stack_check, true);
     untracked!(remark, Passes::Some(vec![String::from("pass1"), String::from("pass2")]));
     untracked!(rpath, true);
@@ -657,11 +688,15 @@ macro_rules! tracked {
 
     // Make sure that changing a [TRACKED_NO_CRATE_HASH] option leaves the crate hash unchanged but changes the incremental hash.
     // tidy-alphabetical-start
+    tracked!(
+        lint_baseline,
+        Some(LintBaselineFile { path: PathBuf::from("abc"), contents: Some(String::new()) })
+    );
     tracked!(
         real_rust_source_base_dir,
         Some("/home/bors/rust/.rustup/toolchains/nightly/lib/rustlib/src/rust".into())
     );
-    tracked!(remap_path_prefix, vec![("/home/bors/rust".into(), "src".into())]);
+    tracked!(remap_path_prefix, RemapPathPrefix(vec![("/home/bors/rust".into(), "src".into())]));
     // tidy-alphabetical-end
 }
 
@@ -680,6 +715,8 @@ macro_rules! untracked {
 
     // Make sure that changing an [UNTRACKED] option leaves the hash unchanged.
     // tidy-alphabetical-start
+    untracked!(apply_suggestions, Some(vec![String::from("unused_mut")]));
+    untracked!(apply_suggestions_dry_run, Some(PathBuf::from("abc")));
     untracked!(assert_incr_state, Some(String::from("loaded")));
     untracked!(deduplicate_diagnostics, false);
     untracked!(dump_dep_graph, true);
@@ -693,13 +730,18 @@ macro_rules! untracked {
     untracked!(dump_mono_stats_format, DumpMonoStatsFormat::Json);
     untracked!(dylib_lto, true);
     untracked!(emit_stack_sizes, true);
+    untracked!(export_dep_graph, Some(PathBuf::from("dep-graph.json")));
     untracked!(future_incompat_test, true);
     untracked!(hir_stats, true);
     untracked!(identify_regions, true);
+    untracked!(incremental_cache_max_age, Some(7 * 24 * 60 * 60));
+    untracked!(incremental_cache_max_size, Some(10 << 30));
     untracked!(incremental_info, true);
     untracked!(incremental_verify_ich, true);
     untracked!(input_stats, true);
+    untracked!(json_diagnostic_version, Some(1));
     untracked!(link_native_libraries, false);
+    untracked!(link_reproducer, Some(PathBuf::from("abc")));
     untracked!(llvm_time_trace, true);
     untracked!(ls, vec!["all".to_owned()]);
     untracked!(macro_backtrace, true);
@@ -730,12 +772,14 @@ macro_rules! untracked {
     untracked!(time_llvm_passes, true);
     untracked!(time_passes, true);
     untracked!(time_passes_format, TimePassesFormat::Json);
+    untracked!(time_report, Some(PathBuf::from("time-report.json")));
     untracked!(trace_macros, true);
     untracked!(track_diagnostics, true);
     untracked!(trim_diagnostic_paths, false);
     untracked!(ui_testing, true);
     untracked!(unpretty, Some("expanded".to_string()));
     untracked!(unstable_options, true);
+    untracked!(update_lint_baseline, true);
     untracked!(validate_mir, true);
     untracked!(write_long_types_to_disk, false);
     // tidy-alphabetical-end
@@ -787,6 +831,8 @@ macro_rules! tracked {
     tracked!(inline_in_all_cgus, Some(true));
     tracked!(inline_mir, Some(true));
     tracked!(inline_mir_hint_threshold, Some(123));
+    tracked!(inline_mir_hot_threshold, Some(123));
+    tracked!(inline_mir_profile, Some(PathBuf::from("abc")));
     tracked!(inline_mir_threshold, Some(123));
     tracked!(instrument_mcount, true);
     tracked!(instrument_xray, Some(InstrumentXRay::default()));
@@ -800,6 +846,7 @@ macro_rules! tracked {
     tracked!(mir_emit_retag, true);
     tracked!(mir_enable_passes, vec![("DestProp".to_string(), false)]);
     tracked!(mir_keep_place_mention, true);
+    tracked!(mir_opt_bisect_limit, Some(4));
     tracked!(mir_opt_level, Some(4));
     tracked!(move_size_limit, Some(4096));
     tracked!(mutable_noalias, false);
@@ -813,6 +860,7 @@ macro_rules! tracked {
     tracked!(on_broken_pipe, OnBrokenPipe::Kill);
     tracked!(oom, OomStrategy::Panic);
     tracked!(osx_rpath_install_name, true);
+    tracked!(outline_cold_paths, true);
     tracked!(packed_bundled_libs, true);
     tracked!(panic_abort_tests, true);
     tracked!(panic_in_drop, PanicStrategy::Abort);
@@ -842,6 +890,7 @@ macro_rules! tracked {
     tracked!(share_generics, Some(true));
     tracked!(show_span, Some(String::from("abc")));
     tracked!(simulate_remapped_rust_src_base, Some(PathBuf::from("/rustc/abc")));
+    tracked!(specialize_const_args, true);
     tracked!(split_lto_unit, Some(true));
     tracked!(src_hash_algorithm, Some(SourceFileHashAlgorithm::Sha1));
     tracked!(stack_protector, StackProtector::All);
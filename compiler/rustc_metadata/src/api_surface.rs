@@ -0,0 +1,168 @@
+//! The public API surface of the local crate, emitted with `--emit=api-surface`.
+//!
+//! The surface lists every item that other crates can reach, according to the effective
+//! visibilities computed by `rustc_privacy`, along with the parts of its signature that other
+//! crates depend on: generic parameters and where clauses, field, constant and static types,
+//! function signatures and the impls of reachable types. Items are written one per line with
+//! untrimmed paths and sorted, so that the surfaces of two versions of a crate can be compared
+//! with a plain diff to find out whether the changes between them are semver compatible.
+
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::LocalDefId;
+use rustc_middle::middle::privacy::Level;
+use rustc_middle::ty::print::{with_no_trimmed_paths, PrintTraitRefExt};
+use rustc_middle::ty::{self, TyCtxt};
+use rustc_session::config::OutputType;
+use rustc_span::symbol::{kw, sym};
+
+pub fn emit_api_surface(tcx: TyCtxt<'_>) {
+    let effective_visibilities = tcx.effective_visibilities(());
+    let mut items: Vec<String> = with_no_trimmed_paths!(
+        tcx.iter_local_def_id()
+            .filter(|&def_id| effective_visibilities.is_public_at_level(def_id, Level::Reachable))
+            .filter_map(|def_id| describe_item(tcx, def_id))
+            .collect()
+    );
+    items.sort();
+    items.dedup();
+
+    let mut surface = items.join("\n");
+    surface.push('\n');
+    tcx.output_filenames(()).path(OutputType::ApiSurface).overwrite(&surface, tcx.sess);
+}
+
+/// Returns the line describing `def_id` in the surface, or `None` if it is not part of it on
+/// its own, like constructors, which are covered by their fields.
+fn describe_item(tcx: TyCtxt<'_>, def_id: LocalDefId) -> Option<String> {
+    let path = tcx.def_path_str(def_id);
+    let def_kind = tcx.def_kind(def_id);
+    // The associated types of `impl Trait` in trait methods are covered by their signature.
+    if tcx.is_impl_trait_in_trait(def_id.to_def_id()) {
+        return None;
+    }
+    let non_exhaustive = match def_kind {
+        DefKind::Struct | DefKind::Union | DefKind::Enum | DefKind::Variant
+            if tcx.has_attr(def_id, sym::non_exhaustive) =>
+        {
+            "#[non_exhaustive] "
+        }
+        _ => "",
+    };
+    let (params, where_clause) = generics(tcx, def_id, def_kind);
+    let type_of = || tcx.type_of(def_id).instantiate_identity();
+
+    let item = match def_kind {
+        DefKind::Mod => format!("mod {path}"),
+        DefKind::Struct => format!("{non_exhaustive}struct {path}{params}{where_clause}"),
+        DefKind::Union => format!("{non_exhaustive}union {path}{params}{where_clause}"),
+        DefKind::Enum => format!("{non_exhaustive}enum {path}{params}{where_clause}"),
+        DefKind::Variant => format!("{non_exhaustive}variant {path}"),
+        DefKind::Field => format!("field {path}: {}", type_of()),
+        DefKind::Trait => {
+            let safety = tcx.trait_def(def_id).safety.prefix_str();
+            let auto = if tcx.trait_is_auto(def_id.to_def_id()) { "auto " } else { "" };
+            format!("{safety}{auto}trait {path}{params}{where_clause}")
+        }
+        DefKind::TraitAlias => format!("trait {path}{params}{where_clause}"),
+        DefKind::TyAlias => format!("type {path}{params} = {}{where_clause}", type_of()),
+        DefKind::ForeignTy => format!("extern type {path}"),
+        DefKind::AssocTy => match tcx.def_kind(tcx.local_parent(def_id)) {
+            DefKind::Impl { .. } => format!("type {path}{params} = {}{where_clause}", type_of()),
+            _ => format!("type {path}{params}{where_clause}"),
+        },
+        DefKind::Fn | DefKind::AssocFn => {
+            let constness = if tcx.is_const_fn_raw(def_id.to_def_id()) { "const " } else { "" };
+            let sig = tcx.fn_sig(def_id).instantiate_identity();
+            format!("{constness}fn {path}{params}: {sig}{where_clause}")
+        }
+        DefKind::Const | DefKind::AssocConst => {
+            format!("const {path}{params}: {}{where_clause}", type_of())
+        }
+        DefKind::Static { mutability, nested: false, .. } => {
+            format!("static {}{path}: {}", mutability.prefix_str(), type_of())
+        }
+        DefKind::Macro(kind) => format!("{} {path}", kind.descr()),
+        DefKind::Impl { .. } => {
+            let self_ty = type_of();
+            match tcx.impl_trait_header(def_id) {
+                Some(header) => {
+                    let polarity = match header.polarity {
+                        ty::ImplPolarity::Negative => "!",
+                        ty::ImplPolarity::Positive | ty::ImplPolarity::Reservation => "",
+                    };
+                    format!(
+                        "{}impl{params} {polarity}{} for {self_ty}{where_clause}",
+                        header.safety.prefix_str(),
+                        header.trait_ref.instantiate_identity().print_only_trait_path(),
+                    )
+                }
+                None => format!("impl{params} {self_ty}{where_clause}"),
+            }
+        }
+        _ => return None,
+    };
+    Some(item)
+}
+
+/// Returns the own generic parameters of `def_id` as `<...>` and its own predicates, including
+/// the bounds of associated types, as a sorted ` where ...` clause.
+fn generics(tcx: TyCtxt<'_>, def_id: LocalDefId, def_kind: DefKind) -> (String, String) {
+    if !matches!(
+        def_kind,
+        DefKind::Struct
+            | DefKind::Union
+            | DefKind::Enum
+            | DefKind::Trait
+            | DefKind::TraitAlias
+            | DefKind::TyAlias
+            | DefKind::AssocTy
+            | DefKind::Fn
+            | DefKind::AssocFn
+            | DefKind::Const
+            | DefKind::AssocConst
+            | DefKind::Impl { .. }
+    ) {
+        return (String::new(), String::new());
+    }
+
+    let params: Vec<String> = tcx
+        .generics_of(def_id)
+        .own_params
+        .iter()
+        .filter_map(|param| match param.kind {
+            _ if param.name == kw::SelfUpper => None,
+            ty::GenericParamDefKind::Const { is_host_effect: true, .. } => None,
+            ty::GenericParamDefKind::Const { .. } => {
+                Some(format!("const {}: {}", param.name, tcx.type_of(param.def_id).skip_binder()))
+            }
+            ty::GenericParamDefKind::Lifetime | ty::GenericParamDefKind::Type { .. } => {
+                Some(param.name.to_string())
+            }
+        })
+        .collect();
+    let params = if params.is_empty() { String::new() } else { format!("<{}>", params.join(", ")) };
+
+    let mut predicates: Vec<String> = tcx
+        .explicit_predicates_of(def_id)
+        .predicates
+        .iter()
+        .map(|(clause, _)| clause.to_string())
+        .collect();
+    if def_kind == DefKind::AssocTy && tcx.def_kind(tcx.local_parent(def_id)) == DefKind::Trait {
+        predicates.extend(
+            tcx.explicit_item_bounds(def_id)
+                .skip_binder()
+                .iter()
+                .map(|(clause, _)| clause.to_string()),
+        );
+    }
+    predicates.sort();
+    predicates.dedup();
+    let where_clause = if predicates.is_empty() {
+        String::new()
+    } else {
+        format!(" where {}", predicates.join(", "))
+    };
+
+    (params, where_clause)
+}
@@ -22,7 +22,7 @@
 use rustc_index::IndexVec;
 use rustc_middle::bug;
 use rustc_middle::ty::{TyCtxt, TyCtxtFeed};
-use rustc_session::config::{self, CrateType, ExternLocation};
+use rustc_session::config::{self, CrateType, ExternLocation, OutputType};
 use rustc_session::cstore::{CrateDepKind, CrateSource, ExternCrate, ExternCrateSource};
 use rustc_session::lint::{self, BuiltinLintDiag};
 use rustc_session::output::validate_crate_name;
@@ -66,6 +66,9 @@ pub struct CStore {
 
     /// Unused externs of the crate
     unused_externs: Vec<Symbol>,
+    /// The `--extern` crates that can be unused, and whether they were used, for
+    /// `--emit=unused-deps`.
+    extern_usage: Vec<(Symbol, bool)>,
 }
 
 impl std::fmt::Debug for CStore {
@@ -271,6 +274,10 @@ pub(crate) fn has_alloc_error_handler(&self) -> bool {
     }
 
     pub fn report_unused_deps(&self, tcx: TyCtxt<'_>) {
+        if tcx.sess.opts.output_types.contains_key(&OutputType::UnusedDeps) {
+            self.emit_unused_deps_report(tcx);
+        }
+
         let json_unused_externs = tcx.sess.opts.json_unused_externs;
 
         // We put the check for the option before the lint_level_at_node call
@@ -290,6 +297,29 @@ pub fn report_unused_deps(&self, tcx: TyCtxt<'_>) {
         }
     }
 
+    /// Writes the `--extern` crates that were never referenced by name, and those that were, as
+    /// JSON. Unlike the `unused_crate_dependencies` lint, this doesn't depend on lint levels.
+    fn emit_unused_deps_report(&self, tcx: TyCtxt<'_>) {
+        #[derive(serde::Serialize)]
+        struct UnusedDepsReport<'a> {
+            crate_name: &'a str,
+            unused: Vec<&'a str>,
+            used: Vec<&'a str>,
+        }
+
+        let crate_name = tcx.crate_name(LOCAL_CRATE);
+        let (used, unused): (Vec<_>, Vec<_>) =
+            self.extern_usage.iter().partition(|&&(_, used)| used);
+        let report = UnusedDepsReport {
+            crate_name: crate_name.as_str(),
+            unused: unused.into_iter().map(|(name, _)| name.as_str()).collect(),
+            used: used.into_iter().map(|(name, _)| name.as_str()).collect(),
+        };
+        let mut json = serde_json::to_string(&report).unwrap();
+        json.push('\n');
+        tcx.output_filenames(()).path(OutputType::UnusedDeps).overwrite(&json, tcx.sess);
+    }
+
     pub fn new(metadata_loader: Box<MetadataLoaderDyn>) -> CStore {
         CStore {
             metadata_loader,
@@ -304,6 +334,7 @@ pub fn new(metadata_loader: Box<MetadataLoaderDyn>) -> CStore {
             has_global_allocator: false,
             has_alloc_error_handler: false,
             unused_externs: Vec::new(),
+            extern_usage: Vec::new(),
         }
     }
 }
@@ -964,7 +995,9 @@ fn report_unused_deps(&mut self, krate: &ast::Crate) {
                 continue;
             }
             let name_interned = Symbol::intern(name);
-            if self.used_extern_options.contains(&name_interned) {
+            let used = self.used_extern_options.contains(&name_interned);
+            self.cstore.extern_usage.push((name_interned, used));
+            if used {
                 continue;
             }
 
@@ -22,6 +22,7 @@
 
 pub use rmeta::provide;
 
+mod api_surface;
 mod dependency_format;
 mod foreign_modules;
 mod native_libs;
@@ -32,6 +33,7 @@
 pub mod fs;
 pub mod locator;
 
+pub use api_surface::emit_api_surface;
 pub use creader::{load_symbol_from_dylib, DylibError};
 pub use fs::{emit_wrapper_file, METADATA_FILENAME};
 pub use native_libs::find_native_static_library;
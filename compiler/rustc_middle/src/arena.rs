@@ -29,6 +29,7 @@ macro_rules! arena_types {
                 >,
             [decode] typeck_results: rustc_middle::ty::TypeckResults<'tcx>,
             [decode] borrowck_result: rustc_middle::mir::BorrowCheckResult<'tcx>,
+            [decode] unsafety_check_result: rustc_middle::mir::UnsafetyCheckResult,
             [] resolver: rustc_data_structures::steal::Steal<(
                 rustc_middle::ty::ResolverAstLowering,
                 rustc_data_structures::sync::Lrc<rustc_ast::Crate>,
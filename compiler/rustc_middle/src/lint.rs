@@ -295,6 +295,17 @@ fn lint_level_impl(
             },
         );
 
+        // The lints recorded in the `--lint-baseline` are allowed where they were recorded.
+        let primary_span = span.as_ref().and_then(|span| span.primary_span());
+        let level = match level {
+            Level::Warn | Level::Deny | Level::Forbid
+                if sess.lint_baseline_covers(lint, primary_span) =>
+            {
+                Level::Allow
+            }
+            _ => level,
+        };
+
         // Convert lint level to error level.
         let err_level = match level {
             Level::Allow => {
@@ -0,0 +1,26 @@
+//! The hotness of functions according to a profile, used by the MIR inliner.
+//!
+//! For all the details, see the provider of the `function_hotness` query.
+
+use rustc_data_structures::unord::UnordMap;
+use rustc_macros::HashStable;
+
+/// The number of calls to functions in the profile given with `-Z inline-mir-profile`.
+#[derive(Debug, Default, HashStable)]
+pub struct FunctionHotness {
+    /// The number of calls to each function, by path without generic arguments. The calls to
+    /// all the instances of a generic function are counted together.
+    pub counts: UnordMap<String, u64>,
+    /// The smallest number of calls of a hot function, or `None` if no function was called.
+    pub hot_count: Option<u64>,
+}
+
+impl FunctionHotness {
+    /// Returns `true` if the function with the given path, without generic arguments, is hot.
+    pub fn is_hot(&self, path: &str) -> bool {
+        match (self.counts.get(path), self.hot_count) {
+            (Some(&count), Some(hot_count)) => count >= hot_count,
+            _ => false,
+        }
+    }
+}
@@ -26,6 +26,7 @@
 use rustc_index::{Idx, IndexSlice, IndexVec};
 use rustc_macros::{HashStable, TyDecodable, TyEncodable, TypeFoldable, TypeVisitable};
 use rustc_serialize::{Decodable, Encodable};
+use rustc_session::opt_remarks::OptRemark;
 use rustc_session::Session;
 use rustc_span::source_map::Spanned;
 use rustc_span::symbol::Symbol;
@@ -153,13 +154,22 @@ fn profiler_name(&self) -> &'static str {
         to_profiler_name(self.name())
     }
 
-    /// Returns `true` if this pass is enabled with the current combination of compiler flags.
-    fn is_enabled(&self, _sess: &Session) -> bool {
+    /// Returns `true` if this pass is enabled with the current combination of compiler flags, for
+    /// a body optimized at the MIR optimization level `mir_opt_level`. This is the level of the
+    /// session, unless the `#[optimize(level = "...")]` of the function lowers it.
+    fn is_enabled(&self, _sess: &Session, _mir_opt_level: usize) -> bool {
         true
     }
 
     fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>);
 
+    /// Returns `true` if this pass must run for the MIR to be correct or to be accepted by the
+    /// codegen backends, and `false` if it is an optimization that can be skipped, like for the
+    /// functions with `#[optimize(level = "0")]`. Optimizations must override this.
+    fn is_required(&self) -> bool {
+        true
+    }
+
     fn is_mir_dump_enabled(&self) -> bool {
         true
     }
@@ -351,6 +361,15 @@ pub struct Body<'tcx> {
     /// information.
     pub coroutine: Option<Box<CoroutineInfo<'tcx>>>,
 
+    /// The cold paths called by this body, outlined from it by the `OutlineColdPaths` pass or
+    /// from the bodies inlined into it, which are the bodies of `InstanceKind::ColdPathShim`s.
+    pub cold_paths: Vec<Body<'tcx>>,
+
+    /// The remarks of the optimizations of this body, only recorded for `--emit=opt-remarks`.
+    /// They are part of the body so that they are also emitted when the body is reused from the
+    /// incremental compilation cache.
+    pub opt_remarks: Vec<OptRemark>,
+
     /// Declarations of locals.
     ///
     /// The first local is the return value pointer, followed by `arg_count`
@@ -473,6 +492,8 @@ pub fn new(
             basic_blocks: BasicBlocks::new(basic_blocks),
             source_scopes,
             coroutine,
+            cold_paths: Vec::new(),
+            opt_remarks: Vec::new(),
             local_decls,
             user_type_annotations,
             arg_count,
@@ -504,6 +525,8 @@ pub fn new_cfg_only(basic_blocks: IndexVec<BasicBlock, BasicBlockData<'tcx>>) ->
             basic_blocks: BasicBlocks::new(basic_blocks),
             source_scopes: IndexVec::new(),
             coroutine: None,
+            cold_paths: Vec::new(),
+            opt_remarks: Vec::new(),
             local_decls: IndexVec::new(),
             user_type_annotations: IndexVec::new(),
             arg_count: 0,
@@ -251,6 +251,42 @@ fn to_stable_hash_key(&self, hcx: &StableHashingContext<'_>) -> Self::KeyType {
     }
 }
 
+/// The graph of the mono items of the crate, as computed by the monomorphization collector.
+#[derive(Debug, HashStable)]
+pub struct MonoItemGraph<'tcx> {
+    /// The collected mono items, in a deterministic order.
+    pub items: Vec<MonoItem<'tcx>>,
+    /// The uses of mono items by other mono items, grouped by user in the order of `items`.
+    pub edges: Vec<MonoItemEdge<'tcx>>,
+}
+
+/// A use of a mono item by another, which requires it to be collected.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, HashStable)]
+pub struct MonoItemEdge<'tcx> {
+    pub user: MonoItem<'tcx>,
+    pub used: MonoItem<'tcx>,
+    pub kind: MonoItemUseKind,
+    /// The span of the use in the body of `user`, or a dummy span if it has none.
+    pub span: Span,
+}
+
+/// How a mono item uses another.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, HashStable)]
+pub enum MonoItemUseKind {
+    /// A call of a function, including the calls inserted by codegen, like the ones of the panic
+    /// functions of failed assertions.
+    Call,
+    /// A drop of a value, which calls its drop glue.
+    DropGlue,
+    /// A method, or the drop glue, of a vtable built by an unsizing coercion to a trait object.
+    Vtable,
+    /// A function whose address is taken, like by a cast to a function pointer, or by a pointer
+    /// stored in a constant.
+    FnPtr,
+    /// A reference to a static.
+    Static,
+}
+
 #[derive(Debug, HashStable)]
 pub struct CodegenUnit<'tcx> {
     /// A name for this CGU. Incremental compilation requires that
@@ -420,7 +456,9 @@ fn item_sort_key<'tcx>(tcx: TyCtxt<'tcx>, item: MonoItem<'tcx>) -> ItemSortKey<'
                             | InstanceKind::CloneShim(..)
                             | InstanceKind::ThreadLocalShim(..)
                             | InstanceKind::FnPtrAddrShim(..)
-                            | InstanceKind::AsyncDropGlueCtorShim(..) => None,
+                            | InstanceKind::AsyncDropGlueCtorShim(..)
+                            | InstanceKind::ConstArgsShim(..)
+                            | InstanceKind::ColdPathShim { .. } => None,
                         }
                     }
                     MonoItem::Static(def_id) => def_id.as_local().map(Idx::index),
@@ -199,6 +199,24 @@ fn dump_path<'tcx>(
             }));
             s
         }
+        // The clones of a function specialized for different constant arguments also have the
+        // same `DefId`, so we add the constants.
+        ty::InstanceKind::ConstArgsShim(_, args) => {
+            let mut s = ".const_args".to_owned();
+            for arg in args {
+                match arg.as_const() {
+                    Some(ct) => write!(s, "-{}", ct.to_valtree().unwrap_leaf()).unwrap(),
+                    None => s.push_str("-_"),
+                }
+            }
+            s
+        }
+        // The cold paths calling a function are told apart by the function they are outlined
+        // from and their index.
+        ty::InstanceKind::ColdPathShim { outlined_from, index, .. } => {
+            let outlined_from = tcx.def_path(outlined_from).to_filename_friendly_no_crate();
+            format!(".cold_path-{outlined_from}-{index}")
+        }
         _ => String::new(),
     };
 
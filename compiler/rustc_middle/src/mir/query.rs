@@ -6,7 +6,7 @@
 use derive_where::derive_where;
 use rustc_data_structures::fx::FxIndexMap;
 use rustc_errors::ErrorGuaranteed;
-use rustc_hir::def_id::LocalDefId;
+use rustc_hir::def_id::{DefId, LocalDefId};
 use rustc_index::bit_set::BitMatrix;
 use rustc_index::{Idx, IndexVec};
 use rustc_macros::{HashStable, TyDecodable, TyEncodable, TypeFoldable, TypeVisitable};
@@ -129,6 +129,44 @@ pub struct BorrowCheckResult<'tcx> {
     pub tainted_by_errors: Option<ErrorGuaranteed>,
 }
 
+/// The result of the `check_unsafety` query: the `unsafe` blocks of a body, and the operations
+/// that needed them, or that were allowed by the body being an `unsafe fn`.
+///
+/// The blocks and operations of closures and inline consts are part of the result of the body
+/// they are in.
+#[derive(Clone, Debug, Default, TyEncodable, TyDecodable, HashStable)]
+pub struct UnsafetyCheckResult {
+    /// The spans of the `unsafe` blocks written by the user, excluding the compiler-generated ones.
+    pub unsafe_blocks: Vec<Span>,
+    pub unsafe_ops: Vec<UnsafeOperation>,
+}
+
+#[derive(Clone, Debug, TyEncodable, TyDecodable, HashStable)]
+pub struct UnsafeOperation {
+    pub kind: UnsafeOperationKind,
+    pub span: Span,
+    /// The span of the innermost `unsafe` block the operation is in, or `None` if it is directly
+    /// in the body of an `unsafe fn`.
+    pub unsafe_block: Option<Span>,
+}
+
+/// An operation which is only allowed in an `unsafe` block or `unsafe fn`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, TyEncodable, TyDecodable, HashStable)]
+pub enum UnsafeOperationKind {
+    /// A call to an unsafe function, like a foreign function, with its `DefId` if it is known.
+    CallToUnsafeFunction(Option<DefId>),
+    UseOfInlineAssembly,
+    InitializingTypeWithLayoutConstraints,
+    UseOfMutableStatic,
+    UseOfExternStatic,
+    DerefOfRawPointer,
+    AccessToUnionField,
+    MutationOfLayoutConstrainedField,
+    BorrowOfLayoutConstrainedField,
+    /// A call to a function with target features the caller doesn't enable.
+    CallToFunctionWithTargetFeatures(DefId),
+}
+
 /// The result of the `mir_const_qualif` query.
 ///
 /// Each field (except `tainted_by_errors`) corresponds to an implementer of the `Qualif` trait in
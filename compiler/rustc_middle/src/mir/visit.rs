@@ -351,7 +351,12 @@ fn super_source_scope_data(
                         } |
                         ty::InstanceKind::CoroutineKindShim { coroutine_def_id: _def_id } |
                         ty::InstanceKind::AsyncDropGlueCtorShim(_def_id, None) |
-                        ty::InstanceKind::DropGlue(_def_id, None) => {}
+                        ty::InstanceKind::DropGlue(_def_id, None) |
+                        ty::InstanceKind::ColdPathShim {
+                            callee: _def_id,
+                            outlined_from: _,
+                            index: _,
+                        } => {}
 
                         ty::InstanceKind::FnPtrShim(_def_id, ty) |
                         ty::InstanceKind::DropGlue(_def_id, Some(ty)) |
@@ -361,6 +366,10 @@ fn super_source_scope_data(
                             // FIXME(eddyb) use a better `TyContext` here.
                             self.visit_ty($(& $mutability)? *ty, TyContext::Location(location));
                         }
+
+                        ty::InstanceKind::ConstArgsShim(_def_id, const_args) => {
+                            self.visit_args(const_args, location);
+                        }
                     }
                     self.visit_args(callee_args, location);
                 }
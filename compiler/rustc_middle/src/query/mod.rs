@@ -50,6 +50,7 @@
 use crate::middle::codegen_fn_attrs::CodegenFnAttrs;
 use crate::middle::debugger_visualizer::DebuggerVisualizerFile;
 use crate::middle::exported_symbols::{ExportedSymbol, SymbolExportInfo};
+use crate::middle::function_hotness::FunctionHotness;
 use crate::middle::lib_features::LibFeatures;
 use crate::middle::privacy::EffectiveVisibilities;
 use crate::middle::resolve_bound_vars::{ObjectLifetimeDefault, ResolveBoundVars, ResolvedArg};
@@ -58,7 +59,7 @@
     EvalStaticInitializerRawResult, EvalToAllocationRawResult, EvalToConstValueResult,
     EvalToValTreeResult, GlobalId, LitToConstError, LitToConstInput,
 };
-use crate::mir::mono::CodegenUnit;
+use crate::mir::mono::{CodegenUnit, MonoItemGraph};
 use crate::query::erase::{erase, restore, Erase};
 use crate::query::plumbing::{
     query_ensure, query_ensure_error_guaranteed, query_get_at, CyclePlaceholder, DynamicQuery,
@@ -888,8 +889,8 @@
         desc { |tcx| "collecting all inherent impls for `{:?}`", key }
     }
 
-    /// Unsafety-check this `LocalDefId`.
-    query check_unsafety(key: LocalDefId) {
+    /// Unsafety-check this `LocalDefId`, and return its `unsafe` blocks and unsafe operations.
+    query check_unsafety(key: LocalDefId) -> &'tcx mir::UnsafetyCheckResult {
         desc { |tcx| "unsafety-checking `{}`", tcx.def_path_str(key) }
         cache_on_disk_if { true }
     }
@@ -1959,6 +1960,15 @@
         desc { "collect_and_partition_mono_items" }
     }
 
+    /// The graph of the mono items of the crate, computed by the monomorphization collector with
+    /// the same strategy as for codegen. This collects the mono items again, so it's meant for
+    /// tools analyzing the crate and for `--emit=mono-report`, not for codegen itself.
+    query mono_item_graph(_: ()) -> &'tcx MonoItemGraph<'tcx> {
+        eval_always
+        arena_cache
+        desc { "collecting the graph of mono items" }
+    }
+
     query is_codegened_item(def_id: DefId) -> bool {
         desc { |tcx| "determining whether `{}` needs codegen", tcx.def_path_str(def_id) }
     }
@@ -1980,6 +1990,14 @@
         desc { "optimization level used by backend" }
     }
 
+    /// The hotness of functions according to the profile given with `-Z inline-mir-profile`,
+    /// or `None` without a profile.
+    query function_hotness(_: ()) -> &'tcx Option<FunctionHotness> {
+        arena_cache
+        eval_always
+        desc { "loading the profile of the MIR inliner" }
+    }
+
     /// Return the filenames where output artefacts shall be stored.
     ///
     /// This query returns an `&Arc` because codegen backends need the value even after the `TyCtxt`
@@ -181,6 +181,29 @@ pub enum InstanceKind<'tcx> {
     /// The `DefId` is for `core::future::async_drop::async_drop_in_place`, the `Ty`
     /// is the type `T`.
     AsyncDropGlueCtorShim(DefId, Option<Ty<'tcx>>),
+
+    /// A clone of a local `fn` item, specialized for the constant arguments of some of its calls
+    /// by the `SpecializeConstArgs` MIR pass.
+    ///
+    /// The `GenericArgsRef` has an entry for each argument of the function: the constant the
+    /// argument is specialized for, or the type of the argument if it is not specialized. The
+    /// constants are typed as the unsigned integer of the size of the argument, since arguments
+    /// like enums don't have scalar valtrees.
+    ///
+    /// The clone has the signature of the function, and is called through a constant `fn`
+    /// pointer, since calls to `fn` items always resolve to the items themselves.
+    ConstArgsShim(DefId, GenericArgsRef<'tcx>),
+
+    /// A cold path, like the formatting of a panic message or the failure of an assertion, outlined
+    /// from a local `fn` item by the `OutlineColdPaths` MIR pass.
+    ///
+    /// `callee` is the `#[cold]` function the cold path ends in a call to, and after which it is
+    /// named. The cold path is the `index`th one outlined from `outlined_from`, and its body is in
+    /// `Body::cold_paths` of the optimized MIR of `outlined_from`. The cold path takes the values
+    /// it reads as arguments and never returns, and is called through a constant `fn` pointer,
+    /// like `ConstArgsShim`. If `callee` is `#[track_caller]`, so is the cold path, and the panic
+    /// location is the last argument of the `fn` pointer.
+    ColdPathShim { callee: DefId, outlined_from: DefId, index: u32 },
 }
 
 impl<'tcx> Instance<'tcx> {
@@ -252,7 +275,9 @@ pub fn def_id(self) -> DefId {
             | InstanceKind::DropGlue(def_id, _)
             | InstanceKind::CloneShim(def_id, _)
             | InstanceKind::FnPtrAddrShim(def_id, _)
-            | InstanceKind::AsyncDropGlueCtorShim(def_id, _) => def_id,
+            | InstanceKind::AsyncDropGlueCtorShim(def_id, _)
+            | InstanceKind::ConstArgsShim(def_id, _)
+            | InstanceKind::ColdPathShim { callee: def_id, .. } => def_id,
         }
     }
 
@@ -274,7 +299,9 @@ pub fn def_id_if_not_guaranteed_local_codegen(self) -> Option<DefId> {
             | InstanceKind::DropGlue(..)
             | InstanceKind::AsyncDropGlueCtorShim(..)
             | InstanceKind::CloneShim(..)
-            | InstanceKind::FnPtrAddrShim(..) => None,
+            | InstanceKind::FnPtrAddrShim(..)
+            | InstanceKind::ConstArgsShim(..)
+            | InstanceKind::ColdPathShim { .. } => None,
         }
     }
 
@@ -295,10 +322,12 @@ pub fn get_attrs(
     pub fn requires_inline(&self, tcx: TyCtxt<'tcx>) -> bool {
         use rustc_hir::definitions::DefPathData;
         let def_id = match *self {
-            ty::InstanceKind::Item(def) => def,
+            ty::InstanceKind::Item(def) | ty::InstanceKind::ConstArgsShim(def, _) => def,
             ty::InstanceKind::DropGlue(_, Some(_)) => return false,
             ty::InstanceKind::AsyncDropGlueCtorShim(_, Some(_)) => return false,
             ty::InstanceKind::ThreadLocalShim(_) => return false,
+            // Cold paths are shared by the functions their function is inlined into.
+            ty::InstanceKind::ColdPathShim { .. } => return false,
             _ => return true,
         };
         matches!(
@@ -345,7 +374,8 @@ pub fn generates_cgu_internal_copy(&self, tcx: TyCtxt<'tcx>) -> bool {
                 .map_or_else(|| adt_def.is_enum(), |did| tcx.cross_crate_inlinable(did))
             });
         }
-        if let ty::InstanceKind::ThreadLocalShim(..) = *self {
+        if let ty::InstanceKind::ThreadLocalShim(..) | ty::InstanceKind::ColdPathShim { .. } = *self
+        {
             return false;
         }
         tcx.cross_crate_inlinable(self.def_id())
@@ -357,6 +387,9 @@ pub fn requires_caller_location(&self, tcx: TyCtxt<'_>) -> bool {
                 tcx.body_codegen_attrs(def_id).flags.contains(CodegenFnAttrFlags::TRACK_CALLER)
             }
             InstanceKind::ClosureOnceShim { call_once: _, track_caller } => track_caller,
+            InstanceKind::ColdPathShim { callee, .. } => {
+                tcx.codegen_fn_attrs(callee).flags.contains(CodegenFnAttrFlags::TRACK_CALLER)
+            }
             _ => false,
         }
     }
@@ -384,7 +417,9 @@ pub fn has_polymorphic_mir_body(&self) -> bool {
             | InstanceKind::Intrinsic(..)
             | InstanceKind::ReifyShim(..)
             | InstanceKind::Virtual(..)
-            | InstanceKind::VTableShim(..) => true,
+            | InstanceKind::VTableShim(..)
+            | InstanceKind::ConstArgsShim(..)
+            | InstanceKind::ColdPathShim { .. } => true,
         }
     }
 }
@@ -459,6 +494,10 @@ pub fn fmt_instance(
         InstanceKind::FnPtrAddrShim(_, ty) => write!(f, " - shim({ty})"),
         InstanceKind::AsyncDropGlueCtorShim(_, None) => write!(f, " - shim(None)"),
         InstanceKind::AsyncDropGlueCtorShim(_, Some(ty)) => write!(f, " - shim(Some({ty}))"),
+        InstanceKind::ConstArgsShim(_, args) => write!(f, " - shim(const args {args:?})"),
+        InstanceKind::ColdPathShim { outlined_from, index, .. } => {
+            write!(f, " - shim(cold path #{index} of {outlined_from:?})")
+        }
     }
 }
 
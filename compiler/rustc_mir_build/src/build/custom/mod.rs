@@ -48,6 +48,8 @@ pub(super) fn build_custom_mir<'tcx>(
         phase: MirPhase::Built,
         source_scopes: IndexVec::new(),
         coroutine: None,
+        cold_paths: Vec::new(),
+        opt_remarks: Vec::new(),
         local_decls: IndexVec::new(),
         user_type_annotations: IndexVec::new(),
         arg_count: params.len(),
@@ -5,7 +5,7 @@
 use rustc_errors::DiagArgValue;
 use rustc_hir::def::DefKind;
 use rustc_hir::{self as hir, BindingMode, ByRef, HirId, Mutability};
-use rustc_middle::mir::BorrowKind;
+use rustc_middle::mir::{BorrowKind, UnsafeOperation, UnsafeOperationKind, UnsafetyCheckResult};
 use rustc_middle::span_bug;
 use rustc_middle::thir::visit::Visitor;
 use rustc_middle::thir::*;
@@ -39,6 +39,8 @@ struct UnsafetyVisitor<'a, 'tcx> {
     param_env: ParamEnv<'tcx>,
     inside_adt: bool,
     warnings: &'a mut Vec<UnusedUnsafeWarning>,
+    /// The `unsafe` blocks and unsafe operations found so far, returned by the query.
+    result: &'a mut UnsafetyCheckResult,
 
     /// Flag to ensure that we only suggest wrapping the entire function body in
     /// an unsafe block once.
@@ -121,6 +123,18 @@ fn emit_deprecated_safe_fn_call(&self, span: Span, kind: &UnsafeOpKind) -> bool
 
     fn requires_unsafe(&mut self, span: Span, kind: UnsafeOpKind) {
         let unsafe_op_in_unsafe_fn_allowed = self.unsafe_op_in_unsafe_fn_allowed();
+        let unsafe_block = match self.safety_context {
+            SafetyContext::UnsafeBlock { span, .. } => Some(Some(span)),
+            SafetyContext::UnsafeFn => Some(None),
+            SafetyContext::BuiltinUnsafeBlock | SafetyContext::Safe => None,
+        };
+        if let Some(unsafe_block) = unsafe_block {
+            self.result.unsafe_ops.push(UnsafeOperation {
+                kind: kind.to_operation_kind(),
+                span,
+                unsafe_block,
+            });
+        }
         match self.safety_context {
             SafetyContext::BuiltinUnsafeBlock => {}
             SafetyContext::UnsafeBlock { ref mut used, .. } => {
@@ -191,6 +205,7 @@ fn visit_inner_body(&mut self, def: LocalDefId) {
                 param_env: self.param_env,
                 inside_adt: false,
                 warnings: self.warnings,
+                result: self.result,
                 suggest_unsafe_block: self.suggest_unsafe_block,
             };
             inner_visitor.visit_expr(&inner_thir[expr]);
@@ -259,6 +274,7 @@ fn visit_block(&mut self, block: &'a Block) {
                 });
             }
             BlockSafety::ExplicitUnsafe(hir_id) => {
+                self.result.unsafe_blocks.push(block.span);
                 let used =
                     matches!(self.tcx.lint_level_at_node(UNUSED_UNSAFE, hir_id), (Level::Allow, _));
                 self.in_safety_context(
@@ -635,6 +651,25 @@ enum UnsafeOpKind {
 use UnsafeOpKind::*;
 
 impl UnsafeOpKind {
+    fn to_operation_kind(&self) -> UnsafeOperationKind {
+        match *self {
+            CallToUnsafeFunction(did) => UnsafeOperationKind::CallToUnsafeFunction(did),
+            UseOfInlineAssembly => UnsafeOperationKind::UseOfInlineAssembly,
+            InitializingTypeWith => UnsafeOperationKind::InitializingTypeWithLayoutConstraints,
+            UseOfMutableStatic => UnsafeOperationKind::UseOfMutableStatic,
+            UseOfExternStatic => UnsafeOperationKind::UseOfExternStatic,
+            DerefOfRawPointer => UnsafeOperationKind::DerefOfRawPointer,
+            AccessToUnionField => UnsafeOperationKind::AccessToUnionField,
+            MutationOfLayoutConstrainedField => {
+                UnsafeOperationKind::MutationOfLayoutConstrainedField
+            }
+            BorrowOfLayoutConstrainedField => UnsafeOperationKind::BorrowOfLayoutConstrainedField,
+            CallToFunctionWith { function, .. } => {
+                UnsafeOperationKind::CallToFunctionWithTargetFeatures(function)
+            }
+        }
+    }
+
     fn emit_unsafe_op_in_unsafe_fn_lint(
         &self,
         tcx: TyCtxt<'_>,
@@ -961,20 +996,22 @@ fn emit_requires_unsafe_err(
     }
 }
 
-pub fn check_unsafety(tcx: TyCtxt<'_>, def: LocalDefId) {
+pub fn check_unsafety(tcx: TyCtxt<'_>, def: LocalDefId) -> &UnsafetyCheckResult {
     // Closures and inline consts are handled by their owner, if it has a body
     // Also, don't safety check custom MIR
     if tcx.is_typeck_child(def.to_def_id()) || tcx.has_attr(def, sym::custom_mir) {
-        return;
+        return tcx.arena.alloc(UnsafetyCheckResult::default());
     }
 
-    let Ok((thir, expr)) = tcx.thir_body(def) else { return };
+    let Ok((thir, expr)) = tcx.thir_body(def) else {
+        return tcx.arena.alloc(UnsafetyCheckResult::default());
+    };
     // Runs all other queries that depend on THIR.
     tcx.ensure_with_value().mir_built(def);
     let thir = &thir.steal();
     // If `thir` is empty, a type error occurred, skip this body.
     if thir.exprs.is_empty() {
-        return;
+        return tcx.arena.alloc(UnsafetyCheckResult::default());
     }
 
     let hir_id = tcx.local_def_id_to_hir_id(def);
@@ -987,6 +1024,7 @@ pub fn check_unsafety(tcx: TyCtxt<'_>, def: LocalDefId) {
     });
     let body_target_features = &tcx.body_codegen_attrs(def.to_def_id()).target_features;
     let mut warnings = Vec::new();
+    let mut result = UnsafetyCheckResult::default();
     let mut visitor = UnsafetyVisitor {
         tcx,
         thir,
@@ -998,6 +1036,7 @@ pub fn check_unsafety(tcx: TyCtxt<'_>, def: LocalDefId) {
         param_env: tcx.param_env(def),
         inside_adt: false,
         warnings: &mut warnings,
+        result: &mut result,
         suggest_unsafe_block: true,
     };
     visitor.visit_expr(&thir[expr]);
@@ -1012,4 +1051,6 @@ pub fn check_unsafety(tcx: TyCtxt<'_>, def: LocalDefId) {
             UnusedUnsafe { span: block_span, enclosing: enclosing_unsafe },
         );
     }
+
+    tcx.arena.alloc(result)
 }
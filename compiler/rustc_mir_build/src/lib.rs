@@ -15,8 +15,10 @@
 mod errors;
 pub mod lints;
 mod thir;
+mod unsafe_report;
 
 use rustc_middle::util::Providers;
+pub use unsafe_report::emit_unsafe_report;
 
 rustc_fluent_macro::fluent_messages! { "../messages.ftl" }
 
@@ -0,0 +1,205 @@
+//! The inventory of the unsafe code of the local crate, emitted with `--emit=unsafe-report`.
+//!
+//! The report lists every `unsafe` block, `unsafe fn`, `unsafe impl` and `extern` block of the
+//! crate, sorted by their position in the source, along with the unsafe operations that
+//! `check_unsafety` found in the blocks and directly in the bodies of the functions, and whether
+//! they are justified by a `SAFETY:` comment. It is meant to help auditing the unsafe code of a
+//! crate, and following how it changes.
+
+use rustc_hir::{self as hir, ItemKind, OwnerNode};
+use rustc_middle::mir::{UnsafeOperation, UnsafeOperationKind};
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_middle::ty::TyCtxt;
+use rustc_session::config::OutputType;
+use rustc_span::def_id::LOCAL_CRATE;
+use rustc_span::Span;
+
+#[derive(serde::Serialize)]
+struct UnsafeReport {
+    crate_name: String,
+    items: Vec<UnsafeItem>,
+}
+
+#[derive(serde::Serialize)]
+struct UnsafeItem {
+    kind: &'static str,
+    /// The path of the item, or of the body the `unsafe` block is in.
+    item: String,
+    span: String,
+    safety_comment: bool,
+    operations: Vec<Operation>,
+    /// The items declared in an `extern` block.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    foreign_items: Option<Vec<String>>,
+}
+
+#[derive(serde::Serialize)]
+struct Operation {
+    kind: &'static str,
+    span: String,
+    /// The called function, for calls to known functions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    callee: Option<String>,
+}
+
+pub fn emit_unsafe_report(tcx: TyCtxt<'_>) {
+    let source_map = tcx.sess.source_map();
+    let mut items: Vec<(Span, UnsafeItem)> = vec![];
+
+    with_no_trimmed_paths!({
+        // The blocks in closures and inline consts are part of the result of their owner.
+        for def_id in tcx.hir().body_owners() {
+            if tcx.is_typeck_child(def_id.to_def_id()) {
+                continue;
+            }
+            let result = tcx.check_unsafety(def_id);
+            for &block in &result.unsafe_blocks {
+                let operations =
+                    result.unsafe_ops.iter().filter(|op| op.unsafe_block == Some(block));
+                items.push((
+                    block,
+                    UnsafeItem {
+                        kind: "unsafe block",
+                        item: tcx.def_path_str(def_id),
+                        span: source_map.span_to_embeddable_string(block),
+                        safety_comment: has_safety_comment(tcx, block),
+                        operations: describe_operations(tcx, operations),
+                        foreign_items: None,
+                    },
+                ));
+            }
+        }
+
+        for owner_id in tcx.hir_crate_items(()).owners() {
+            let def_id = owner_id.def_id;
+            let (kind, operations, foreign_items) = match tcx.hir_owner_node(owner_id) {
+                OwnerNode::Item(hir::Item { kind: ItemKind::Impl(impl_), .. })
+                    if impl_.safety == hir::Safety::Unsafe =>
+                {
+                    ("unsafe impl", vec![], None)
+                }
+                OwnerNode::Item(hir::Item { kind: ItemKind::ForeignMod { items, .. }, .. }) => {
+                    let items =
+                        items.iter().map(|item| tcx.def_path_str(item.id.owner_id)).collect();
+                    ("extern block", vec![], Some(items))
+                }
+                node if let Some(sig) = node.fn_sig()
+                    && sig.header.safety == hir::Safety::Unsafe =>
+                {
+                    // Required trait methods have no body to check.
+                    let operations = if tcx.hir().maybe_body_owned_by(def_id).is_some() {
+                        let result = tcx.check_unsafety(def_id);
+                        let operations =
+                            result.unsafe_ops.iter().filter(|op| op.unsafe_block.is_none());
+                        describe_operations(tcx, operations)
+                    } else {
+                        vec![]
+                    };
+                    ("unsafe fn", operations, None)
+                }
+                _ => continue,
+            };
+            let span = tcx.def_span(def_id);
+            items.push((
+                span,
+                UnsafeItem {
+                    kind,
+                    item: tcx.def_path_str(def_id),
+                    span: source_map.span_to_embeddable_string(span),
+                    safety_comment: has_safety_comment(tcx, span),
+                    operations,
+                    foreign_items,
+                },
+            ));
+        }
+    });
+    items.sort_by_key(|&(span, _)| span);
+
+    let report = UnsafeReport {
+        crate_name: tcx.crate_name(LOCAL_CRATE).to_string(),
+        items: items.into_iter().map(|(_, item)| item).collect(),
+    };
+    let mut json = serde_json::to_string(&report).unwrap();
+    json.push('\n');
+    tcx.output_filenames(()).path(OutputType::UnsafeReport).overwrite(&json, tcx.sess);
+}
+
+fn describe_operations<'a>(
+    tcx: TyCtxt<'_>,
+    operations: impl Iterator<Item = &'a UnsafeOperation>,
+) -> Vec<Operation> {
+    operations
+        .map(|op| {
+            let (kind, callee) = match op.kind {
+                UnsafeOperationKind::CallToUnsafeFunction(Some(def_id))
+                    if tcx.is_foreign_item(def_id) && tcx.intrinsic(def_id).is_none() =>
+                {
+                    ("call to foreign function", Some(def_id))
+                }
+                UnsafeOperationKind::CallToUnsafeFunction(def_id) => {
+                    ("call to unsafe function", def_id)
+                }
+                UnsafeOperationKind::UseOfInlineAssembly => ("use of inline assembly", None),
+                UnsafeOperationKind::InitializingTypeWithLayoutConstraints => {
+                    ("initializing type with layout constraints", None)
+                }
+                UnsafeOperationKind::UseOfMutableStatic => ("use of mutable static", None),
+                UnsafeOperationKind::UseOfExternStatic => ("use of extern static", None),
+                UnsafeOperationKind::DerefOfRawPointer => ("dereference of raw pointer", None),
+                UnsafeOperationKind::AccessToUnionField => ("access to union field", None),
+                UnsafeOperationKind::MutationOfLayoutConstrainedField => {
+                    ("mutation of layout constrained field", None)
+                }
+                UnsafeOperationKind::BorrowOfLayoutConstrainedField => {
+                    ("borrow of layout constrained field", None)
+                }
+                UnsafeOperationKind::CallToFunctionWithTargetFeatures(def_id) => {
+                    ("call to function with target features", Some(def_id))
+                }
+            };
+            Operation {
+                kind,
+                span: tcx.sess.source_map().span_to_embeddable_string(op.span),
+                callee: callee.map(|def_id| tcx.def_path_str(def_id)),
+            }
+        })
+        .collect()
+}
+
+/// Whether the code at `span` is justified by a comment with `SAFETY:`, or a `# Safety` doc
+/// section, before it on its line or on the lines of comments and attributes right above it.
+fn has_safety_comment(tcx: TyCtxt<'_>, span: Span) -> bool {
+    let is_safety_comment = |line: &str| line.contains("SAFETY:") || line.contains("# Safety");
+    let loc = tcx.sess.source_map().lookup_char_pos(span.lo());
+    let Some(line) = loc.file.get_line(loc.line - 1) else {
+        return false;
+    };
+    let before_span: String = line.chars().take(loc.col.0).collect();
+    if is_safety_comment(&before_span) {
+        return true;
+    }
+    // The lines are read upwards, so a block comment spanning several lines starts at its `*/`.
+    let mut in_block_comment = false;
+    for line_index in (0..loc.line - 1).rev() {
+        let Some(line) = loc.file.get_line(line_index) else {
+            return false;
+        };
+        let line = line.trim();
+        let is_comment_or_attribute = if in_block_comment {
+            in_block_comment = !line.contains("/*");
+            true
+        } else if line.ends_with("*/") && !line.contains("/*") {
+            in_block_comment = true;
+            true
+        } else {
+            line.starts_with("//") || line.starts_with("/*") || line.starts_with("#[")
+        };
+        if !is_comment_or_attribute {
+            return false;
+        }
+        if is_safety_comment(line) {
+            return true;
+        }
+    }
+    false
+}
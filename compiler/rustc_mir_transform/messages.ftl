@@ -17,6 +17,10 @@ mir_transform_ffi_unwind_call = call to {$foreign ->
 mir_transform_fn_item_ref = taking a reference to a function item does not give a function pointer
     .suggestion = cast `{$ident}` to obtain a function pointer
 
+mir_transform_inline_mir_profile_malformed = malformed line {$line} in the MIR inliner profile `{$path}`, expected `<count> <function>`
+
+mir_transform_inline_mir_profile_read = failed to read the MIR inliner profile `{$path}`: {$error}
+
 mir_transform_must_not_suspend = {$pre}`{$def_path}`{$post} held across a suspend point, but should not be
     .label = the value is held across this suspend point
     .note = {$reason}
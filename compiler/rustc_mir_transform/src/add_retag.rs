@@ -49,7 +49,7 @@ fn may_contain_reference<'tcx>(ty: Ty<'tcx>, depth: u32, tcx: TyCtxt<'tcx>) -> b
 }
 
 impl<'tcx> MirPass<'tcx> for AddRetag {
-    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+    fn is_enabled(&self, sess: &rustc_session::Session, _mir_opt_level: usize) -> bool {
         sess.opts.unstable_opts.mir_emit_retag
     }
 
@@ -9,7 +9,7 @@
 pub struct CheckAlignment;
 
 impl<'tcx> MirPass<'tcx> for CheckAlignment {
-    fn is_enabled(&self, sess: &Session) -> bool {
+    fn is_enabled(&self, sess: &Session, _mir_opt_level: usize) -> bool {
         // FIXME(#112480) MSVC and rustc disagree on minimum stack alignment on x86 Windows
         if sess.target.llvm_target == "i686-pc-windows-msvc" {
             return false;
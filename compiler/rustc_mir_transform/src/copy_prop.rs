@@ -19,8 +19,8 @@
 pub struct CopyProp;
 
 impl<'tcx> MirPass<'tcx> for CopyProp {
-    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
-        sess.mir_opt_level() >= 1
+    fn is_enabled(&self, _sess: &rustc_session::Session, mir_opt_level: usize) -> bool {
+        mir_opt_level >= 1
     }
 
     #[instrument(level = "trace", skip(self, tcx, body))]
@@ -28,6 +28,10 @@ fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
         debug!(def_id = ?body.source.def_id());
         propagate_ssa(tcx, body);
     }
+
+    fn is_required(&self) -> bool {
+        false
+    }
 }
 
 fn propagate_ssa<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
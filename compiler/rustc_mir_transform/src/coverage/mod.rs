@@ -35,7 +35,7 @@
 pub struct InstrumentCoverage;
 
 impl<'tcx> MirPass<'tcx> for InstrumentCoverage {
-    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+    fn is_enabled(&self, sess: &rustc_session::Session, _mir_opt_level: usize) -> bool {
         sess.instrument_coverage()
     }
 
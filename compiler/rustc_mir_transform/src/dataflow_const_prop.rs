@@ -28,8 +28,8 @@
 pub struct DataflowConstProp;
 
 impl<'tcx> MirPass<'tcx> for DataflowConstProp {
-    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
-        sess.mir_opt_level() >= 3
+    fn is_enabled(&self, _sess: &rustc_session::Session, mir_opt_level: usize) -> bool {
+        mir_opt_level >= 3
     }
 
     #[instrument(skip_all level = "debug")]
@@ -64,6 +64,10 @@ fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
         let mut patch = visitor.patch;
         debug_span!("patch").in_scope(|| patch.visit_body_preserves_cfg(body));
     }
+
+    fn is_required(&self) -> bool {
+        false
+    }
 }
 
 struct ConstAnalysis<'a, 'tcx> {
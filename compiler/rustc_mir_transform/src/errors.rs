@@ -1,3 +1,5 @@
+use std::path::Path;
+
 use rustc_errors::codes::*;
 use rustc_errors::{Diag, LintDiagnostic};
 use rustc_macros::{Diagnostic, LintDiagnostic, Subdiagnostic};
@@ -89,6 +91,20 @@ pub(crate) struct FnItemRef {
     pub ident: String,
 }
 
+#[derive(Diagnostic)]
+#[diag(mir_transform_inline_mir_profile_read)]
+pub(crate) struct InlineMirProfileRead<'a> {
+    pub path: &'a Path,
+    pub error: std::io::Error,
+}
+
+#[derive(Diagnostic)]
+#[diag(mir_transform_inline_mir_profile_malformed)]
+pub(crate) struct InlineMirProfileMalformed<'a> {
+    pub path: &'a Path,
+    pub line: usize,
+}
+
 pub(crate) struct MustNotSupend<'tcx, 'a> {
     pub tcx: TyCtxt<'tcx>,
     pub yield_sp: Span,
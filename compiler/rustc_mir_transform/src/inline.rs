@@ -4,33 +4,60 @@
 use std::ops::{Range, RangeFrom};
 
 use rustc_attr::InlineAttr;
+use rustc_data_structures::fx::FxIndexSet;
 use rustc_hir::def::DefKind;
 use rustc_hir::def_id::DefId;
 use rustc_index::bit_set::BitSet;
 use rustc_index::Idx;
 use rustc_middle::bug;
 use rustc_middle::middle::codegen_fn_attrs::{CodegenFnAttrFlags, CodegenFnAttrs};
+use rustc_middle::mir::mono::MonoItem;
 use rustc_middle::mir::visit::*;
 use rustc_middle::mir::*;
 use rustc_middle::ty::{
     self, Instance, InstanceKind, ParamEnv, Ty, TyCtxt, TypeFlags, TypeVisitableExt,
 };
-use rustc_session::config::{DebugInfo, OptLevel};
+use rustc_session::config::{DebugInfo, OptLevel, OutputType};
+use rustc_session::opt_remarks::{OptRemark, OptRemarkKind, OptRemarkLocation, OptRemarkOrigin};
 use rustc_span::source_map::Spanned;
-use rustc_span::sym;
+use rustc_span::{sym, FileNameDisplayPreference, Pos};
 use rustc_target::abi::FieldIdx;
 use rustc_target::spec::abi::Abi;
 
 use crate::cost_checker::CostChecker;
 use crate::deref_separator::deref_finder;
+use crate::outline_cold_paths;
 use crate::simplify::simplify_cfg;
 use crate::util;
 use crate::validate::validate_types;
 
 pub(crate) mod cycle;
+pub(crate) mod profile;
 
 const TOP_DOWN_DEPTH_LIMIT: usize = 5;
 
+/// Records the remarks of the MIR inliner, which are in the optimized MIR of the callers, for
+/// `--emit=opt-remarks`. Only the remarks of the functions that are codegened are recorded, so
+/// that the MIR of the other functions isn't optimized for them.
+pub fn record_opt_remarks(tcx: TyCtxt<'_>) {
+    let (_, codegen_units) = tcx.collect_and_partition_mono_items(());
+    let mut def_ids = FxIndexSet::default();
+    for cgu in codegen_units {
+        for item in cgu.items().keys() {
+            if let MonoItem::Fn(Instance { def: InstanceKind::Item(def_id), .. }) = *item
+                && def_id.is_local()
+            {
+                def_ids.insert(def_id);
+            }
+        }
+    }
+    for def_id in def_ids {
+        for remark in &tcx.optimized_mir(def_id).opt_remarks {
+            tcx.sess.opt_remarks.record(remark.clone());
+        }
+    }
+}
+
 pub struct Inline;
 
 #[derive(Copy, Clone, Debug)]
@@ -42,7 +69,7 @@ struct CallSite<'tcx> {
 }
 
 impl<'tcx> MirPass<'tcx> for Inline {
-    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+    fn is_enabled(&self, sess: &rustc_session::Session, mir_opt_level: usize) -> bool {
         // FIXME(#127234): Coverage instrumentation currently doesn't handle inlined
         // MIR correctly when Modified Condition/Decision Coverage is enabled.
         if sess.instrument_coverage_mcdc() {
@@ -53,7 +80,7 @@ fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
             return enabled;
         }
 
-        match sess.mir_opt_level() {
+        match mir_opt_level {
             0 | 1 => false,
             2 => {
                 (sess.opts.optimize == OptLevel::Default
@@ -73,6 +100,10 @@ fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
             deref_finder(tcx, body);
         }
     }
+
+    fn is_required(&self) -> bool {
+        false
+    }
 }
 
 fn inline<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) -> bool {
@@ -152,7 +183,11 @@ fn process_blocks(&mut self, caller_body: &mut Body<'tcx>, blocks: Range<BasicBl
             let span = trace_span!("process_blocks", %callsite.callee, ?bb);
             let _guard = span.enter();
 
-            match self.try_inlining(caller_body, &callsite) {
+            let result = self.try_inlining(caller_body, &callsite);
+            if self.tcx.sess.opts.output_types.contains_key(&OutputType::OptRemarks) {
+                self.record_remark(caller_body, &callsite, &result);
+            }
+            match result {
                 Err(reason) => {
                     debug!("not-inlined {} [{}]", callsite.callee, reason);
                     continue;
@@ -175,6 +210,44 @@ fn process_blocks(&mut self, caller_body: &mut Body<'tcx>, blocks: Range<BasicBl
         }
     }
 
+    /// Records the decision of inlining `callsite` or not in the remarks of `caller_body`, for
+    /// `--emit=opt-remarks`.
+    fn record_remark(
+        &self,
+        caller_body: &mut Body<'tcx>,
+        callsite: &CallSite<'tcx>,
+        result: &Result<std::ops::Range<BasicBlock>, &'static str>,
+    ) {
+        let tcx = self.tcx;
+        let caller = tcx.def_path_str(caller_body.source.def_id());
+        let (kind, message) = match result {
+            Ok(_) => {
+                (OptRemarkKind::Success, format!("`{}` inlined into `{caller}`", callsite.callee))
+            }
+            Err(reason) => (
+                OptRemarkKind::Missed,
+                format!("`{}` not inlined into `{caller}`: {reason}", callsite.callee),
+            ),
+        };
+        let span = callsite.source_info.span.source_callsite();
+        let location = (!span.is_dummy()).then(|| {
+            let loc = tcx.sess.source_map().lookup_char_pos(span.lo());
+            OptRemarkLocation {
+                file: loc.file.name.display(FileNameDisplayPreference::Remapped).to_string(),
+                line: loc.line as u32,
+                column: loc.col.to_u32() + 1,
+            }
+        });
+        caller_body.opt_remarks.push(OptRemark {
+            location,
+            origin: OptRemarkOrigin::Mir,
+            pass: "inline".to_owned(),
+            kind,
+            function: caller,
+            message,
+        });
+    }
+
     /// Attempts to inline a callsite into the caller body. When successful returns basic blocks
     /// containing the inlined body. Otherwise returns an error describing why inlining didn't take
     /// place.
@@ -210,6 +283,13 @@ fn try_inlining(
         let callee_body = try_instance_mir(self.tcx, callsite.callee.def)?;
         self.check_mir_body(callsite, callee_body, callee_attrs, cross_crate_inlinable)?;
 
+        // The cold paths outlined by the local crate can't be called by the other crates.
+        if !callee_body.cold_paths.is_empty()
+            && outline_cold_paths::mir_is_used_cross_crate(self.tcx, caller_body.source.def_id())
+        {
+            return Err("callee calls cold paths, and caller MIR is used by other crates");
+        }
+
         if !self.tcx.consider_optimizing(|| {
             format!("Inline {:?} into {:?}", callsite.callee, caller_body.source)
         }) {
@@ -346,7 +426,9 @@ fn check_mir_is_available(
             | InstanceKind::CloneShim(..)
             | InstanceKind::ThreadLocalShim(..)
             | InstanceKind::FnPtrAddrShim(..)
-            | InstanceKind::AsyncDropGlueCtorShim(..) => return Ok(()),
+            | InstanceKind::AsyncDropGlueCtorShim(..)
+            | InstanceKind::ConstArgsShim(..)
+            | InstanceKind::ColdPathShim { .. } => return Ok(()),
         }
 
         if self.tcx.is_constructor(callee_def_id) {
@@ -446,6 +528,10 @@ fn check_codegen_attributes(
             return Err("never inline hint");
         }
 
+        if callee_attrs.optimize.do_not_optimize() {
+            return Err("callee is not optimized");
+        }
+
         // Reachability pass defines which functions are eligible for inlining. Generally inlining
         // other functions is incorrect because they could reference symbols that aren't exported.
         let is_generic = callsite
@@ -511,6 +597,16 @@ fn check_mir_body(
             self.tcx.sess.opts.unstable_opts.inline_mir_threshold.unwrap_or(50)
         };
 
+        // Inline the functions that are hot according to the profile more. The query is only
+        // called with a profile, so that `optimized_mir` doesn't depend on it without one.
+        if tcx.sess.opts.unstable_opts.inline_mir_profile.is_some()
+            && let Some(hotness) = tcx.function_hotness(())
+            && hotness.is_hot(&profile::function_path(tcx, callsite.callee.def_id()))
+        {
+            let hot_threshold = tcx.sess.opts.unstable_opts.inline_mir_hot_threshold.unwrap_or(400);
+            threshold = threshold.max(hot_threshold);
+        }
+
         // Give a bonus functions with a small number of blocks,
         // We normally have two or three blocks for even
         // very small functions.
@@ -765,6 +861,17 @@ fn dest_needs_borrow(place: Place<'_>) -> bool {
             // already got removed by being inlined elsewhere in the same function, so we already
             // took its items.
         }
+
+        // The cold paths called by the callee are now called by the caller.
+        for cold_path in callee_body.cold_paths {
+            if !caller_body
+                .cold_paths
+                .iter()
+                .any(|other| other.source.instance == cold_path.source.instance)
+            {
+                caller_body.cold_paths.push(cold_path);
+            }
+        }
     }
 
     fn make_call_args(
@@ -92,6 +92,9 @@ fn process<'tcx>(
                 | InstanceKind::ThreadLocalShim { .. }
                 | InstanceKind::CloneShim(..) => {}
 
+                // These are only called through `fn` pointers, which are not inlined.
+                InstanceKind::ConstArgsShim(..) | InstanceKind::ColdPathShim { .. } => continue,
+
                 // This shim does not call any other functions, thus there can be no recursion.
                 InstanceKind::FnPtrAddrShim(..) => {
                     continue;
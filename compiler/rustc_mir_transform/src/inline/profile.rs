@@ -0,0 +1,169 @@
+//! The profile of the MIR inliner, given with `-Z inline-mir-profile`.
+//!
+//! The profile is a text file with the number of calls to functions, one function per line, like
+//! `12345 core::iter::traits::iterator::Iterator::sum`. Functions are either symbol names, like
+//! those of the profiles of LLVM, or demangled paths. The calls to all the instances of a generic
+//! function are counted together, and the most called functions, which account for 99% of all
+//! calls, are hot.
+//!
+//! Functions are looked up by their path without generic arguments, since the MIR inliner works
+//! on the generic bodies of functions, before their instances are known.
+
+#[cfg(test)]
+mod tests;
+
+use rustc_data_structures::unord::UnordMap;
+use rustc_hir::def_id::{DefId, LOCAL_CRATE};
+use rustc_middle::middle::function_hotness::FunctionHotness;
+use rustc_middle::ty::print::{with_crate_prefix, with_no_trimmed_paths, with_no_visible_paths};
+use rustc_middle::ty::TyCtxt;
+
+use crate::errors;
+
+/// The share of the calls of the profile, in per mille, that go to hot functions.
+const HOT_CALLS_PER_MILLE: u128 = 990;
+
+pub(crate) fn function_hotness(tcx: TyCtxt<'_>, (): ()) -> Option<FunctionHotness> {
+    let path = tcx.sess.opts.unstable_opts.inline_mir_profile.as_deref()?;
+    let contents = std::fs::read_to_string(path)
+        .unwrap_or_else(|error| tcx.dcx().emit_fatal(errors::InlineMirProfileRead { path, error }));
+
+    // The paths of local functions start with `crate::`, or nothing before Rust 2018.
+    let local_crate = tcx.crate_name(LOCAL_CRATE);
+    let local_prefix = if tcx.sess.at_least_rust_2018() { "crate::" } else { "" };
+
+    let mut counts = UnordMap::default();
+    for (index, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let Some((count, function)) = line
+            .split_once(char::is_whitespace)
+            .and_then(|(count, function)| Some((count.parse::<u64>().ok()?, function.trim())))
+        else {
+            tcx.dcx().emit_fatal(errors::InlineMirProfileMalformed { path, line: index + 1 });
+        };
+        let function = normalize_path(&demangle(function));
+        let function = replace_crate_name(&function, local_crate.as_str(), local_prefix);
+        *counts.entry(function).or_default() += count;
+    }
+
+    let hot_count = hot_count(counts.items().map(|(_, &count)| count).into_sorted_stable_ord());
+    Some(FunctionHotness { counts, hot_count })
+}
+
+/// Returns the path of `def_id` as it is looked up in the profile.
+pub(crate) fn function_path(tcx: TyCtxt<'_>, def_id: DefId) -> String {
+    let path = with_crate_prefix!(with_no_visible_paths!(with_no_trimmed_paths!(
+        tcx.def_path_str(def_id)
+    )));
+    normalize_path(&path)
+}
+
+/// Returns the smallest number of calls of the most called functions that account for
+/// `HOT_CALLS_PER_MILLE` of all calls, or `None` if no function was called.
+fn hot_count(mut counts: Vec<u64>) -> Option<u64> {
+    counts.sort_unstable_by(|a, b| b.cmp(a));
+    let total: u128 = counts.iter().map(|&count| u128::from(count)).sum();
+    let mut calls = 0;
+    for count in counts {
+        if count == 0 {
+            break;
+        }
+        calls += u128::from(count);
+        if calls * 1000 >= total * HOT_CALLS_PER_MILLE {
+            return Some(count);
+        }
+    }
+    None
+}
+
+/// Demangles the symbol name `function`, or returns it as is if it is already a path.
+fn demangle(function: &str) -> String {
+    // The profiles of LLVM prefix the names of internal functions with their file, like
+    // `main.rs;_ZN4main3foo17h0123456789abcdefE`.
+    let symbol = function.rsplit_once([';', ':']).map_or(function, |(_, symbol)| symbol);
+    match rustc_demangle::try_demangle(symbol) {
+        Ok(demangled) => format!("{demangled:#}"),
+        Err(_) => function.to_owned(),
+    }
+}
+
+/// Returns `path` without the generic arguments of its segments, like `u8` in
+/// `<alloc::vec::Vec<u8> as core::ops::drop::Drop>::drop`, and without the angle brackets of
+/// the qualified paths of inherent impls, like in `<alloc::vec::Vec<u8>>::push`, so that
+/// demangled symbols and the paths printed by rustc are the same.
+fn normalize_path(path: &str) -> String {
+    let mut normalized = String::with_capacity(path.len());
+    let mut rest = path;
+    while let Some(start) = rest.find('<') {
+        normalized.push_str(&rest[..start]);
+        let Some(len) = angle_bracketed_len(&rest[start..]) else {
+            // Unbalanced brackets, keep the rest as is.
+            rest = &rest[start..];
+            break;
+        };
+        let inner = &rest[start + 1..start + len - 1];
+        rest = &rest[start + len..];
+        let follows_segment =
+            normalized.ends_with(|prev: char| prev.is_alphanumeric() || prev == '_' || prev == '}');
+        if follows_segment {
+            // Generic arguments, like `Vec<u8>`.
+        } else if let Some(segment) = normalized.strip_suffix("::") {
+            // Generic arguments with a turbofish, like `Vec::<u8>`.
+            normalized.truncate(segment.len());
+        } else if inner.contains(" as ") {
+            normalized.push('<');
+            normalized.push_str(&normalize_path(inner));
+            normalized.push('>');
+        } else {
+            normalized.push_str(&normalize_path(inner));
+        }
+    }
+    normalized.push_str(rest);
+    normalized
+}
+
+/// Returns the length of the angle-bracketed group at the start of `s`, brackets included.
+fn angle_bracketed_len(s: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut prev = ' ';
+    for (index, c) in s.char_indices() {
+        match c {
+            '<' => depth += 1,
+            // The `>` of `->` in function pointer types doesn't close anything.
+            '>' if prev != '-' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(index + 1);
+                }
+            }
+            _ => {}
+        }
+        prev = c;
+    }
+    None
+}
+
+/// Replaces the segments of `path` that are the name of the local crate by `local_prefix`, the
+/// way rustc prints the paths of local items.
+fn replace_crate_name(path: &str, local_crate: &str, local_prefix: &str) -> String {
+    let crate_segment = format!("{local_crate}::");
+    let mut replaced = String::with_capacity(path.len());
+    let mut rest = path;
+    while let Some(index) = rest.find(&crate_segment) {
+        let prev = rest[..index].chars().next_back().or_else(|| replaced.chars().next_back());
+        let at_start_of_path =
+            !prev.is_some_and(|prev| prev.is_alphanumeric() || prev == '_' || prev == ':');
+        replaced.push_str(&rest[..index]);
+        if at_start_of_path {
+            replaced.push_str(local_prefix);
+        } else {
+            replaced.push_str(&crate_segment);
+        }
+        rest = &rest[index + crate_segment.len()..];
+    }
+    replaced.push_str(rest);
+    replaced
+}
@@ -0,0 +1,54 @@
+use super::{demangle, normalize_path, replace_crate_name};
+
+#[test]
+fn demangle_symbols() {
+    assert_eq!(demangle("_ZN4main3foo17h0123456789abcdefE"), "main::foo");
+    assert_eq!(demangle("_RNvCs1234_4main3foo"), "main::foo");
+    // Internal functions are prefixed with their file.
+    assert_eq!(demangle("main.rs;_ZN4main3foo17h0123456789abcdefE"), "main::foo");
+    // Paths are kept as is.
+    assert_eq!(
+        demangle("core::iter::traits::iterator::Iterator::sum"),
+        "core::iter::traits::iterator::Iterator::sum"
+    );
+}
+
+#[test]
+fn normalize_generic_args() {
+    assert_eq!(normalize_path("alloc::vec::Vec<u8>::push"), "alloc::vec::Vec::push");
+    assert_eq!(normalize_path("core::mem::replace::<u8>"), "core::mem::replace");
+    assert_eq!(
+        normalize_path("<alloc::vec::Vec<u8> as core::ops::drop::Drop>::drop"),
+        "<alloc::vec::Vec as core::ops::drop::Drop>::drop"
+    );
+    assert_eq!(
+        normalize_path("<fn() -> u8 as core::clone::Clone>::clone"),
+        "<fn() -> u8 as core::clone::Clone>::clone"
+    );
+}
+
+#[test]
+fn normalize_inherent_impls() {
+    assert_eq!(normalize_path("<alloc::vec::Vec<u8>>::push"), "alloc::vec::Vec::push");
+}
+
+#[test]
+fn normalize_unbalanced_brackets() {
+    assert_eq!(normalize_path("foo::<bar"), "foo::<bar");
+}
+
+#[test]
+fn replace_local_crate_name() {
+    assert_eq!(replace_crate_name("main::foo", "main", "crate::"), "crate::foo");
+    assert_eq!(replace_crate_name("main::foo", "main", ""), "foo");
+    assert_eq!(
+        replace_crate_name("<main::S as core::clone::Clone>::clone", "main", "crate::"),
+        "<crate::S as core::clone::Clone>::clone"
+    );
+}
+
+#[test]
+fn keep_other_crate_names() {
+    assert_eq!(replace_crate_name("domain::foo", "main", "crate::"), "domain::foo");
+    assert_eq!(replace_crate_name("other::main::foo", "main", "crate::"), "other::main::foo");
+}
@@ -20,6 +20,7 @@
 
 use hir::ConstContext;
 use required_consts::RequiredConstsVisitor;
+use rustc_attr::OptimizeAttr;
 use rustc_const_eval::util;
 use rustc_data_structures::fx::FxIndexSet;
 use rustc_data_structures::steal::Steal;
@@ -36,6 +37,7 @@
 use rustc_middle::ty::{self, TyCtxt, TypeVisitableExt};
 use rustc_middle::util::Providers;
 use rustc_middle::{bug, query, span_bug};
+use rustc_session::config::OptLevel;
 use rustc_span::source_map::Spanned;
 use rustc_span::{sym, DUMMY_SP};
 use rustc_trait_selection::traits;
@@ -87,6 +89,7 @@
 mod mentioned_items;
 mod multiple_return_terminators;
 mod nrvo;
+mod outline_cold_paths;
 mod prettify;
 mod promote_consts;
 mod ref_prop;
@@ -105,6 +108,7 @@
 mod simplify_branches;
 mod simplify_comparison_integral;
 mod single_use_consts;
+mod specialize_const_args;
 mod sroa;
 mod unreachable_enum_branching;
 mod unreachable_prop;
@@ -133,6 +137,7 @@ pub fn provide(providers: &mut Providers) {
         is_ctfe_mir_available: |tcx, did| is_mir_available(tcx, did),
         mir_callgraph_reachable: inline::cycle::mir_callgraph_reachable,
         mir_inliner_callees: inline::cycle::mir_inliner_callees,
+        function_hotness: inline::profile::function_hotness,
         promoted_mir,
         deduced_param_attrs: deduce_param_attrs::deduced_param_attrs,
         ..providers.queries
@@ -302,6 +307,7 @@ fn mir_built(tcx: TyCtxt<'_>, def: LocalDefId) -> &Steal<Body<'_>> {
             &rustc_peek::SanityCheck, // Just a lint
         ],
         None,
+        pm::Optimizations::Allowed,
     );
     tcx.alloc_steal_mir(body)
 }
@@ -355,6 +361,7 @@ fn mir_promoted(
         &mut body,
         &[&promote_pass, &simplify::SimplifyCfg::PromoteConsts, &coverage::InstrumentCoverage],
         Some(MirPhase::Analysis(AnalysisPhase::Initial)),
+        pm::Optimizations::Allowed,
     );
 
     let promoted = promote_pass.promoted_fragments.into_inner();
@@ -386,7 +393,7 @@ fn inner_mir_for_ctfe(tcx: TyCtxt<'_>, def: LocalDefId) -> Body<'_> {
     };
 
     let mut body = remap_mir_for_const_eval_select(tcx, body, hir::Constness::Const);
-    pm::run_passes(tcx, &mut body, &[&ctfe_limit::CtfeLimit], None);
+    pm::run_passes(tcx, &mut body, &[&ctfe_limit::CtfeLimit], None, pm::Optimizations::Allowed);
 
     body
 }
@@ -403,7 +410,7 @@ fn mir_drops_elaborated_and_const_checked(tcx: TyCtxt<'_>, def: LocalDefId) -> &
     let is_fn_like = tcx.def_kind(def).is_fn_like();
     if is_fn_like {
         // Do not compute the mir call graph without said call graph actually being used.
-        if pm::should_run_pass(tcx, &inline::Inline) {
+        if pm::should_run_pass(tcx, &inline::Inline, pm::Optimizations::Allowed) {
             tcx.ensure_with_value().mir_inliner_callees(ty::InstanceKind::Item(def.to_def_id()));
         }
     }
@@ -484,6 +491,7 @@ pub fn run_analysis_to_runtime_passes<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'
             body,
             &[&remove_uninit_drops::RemoveUninitDrops, &simplify::SimplifyCfg::RemoveFalseEdges],
             None,
+            pm::Optimizations::Allowed,
         );
         check_consts::post_drop_elaboration::check_live_drops(tcx, body); // FIXME: make this a MIR lint
     }
@@ -508,7 +516,13 @@ fn run_analysis_cleanup_passes<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
         &deref_separator::Derefer,
     ];
 
-    pm::run_passes(tcx, body, passes, Some(MirPhase::Analysis(AnalysisPhase::PostCleanup)));
+    pm::run_passes(
+        tcx,
+        body,
+        passes,
+        Some(MirPhase::Analysis(AnalysisPhase::PostCleanup)),
+        pm::Optimizations::Allowed,
+    );
 }
 
 /// Returns the sequence of passes that lowers analysis to runtime MIR.
@@ -544,7 +558,13 @@ fn run_runtime_cleanup_passes<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
         &simplify::SimplifyCfg::PreOptimizations,
     ];
 
-    pm::run_passes(tcx, body, passes, Some(MirPhase::Runtime(RuntimePhase::PostCleanup)));
+    pm::run_passes(
+        tcx,
+        body,
+        passes,
+        Some(MirPhase::Runtime(RuntimePhase::PostCleanup)),
+        pm::Optimizations::Allowed,
+    );
 
     // Clear this by anticipation. Optimizations and runtime MIR have no reason to look
     // into this information, which is meant for borrowck diagnostics.
@@ -558,6 +578,29 @@ fn o1<T>(x: T) -> WithMinOptLevel<T> {
         WithMinOptLevel(1, x)
     }
 
+    let def_id = body.source.def_id();
+    let optimize = if tcx.def_kind(def_id).has_codegen_attrs() {
+        tcx.codegen_fn_attrs(def_id).optimize.clone()
+    } else {
+        OptimizeAttr::None
+    };
+    // Like `-C opt-level`, the optimization levels other than 0 set the MIR optimization level of
+    // the function, which can only be lower than that of the crate.
+    let mir_opt_level = tcx.sess.mir_opt_level();
+    let optimizations = match optimize {
+        OptimizeAttr::Level(OptLevel::No) => pm::Optimizations::Suppressed,
+        OptimizeAttr::Level(OptLevel::Less) if mir_opt_level > 1 => pm::Optimizations::Limited(1),
+        OptimizeAttr::Level(OptLevel::Default | OptLevel::Size | OptLevel::SizeMin)
+            if mir_opt_level > 2 =>
+        {
+            pm::Optimizations::Limited(2)
+        }
+        OptimizeAttr::Level(OptLevel::Aggressive) if mir_opt_level > 3 => {
+            pm::Optimizations::Limited(3)
+        }
+        _ => pm::Optimizations::Allowed,
+    };
+
     // The main optimizations that we do on MIR.
     pm::run_passes(
         tcx,
@@ -599,6 +642,8 @@ fn o1<T>(x: T) -> WithMinOptLevel<T> {
             &simplify::SimplifyLocals::AfterGVN,
             &dataflow_const_prop::DataflowConstProp,
             &single_use_consts::SingleUseConsts,
+            // After constant propagation, which turns the arguments of calls into constants.
+            &specialize_const_args::SpecializeConstArgs,
             &o1(simplify_branches::SimplifyConstCondition::AfterConstProp),
             &jump_threading::JumpThreading,
             &early_otherwise_branch::EarlyOtherwiseBranch,
@@ -613,6 +658,8 @@ fn o1<T>(x: T) -> WithMinOptLevel<T> {
             &simplify::SimplifyLocals::Final,
             &multiple_return_terminators::MultipleReturnTerminators,
             &deduplicate_blocks::DeduplicateBlocks,
+            // After deduplication, so that identical cold paths are outlined once.
+            &outline_cold_paths::OutlineColdPaths,
             &large_enums::EnumSizeOpt { discrepancy: 128 },
             // Some cleanup necessary at least for LLVM and potentially other codegen backends.
             &add_call_guards::CriticalCallEdges,
@@ -623,6 +670,7 @@ fn o1<T>(x: T) -> WithMinOptLevel<T> {
             &dump_mir::Marker("PreCodegen"),
         ],
         Some(MirPhase::Runtime(RuntimePhase::Optimized)),
+        optimizations,
     );
 }
 
@@ -0,0 +1,577 @@
+//! Outlines the cold paths of functions, like the formatting of panic messages and the failures of
+//! assertions, into separate functions. The pass only runs with `-Z outline-cold-paths`.
+//!
+//! A block which ends in a call to a `#[cold]` function that never returns, like `panic_fmt` or
+//! `unwrap_failed`, is moved into a `ColdPathShim`, and replaced by a call to it. The statements
+//! of the block, which build the arguments of the call, then no longer take up space in the code
+//! of the function, which is often hot, nor in the code of the functions it is inlined into. The
+//! failure of an `Assert` terminator is moved into a `ColdPathShim` calling its panic function
+//! the same way, and the assertion becomes a branch on its condition.
+//!
+//! The cold paths don't keep the spans, scopes and debuginfo of the functions they are outlined
+//! from, and take the values they read as arguments. Each one is identified by the function it is
+//! outlined from and its index among the cold paths of that function, and its body is kept in the
+//! optimized MIR of that function. The functions it is inlined into call the same cold path. The
+//! panic location is computed where the cold path is called, and passed on to the `#[track_caller]`
+//! function the cold path calls, so panic messages and locations don't change.
+
+use rustc_attr::InlineAttr;
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::DefId;
+use rustc_hir::LangItem;
+use rustc_index::bit_set::BitSet;
+use rustc_index::IndexVec;
+use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
+use rustc_middle::mir::interpret::{Pointer, Scalar};
+use rustc_middle::mir::visit::{MutVisitor, MutatingUseContext, PlaceContext, Visitor};
+use rustc_middle::mir::*;
+use rustc_middle::ty::{self, Instance, Ty, TyCtxt};
+use rustc_session::config::InliningThreshold;
+use rustc_span::source_map::Spanned;
+use rustc_span::{Span, DUMMY_SP};
+use rustc_target::spec::abi::Abi;
+
+use crate::required_consts::RequiredConstsVisitor;
+use crate::{mentioned_items, pass_manager as pm};
+
+/// Blocks with fewer statements than this are not outlined, since the call to the cold path would
+/// take about as much space as they do.
+const MIN_STATEMENTS: usize = 2;
+
+pub struct OutlineColdPaths;
+
+impl<'tcx> MirPass<'tcx> for OutlineColdPaths {
+    fn is_enabled(&self, sess: &rustc_session::Session, mir_opt_level: usize) -> bool {
+        // The cold paths would increment the coverage counters of their function. When the MIR of
+        // all functions is encoded, it can be used by other crates, which can't call the cold
+        // paths of the local crate.
+        sess.opts.unstable_opts.outline_cold_paths
+            && mir_opt_level >= 2
+            && !sess.instrument_coverage()
+            && !sess.opts.unstable_opts.always_encode_mir
+    }
+
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        let def_id = body.source.def_id();
+        if body.source.promoted.is_some() || !is_outlinable_from(tcx, def_id) {
+            return;
+        }
+
+        let param_env = tcx.param_env_reveal_all_normalized(def_id);
+        for block in body.basic_blocks.indices() {
+            outline_block(tcx, param_env, body, block);
+            outline_assert_failure(tcx, body, block);
+        }
+    }
+
+    fn is_required(&self) -> bool {
+        false
+    }
+}
+
+/// Returns whether the optimized MIR of `def_id` may be used by other crates, which can't call the
+/// cold paths outlined by the local crate.
+pub(crate) fn mir_is_used_cross_crate(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
+    // The cross-crate inlinability of the other functions is inferred from bodies without calls,
+    // which the bodies calling cold paths are not.
+    !matches!(tcx.def_kind(def_id), DefKind::Fn | DefKind::AssocFn)
+        || tcx.generics_of(def_id).requires_monomorphization(tcx)
+        || matches!(tcx.codegen_fn_attrs(def_id).inline, InlineAttr::Hint | InlineAttr::Always)
+        || tcx.sess.opts.unstable_opts.cross_crate_inline_threshold == InliningThreshold::Always
+}
+
+/// Returns whether cold paths can be outlined from the function `def_id`.
+fn is_outlinable_from(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
+    // Cold paths are called through constant `fn` pointers, which can't depend on generic
+    // parameters.
+    if mir_is_used_cross_crate(tcx, def_id) || tcx.intrinsic(def_id).is_some() {
+        return false;
+    }
+
+    // The panic locations of a `#[track_caller]` function are those of its callers, which aren't
+    // known where it calls its cold paths. The cold paths don't have the attributes of the
+    // functions they are outlined from, like their target features.
+    let attrs = tcx.codegen_fn_attrs(def_id);
+    !attrs.flags.intersects(CodegenFnAttrFlags::TRACK_CALLER | CodegenFnAttrFlags::NAKED)
+        && attrs.target_features.is_empty()
+        && attrs.instruction_set.is_none()
+        && attrs.no_sanitize.is_empty()
+}
+
+/// Returns whether cold paths can end in a call to `callee`, which is how they are named, and
+/// whose attributes they have.
+fn is_outlinable_callee(tcx: TyCtxt<'_>, callee: DefId) -> bool {
+    let attrs = tcx.codegen_fn_attrs(callee);
+    attrs.flags.contains(CodegenFnAttrFlags::COLD)
+        && !attrs.flags.contains(CodegenFnAttrFlags::NAKED)
+        && !attrs.contains_extern_indicator()
+        && attrs.target_features.is_empty()
+        && attrs.instruction_set.is_none()
+        && !tcx.is_foreign_item(callee)
+        && tcx.intrinsic(callee).is_none()
+        && !tcx.generics_of(callee).requires_monomorphization(tcx)
+}
+
+/// Outlines `block` into a cold path if it ends in a call to a `#[cold]` function that never
+/// returns, and is worth outlining.
+fn outline_block<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    param_env: ty::ParamEnv<'tcx>,
+    body: &mut Body<'tcx>,
+    block: BasicBlock,
+) -> Option<()> {
+    let data = &body.basic_blocks[block];
+    // A cold entry block means that the whole function is cold.
+    if block == START_BLOCK || data.is_cleanup {
+        return None;
+    }
+    let terminator = data.terminator();
+    let TerminatorKind::Call { func, target: None, unwind, fn_span, .. } = &terminator.kind else {
+        return None;
+    };
+    let (callee, _) = func.const_fn_def()?;
+    if !is_outlinable_callee(tcx, callee) {
+        return None;
+    }
+
+    let mut locals = BlockLocals::new(body.local_decls.len());
+    locals.visit_basic_block_data(block, data);
+    let statements = data
+        .statements
+        .iter()
+        .filter(|statement| {
+            !matches!(
+                statement.kind,
+                StatementKind::StorageLive(_) | StatementKind::StorageDead(_) | StatementKind::Nop
+            )
+        })
+        .count();
+    if !locals.outlinable
+        || statements < MIN_STATEMENTS
+        || statements <= locals.inputs.len()
+        // The inputs are copied into the cold path, which must not change them.
+        || !locals.inputs.iter().all(|&local| {
+            local != RETURN_PLACE
+                && body.local_decls[local].ty.is_copy_modulo_regions(tcx, param_env)
+        })
+    {
+        return None;
+    }
+
+    // The locals which the block assigns must not be used by the rest of the function, like its
+    // cleanup blocks, since they are now only assigned in the cold path.
+    let mut used_elsewhere = UsedLocals(BitSet::new_empty(body.local_decls.len()));
+    for (other, other_data) in body.basic_blocks.iter_enumerated() {
+        if other != block {
+            used_elsewhere.visit_basic_block_data(other, other_data);
+        }
+    }
+    if locals.assigned.iter().any(|&local| used_elsewhere.0.contains(local)) {
+        return None;
+    }
+
+    // The panic location of a call is that of its `fn_span`.
+    let source_info = terminator.source_info;
+    let location_span =
+        body.caller_location_span(SourceInfo { span: *fn_span, ..source_info }, None, tcx, |s| s);
+    let unwind = *unwind;
+    let cold_path = build_cold_path(tcx, body, block, callee, &locals);
+    let args = locals.inputs.iter().map(|&local| Operand::Copy(local.into())).collect();
+    let call = call_cold_path(tcx, body, cold_path, args, source_info, location_span, unwind);
+
+    let data = &mut body.basic_blocks_mut()[block];
+    data.statements.clear();
+    data.terminator_mut().kind = call;
+    Some(())
+}
+
+/// Outlines the failure of the `Assert` terminator of `block` into a cold path, which calls the
+/// panic function of the assertion.
+fn outline_assert_failure<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &mut Body<'tcx>,
+    block: BasicBlock,
+) -> Option<()> {
+    let data = &body.basic_blocks[block];
+    if data.is_cleanup {
+        return None;
+    }
+    let terminator = data.terminator();
+    let TerminatorKind::Assert { cond, expected, msg, target, unwind } = &terminator.kind else {
+        return None;
+    };
+    let lang_item = match **msg {
+        AssertKind::BoundsCheck { .. } => LangItem::PanicBoundsCheck,
+        AssertKind::MisalignedPointerDereference { .. } => {
+            LangItem::PanicMisalignedPointerDereference
+        }
+        _ => msg.panic_function(),
+    };
+    let callee = tcx.lang_items().get(lang_item)?;
+    if !is_outlinable_callee(tcx, callee) {
+        return None;
+    }
+
+    // The panic functions of the other assertions only take the location of the failure, so the
+    // operands of their messages are replaced by zeros.
+    let pass_operands = matches!(
+        **msg,
+        AssertKind::BoundsCheck { .. } | AssertKind::MisalignedPointerDereference { .. }
+    );
+    let mut msg = msg.clone();
+    let mut operands = AssertOperands {
+        tcx,
+        local_decls: &body.local_decls,
+        pass_operands,
+        args: Vec::new(),
+        arg_tys: Vec::new(),
+    };
+    operands.visit_assert_message(&mut msg, Location::START);
+    let AssertOperands { args, arg_tys, .. } = operands;
+
+    let mut local_decls = IndexVec::new();
+    local_decls.push(LocalDecl::new(tcx.types.never, DUMMY_SP));
+    for ty in arg_tys {
+        local_decls.push(LocalDecl::new(ty, DUMMY_SP));
+    }
+    let cold_source_info = SourceInfo::outermost(DUMMY_SP);
+    let basic_blocks = IndexVec::from_raw(vec![
+        BasicBlockData::new(Some(Terminator {
+            source_info: cold_source_info,
+            kind: TerminatorKind::Assert {
+                cond: Operand::Constant(Box::new(ConstOperand {
+                    span: DUMMY_SP,
+                    user_ty: None,
+                    const_: Const::from_bool(tcx, false),
+                })),
+                expected: true,
+                msg,
+                target: BasicBlock::from_usize(1),
+                unwind: UnwindAction::Continue,
+            },
+        })),
+        BasicBlockData::new(Some(Terminator {
+            source_info: cold_source_info,
+            kind: TerminatorKind::Unreachable,
+        })),
+    ]);
+    let arg_count = args.len();
+    let cold_path = new_cold_path(tcx, body, callee, basic_blocks, local_decls, arg_count);
+
+    let source_info = terminator.source_info;
+    let location_span = body.caller_location_span(source_info, None, tcx, |span| span);
+    let (cond, expected, target, unwind) = (cond.clone(), *expected, *target, *unwind);
+    let call = call_cold_path(tcx, body, cold_path, args, source_info, location_span, unwind);
+    let failure = body
+        .basic_blocks_mut()
+        .push(BasicBlockData::new(Some(Terminator { source_info, kind: call })));
+    let (if_false, if_true) = if expected { (failure, target) } else { (target, failure) };
+    body.basic_blocks_mut()[block].terminator_mut().kind = TerminatorKind::SwitchInt {
+        discr: cond,
+        targets: SwitchTargets::static_if(0, if_false, if_true),
+    };
+    Some(())
+}
+
+/// Builds the cold path outlined from `block`, which takes the inputs of the block as arguments
+/// and ends in the call to `callee`.
+fn build_cold_path<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &Body<'tcx>,
+    block: BasicBlock,
+    callee: DefId,
+    locals: &BlockLocals,
+) -> Body<'tcx> {
+    let mut local_map = IndexVec::from_elem(None, &body.local_decls);
+    let mut local_decls = IndexVec::new();
+    local_decls.push(LocalDecl::new(tcx.types.never, DUMMY_SP));
+    for &local in locals.inputs.iter().chain(&locals.assigned) {
+        local_map[local] =
+            Some(local_decls.push(LocalDecl::new(body.local_decls[local].ty, DUMMY_SP)));
+    }
+
+    let data = &body.basic_blocks[block];
+    let mut terminator = data.terminator().clone();
+    // The cleanup of the function runs after the call to the cold path unwinds.
+    if let TerminatorKind::Call { unwind, .. } = &mut terminator.kind {
+        *unwind = UnwindAction::Continue;
+    }
+    let statements = data
+        .statements
+        .iter()
+        .filter(|statement| {
+            !matches!(
+                statement.kind,
+                StatementKind::StorageLive(_) | StatementKind::StorageDead(_) | StatementKind::Nop
+            )
+        })
+        .cloned()
+        .collect();
+    let basic_blocks = IndexVec::from_raw(vec![BasicBlockData {
+        statements,
+        terminator: Some(terminator),
+        is_cleanup: false,
+    }]);
+
+    let arg_count = locals.inputs.len();
+    let mut cold_path = new_cold_path(tcx, body, callee, basic_blocks, local_decls, arg_count);
+    RenameLocals { tcx, local_map }.visit_body_preserves_cfg(&mut cold_path);
+    cold_path
+}
+
+/// Creates the body of the next cold path outlined from `body`, which calls `callee`.
+fn new_cold_path<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &Body<'tcx>,
+    callee: DefId,
+    basic_blocks: IndexVec<BasicBlock, BasicBlockData<'tcx>>,
+    local_decls: IndexVec<Local, LocalDecl<'tcx>>,
+    arg_count: usize,
+) -> Body<'tcx> {
+    let source_scopes = IndexVec::from_elem_n(
+        SourceScopeData {
+            span: DUMMY_SP,
+            parent_scope: None,
+            inlined: None,
+            inlined_parent_scope: None,
+            local_data: ClearCrossCrate::Clear,
+        },
+        1,
+    );
+    // The cold paths are numbered in the order they are outlined from the function, after which
+    // they are added to its cold paths.
+    let outlined_from = body.source.def_id();
+    let index = body
+        .cold_paths
+        .iter()
+        .filter(|cold_path| match cold_path.source.instance {
+            ty::InstanceKind::ColdPathShim { outlined_from: def_id, .. } => def_id == outlined_from,
+            _ => false,
+        })
+        .count();
+    let instance =
+        ty::InstanceKind::ColdPathShim { callee, outlined_from, index: index.try_into().unwrap() };
+    Body::new(
+        MirSource::from_instance(instance),
+        basic_blocks,
+        source_scopes,
+        local_decls,
+        IndexVec::new(),
+        arg_count,
+        vec![],
+        DUMMY_SP,
+        None,
+        None,
+    )
+}
+
+/// Adds `cold_path` to the cold paths of `body`, and returns a call to it with `args`, which
+/// replaces the code it was outlined from at `source_info`. The panic location of the cold path,
+/// if it has one, is that of `location_span`.
+fn call_cold_path<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &mut Body<'tcx>,
+    mut cold_path: Body<'tcx>,
+    mut args: Vec<Operand<'tcx>>,
+    source_info: SourceInfo,
+    location_span: Span,
+    unwind: UnwindAction,
+) -> TerminatorKind<'tcx> {
+    let param_env = tcx.param_env_reveal_all_normalized(body.source.def_id());
+    cold_path.phase = body.phase;
+    NormalizeColdPath { tcx, param_env }.visit_body_preserves_cfg(&mut cold_path);
+    RequiredConstsVisitor::compute_required_consts(&mut cold_path);
+    mentioned_items::MentionedItems.run_pass(tcx, &mut cold_path);
+    pm::run_passes(
+        tcx,
+        &mut cold_path,
+        &[],
+        Some(MirPhase::Runtime(RuntimePhase::Optimized)),
+        pm::Optimizations::Allowed,
+    );
+
+    let instance = Instance { def: cold_path.source.instance, args: ty::List::empty() };
+
+    let mut inputs: Vec<_> =
+        cold_path.args_iter().map(|local| cold_path.local_decls[local].ty).collect();
+    // The location is an implicit argument of the cold path, but an explicit one of the `fn`
+    // pointer it is called through.
+    if instance.def.requires_caller_location(tcx) {
+        inputs.push(tcx.caller_location_ty());
+        args.push(Operand::Constant(Box::new(ConstOperand {
+            span: location_span,
+            user_ty: None,
+            const_: Const::Val(
+                tcx.span_as_caller_location(location_span),
+                tcx.caller_location_ty(),
+            ),
+        })));
+    }
+    body.cold_paths.push(cold_path);
+
+    let sig = tcx.mk_fn_sig(inputs, tcx.types.never, false, rustc_hir::Safety::Safe, Abi::Rust);
+    let fn_ptr = Pointer::from(tcx.reserve_and_set_fn_alloc(instance));
+    let destination = body.local_decls.push(LocalDecl::new(tcx.types.never, source_info.span));
+    let span = source_info.span;
+    TerminatorKind::Call {
+        func: Operand::Constant(Box::new(ConstOperand {
+            span,
+            user_ty: None,
+            const_: Const::Val(
+                ConstValue::Scalar(Scalar::from_pointer(fn_ptr, &tcx)),
+                Ty::new_fn_ptr(tcx, ty::Binder::dummy(sig)),
+            ),
+        })),
+        args: args.into_iter().map(|node| Spanned { node, span }).collect(),
+        destination: destination.into(),
+        target: None,
+        unwind,
+        call_source: CallSource::Misc,
+        fn_span: span,
+    }
+}
+
+/// The locals of a block, sorted into those it reads before assigning them, its inputs, and those
+/// it assigns before reading them.
+struct BlockLocals {
+    inputs: Vec<Local>,
+    assigned: Vec<Local>,
+    seen: BitSet<Local>,
+    /// Whether the block can be outlined, which it can't if it changes its inputs.
+    outlinable: bool,
+}
+
+impl BlockLocals {
+    fn new(locals: usize) -> Self {
+        BlockLocals {
+            inputs: Vec::new(),
+            assigned: Vec::new(),
+            seen: BitSet::new_empty(locals),
+            outlinable: true,
+        }
+    }
+}
+
+impl<'tcx> Visitor<'tcx> for BlockLocals {
+    fn visit_assign(&mut self, place: &Place<'tcx>, rvalue: &Rvalue<'tcx>, location: Location) {
+        // The assigned value is read before the place is written.
+        self.visit_rvalue(rvalue, location);
+        self.visit_place(place, PlaceContext::MutatingUse(MutatingUseContext::Store), location);
+    }
+
+    fn visit_local(&mut self, local: Local, context: PlaceContext, _: Location) {
+        if !context.is_use() {
+            return;
+        }
+        if self.seen.insert(local) {
+            match context {
+                PlaceContext::MutatingUse(MutatingUseContext::Store | MutatingUseContext::Call) => {
+                    self.assigned.push(local)
+                }
+                _ => self.inputs.push(local),
+            }
+        }
+        if context.is_mutating_use() && self.inputs.contains(&local) {
+            self.outlinable = false;
+        }
+    }
+}
+
+struct UsedLocals(BitSet<Local>);
+
+impl<'tcx> Visitor<'tcx> for UsedLocals {
+    fn visit_local(&mut self, local: Local, context: PlaceContext, _: Location) {
+        if context.is_use() {
+            self.0.insert(local);
+        }
+    }
+}
+
+/// Turns the operands of an assertion message into the arguments of its cold path, or into zeros.
+struct AssertOperands<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    local_decls: &'a LocalDecls<'tcx>,
+    pass_operands: bool,
+    args: Vec<Operand<'tcx>>,
+    arg_tys: Vec<Ty<'tcx>>,
+}
+
+impl<'tcx> MutVisitor<'tcx> for AssertOperands<'_, 'tcx> {
+    fn tcx(&self) -> TyCtxt<'tcx> {
+        self.tcx
+    }
+
+    fn visit_operand(&mut self, operand: &mut Operand<'tcx>, _: Location) {
+        if let Operand::Constant(_) = operand {
+            return;
+        }
+        let ty = operand.ty(self.local_decls, self.tcx);
+        if self.pass_operands {
+            self.args.push(operand.clone());
+            self.arg_tys.push(ty);
+            *operand = Operand::Copy(Local::from_usize(self.args.len()).into());
+        } else {
+            *operand = Operand::Constant(Box::new(ConstOperand {
+                span: DUMMY_SP,
+                user_ty: None,
+                const_: Const::from_bits(self.tcx, 0, ty::ParamEnv::reveal_all().and(ty)),
+            }));
+        }
+    }
+}
+
+/// Removes what ties a cold path to the function it is outlined from: the spans and scopes of the
+/// function, and its promoted constants, which are evaluated.
+struct NormalizeColdPath<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    param_env: ty::ParamEnv<'tcx>,
+}
+
+impl<'tcx> MutVisitor<'tcx> for NormalizeColdPath<'tcx> {
+    fn tcx(&self) -> TyCtxt<'tcx> {
+        self.tcx
+    }
+
+    fn visit_span(&mut self, span: &mut Span) {
+        *span = DUMMY_SP;
+    }
+
+    fn visit_source_info(&mut self, source_info: &mut SourceInfo) {
+        *source_info = SourceInfo::outermost(DUMMY_SP);
+    }
+
+    fn visit_const_operand(&mut self, constant: &mut ConstOperand<'tcx>, _: Location) {
+        if let Const::Unevaluated(..) = constant.const_
+            && let Ok(val) = constant.const_.eval(self.tcx, self.param_env, constant.span)
+        {
+            constant.const_ = Const::Val(val, constant.const_.ty());
+        }
+        constant.span = DUMMY_SP;
+        constant.user_ty = None;
+    }
+
+    fn visit_terminator(&mut self, terminator: &mut Terminator<'tcx>, location: Location) {
+        if let TerminatorKind::Call { args, fn_span, .. } = &mut terminator.kind {
+            *fn_span = DUMMY_SP;
+            for arg in args.iter_mut() {
+                arg.span = DUMMY_SP;
+            }
+        }
+        self.super_terminator(terminator, location);
+    }
+}
+
+struct RenameLocals<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    local_map: IndexVec<Local, Option<Local>>,
+}
+
+impl<'tcx> MutVisitor<'tcx> for RenameLocals<'tcx> {
+    fn tcx(&self) -> TyCtxt<'tcx> {
+        self.tcx
+    }
+
+    fn visit_local(&mut self, local: &mut Local, _: PlaceContext, _: Location) {
+        *local = self.local_map[*local].unwrap();
+    }
+}
@@ -1,3 +1,6 @@
+use std::sync::atomic::Ordering;
+
+use rustc_data_structures::profiling::TimeReportItem;
 use rustc_middle::mir::{self, Body, MirPhase, RuntimePhase};
 use rustc_middle::ty::TyCtxt;
 use rustc_session::Session;
@@ -35,7 +38,7 @@ fn name(&self) -> &'static str {
         self.0.name()
     }
 
-    fn is_enabled(&self, sess: &Session) -> bool {
+    fn is_enabled(&self, sess: &Session, _mir_opt_level: usize) -> bool {
         self.0.is_enabled(sess)
     }
 
@@ -58,13 +61,28 @@ fn name(&self) -> &'static str {
         self.1.name()
     }
 
-    fn is_enabled(&self, sess: &Session) -> bool {
-        sess.mir_opt_level() >= self.0 as usize
+    fn is_enabled(&self, _sess: &Session, mir_opt_level: usize) -> bool {
+        mir_opt_level >= self.0 as usize
     }
 
     fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
         self.1.run_pass(tcx, body)
     }
+
+    fn is_required(&self) -> bool {
+        self.1.is_required()
+    }
+}
+
+/// Whether the optimization passes, those that are not required, may run on a body.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Optimizations {
+    /// Only the required passes run, for the functions with `#[optimize(level = "0")]`.
+    Suppressed,
+    /// The optimization passes run as at this MIR optimization level, lower than that of the
+    /// session, for the functions with a lower `#[optimize(level = "...")]`.
+    Limited(usize),
+    Allowed,
 }
 
 /// Run the sequence of passes without validating the MIR after each pass. The MIR is still
@@ -75,7 +93,7 @@ pub fn run_passes_no_validate<'tcx>(
     passes: &[&dyn MirPass<'tcx>],
     phase_change: Option<MirPhase>,
 ) {
-    run_passes_inner(tcx, body, passes, phase_change, false);
+    run_passes_inner(tcx, body, passes, phase_change, false, Optimizations::Allowed);
 }
 
 /// The optional `phase_change` is applied after executing all the passes, if present
@@ -84,14 +102,21 @@ pub fn run_passes<'tcx>(
     body: &mut Body<'tcx>,
     passes: &[&dyn MirPass<'tcx>],
     phase_change: Option<MirPhase>,
+    optimizations: Optimizations,
 ) {
-    run_passes_inner(tcx, body, passes, phase_change, true);
+    run_passes_inner(tcx, body, passes, phase_change, true, optimizations);
 }
 
-pub fn should_run_pass<'tcx, P>(tcx: TyCtxt<'tcx>, pass: &P) -> bool
+pub fn should_run_pass<'tcx, P>(tcx: TyCtxt<'tcx>, pass: &P, optimizations: Optimizations) -> bool
 where
     P: MirPass<'tcx> + ?Sized,
 {
+    let mir_opt_level = match optimizations {
+        Optimizations::Suppressed if !pass.is_required() => return false,
+        Optimizations::Limited(mir_opt_level) if !pass.is_required() => mir_opt_level,
+        _ => tcx.sess.mir_opt_level(),
+    };
+
     let name = pass.name();
 
     let overridden_passes = &tcx.sess.opts.unstable_opts.mir_enable_passes;
@@ -104,7 +129,7 @@ pub fn should_run_pass<'tcx, P>(tcx: TyCtxt<'tcx>, pass: &P) -> bool
             );
             *polarity
         });
-    overridden.unwrap_or_else(|| pass.is_enabled(tcx.sess))
+    overridden.unwrap_or_else(|| pass.is_enabled(tcx.sess, mir_opt_level))
 }
 
 fn run_passes_inner<'tcx>(
@@ -113,11 +138,19 @@ fn run_passes_inner<'tcx>(
     passes: &[&dyn MirPass<'tcx>],
     phase_change: Option<MirPhase>,
     validate_each: bool,
+    optimizations: Optimizations,
 ) {
     let overridden_passes = &tcx.sess.opts.unstable_opts.mir_enable_passes;
     trace!(?overridden_passes);
 
     let prof_arg = tcx.sess.prof.enabled().then(|| format!("{:?}", body.source.def_id()));
+    // Only the optimizations applied while preparing the MIR for codegen are bisected.
+    let bisect_limit = tcx
+        .sess
+        .opts
+        .unstable_opts
+        .mir_opt_bisect_limit
+        .filter(|_| phase_change == Some(MirPhase::Runtime(RuntimePhase::Optimized)));
 
     if !body.should_skip() {
         let validate = validate_each & tcx.sess.opts.unstable_opts.validate_mir;
@@ -126,16 +159,27 @@ fn run_passes_inner<'tcx>(
         for pass in passes {
             let name = pass.name();
 
-            if !should_run_pass(tcx, *pass) {
+            if !should_run_pass(tcx, *pass, optimizations) {
                 continue;
             };
 
+            if let Some(limit) = bisect_limit
+                && !pass.is_required()
+                && !bisect_allows_pass(tcx, limit, name, body)
+            {
+                continue;
+            }
+
             let dump_enabled = pass.is_mir_dump_enabled();
 
             if dump_enabled {
                 dump_mir_for_pass(tcx, body, name, false);
             }
 
+            let time_report_guard =
+                tcx.sess.prof.time_report_activity(pass.profiler_name(), || {
+                    TimeReportItem::DefPathHash(tcx.def_path_hash(body.source.def_id()).0)
+                });
             if let Some(prof_arg) = &prof_arg {
                 tcx.sess
                     .prof
@@ -144,6 +188,7 @@ fn run_passes_inner<'tcx>(
             } else {
                 pass.run_pass(tcx, body);
             }
+            drop(time_report_guard);
 
             if dump_enabled {
                 dump_mir_for_pass(tcx, body, name, true);
@@ -185,11 +230,32 @@ fn run_passes_inner<'tcx>(
 
     if let Some(coroutine) = body.coroutine.as_mut() {
         if let Some(by_move_body) = coroutine.by_move_body.as_mut() {
-            run_passes_inner(tcx, by_move_body, passes, phase_change, validate_each);
+            run_passes_inner(tcx, by_move_body, passes, phase_change, validate_each, optimizations);
         }
     }
 }
 
+/// Counts an application of the optimization pass `name` to `body` for
+/// `-Z mir-opt-bisect-limit`, prints it, and returns whether it is within the limit.
+fn bisect_allows_pass<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    limit: usize,
+    name: &str,
+    body: &Body<'tcx>,
+) -> bool {
+    let count = tcx.sess.mir_opt_bisect_count.fetch_add(1, Ordering::Relaxed) + 1;
+    let allowed = count <= limit;
+    let mut target = tcx.def_path_str(body.source.def_id());
+    if let Some(promoted) = body.source.promoted {
+        target += &format!("::{{promoted#{}}}", promoted.as_usize());
+    }
+    eprintln!(
+        "BISECT: {} pass ({count}) {name} on {target}",
+        if allowed { "running" } else { "NOT running" },
+    );
+    allowed
+}
+
 pub fn validate_body<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>, when: String) {
     validate::Validator { when, mir_phase: body.phase }.run_pass(tcx, body);
 }
@@ -10,7 +10,7 @@
 pub struct RemoveNoopLandingPads;
 
 impl<'tcx> MirPass<'tcx> for RemoveNoopLandingPads {
-    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
+    fn is_enabled(&self, sess: &rustc_session::Session, _mir_opt_level: usize) -> bool {
         sess.panic_strategy() != PanicStrategy::Abort
     }
 
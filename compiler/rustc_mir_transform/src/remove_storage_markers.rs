@@ -6,8 +6,8 @@
 pub struct RemoveStorageMarkers;
 
 impl<'tcx> MirPass<'tcx> for RemoveStorageMarkers {
-    fn is_enabled(&self, sess: &rustc_session::Session) -> bool {
-        sess.mir_opt_level() > 0 && !sess.emit_lifetime_markers()
+    fn is_enabled(&self, sess: &rustc_session::Session, mir_opt_level: usize) -> bool {
+        mir_opt_level > 0 && !sess.emit_lifetime_markers()
     }
 
     fn run_pass(&self, _tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
@@ -21,4 +21,8 @@ fn run_pass(&self, _tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
             })
         }
     }
+
+    fn is_required(&self) -> bool {
+        false
+    }
 }
@@ -21,6 +21,7 @@
 use crate::{
     abort_unwinding_calls, add_call_guards, add_moves_for_packed_drops, deref_separator,
     instsimplify, mentioned_items, pass_manager as pm, remove_noop_landing_pads, simplify,
+    specialize_const_args,
 };
 
 mod async_destructor_ctor;
@@ -82,6 +83,19 @@ fn make_shim<'tcx>(tcx: TyCtxt<'tcx>, instance: ty::InstanceKind<'tcx>) -> Body<
             return tcx.optimized_mir(coroutine_def_id).coroutine_by_move_body().unwrap().clone();
         }
 
+        ty::InstanceKind::ConstArgsShim(def_id, const_args) => {
+            return specialize_const_args::build_const_args_shim(tcx, def_id, const_args);
+        }
+
+        ty::InstanceKind::ColdPathShim { outlined_from, .. } => {
+            let cold_paths = &tcx.optimized_mir(outlined_from).cold_paths;
+            return cold_paths
+                .iter()
+                .find(|body| body.source.instance == instance)
+                .unwrap()
+                .clone();
+        }
+
         ty::InstanceKind::DropGlue(def_id, ty) => {
             // FIXME(#91576): Drop shims for coroutines aren't subject to the MIR passes at the end
             // of this function. Is this intentional?
@@ -120,6 +134,7 @@ fn make_shim<'tcx>(tcx: TyCtxt<'tcx>, instance: ty::InstanceKind<'tcx>) -> Body<
                         &add_call_guards::CriticalCallEdges,
                     ],
                     Some(MirPhase::Runtime(RuntimePhase::Optimized)),
+                    pm::Optimizations::Allowed,
                 );
 
                 return body;
@@ -58,4 +58,8 @@ fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
             };
         }
     }
+
+    fn is_required(&self) -> bool {
+        false
+    }
 }
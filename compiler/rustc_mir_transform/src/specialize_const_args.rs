@@ -0,0 +1,313 @@
+//! Specializes local functions for the constant arguments of their calls.
+//!
+//! When a call passes constants for arguments which decide branches of the callee, like flags or
+//! enum discriminants in `parse(input, Mode::Strict)`, the call is redirected to a clone of the
+//! callee specialized for these constants, a `ConstArgsShim`, from which the branches not taken
+//! are removed. Unlike inlining, this works for callees of any size, and the calls with the same
+//! constants share a clone.
+//!
+//! Only `bool` and fieldless enum arguments are specialized, so that the number of clones of a
+//! callee is bounded by the values of its arguments. The pass only runs with
+//! `-Z specialize-const-args`.
+
+use std::iter;
+
+use rustc_attr::InlineAttr;
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::DefId;
+use rustc_index::bit_set::BitSet;
+use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
+use rustc_middle::mir::interpret::{Pointer, Scalar};
+use rustc_middle::mir::visit::{MutVisitor, PlaceContext};
+use rustc_middle::mir::*;
+use rustc_middle::ty::layout::IntegerExt;
+use rustc_middle::ty::{self, GenericArg, GenericArgsRef, Instance, ScalarInt, Ty, TyCtxt};
+use rustc_session::config::{InliningThreshold, OptLevel};
+use rustc_span::source_map::Spanned;
+use rustc_span::sym;
+use rustc_target::abi::Integer;
+use rustc_target::spec::abi::Abi;
+
+use crate::{
+    add_call_guards, dataflow_const_prop, gvn, pass_manager as pm, simplify, simplify_branches,
+};
+
+/// Callees with more basic blocks than this are not specialized, since they are cloned for each
+/// combination of constant arguments.
+const MAX_CALLEE_BLOCKS: usize = 200;
+
+/// Callees which could have more clones than this are not specialized. The clones are built by
+/// the callers, which are optimized independently, so their number is bounded from the values the
+/// arguments of the callee can be specialized for rather than counted.
+const MAX_CLONES_PER_CALLEE: u64 = 8;
+
+pub struct SpecializeConstArgs;
+
+impl<'tcx> MirPass<'tcx> for SpecializeConstArgs {
+    fn is_enabled(&self, sess: &rustc_session::Session, mir_opt_level: usize) -> bool {
+        // The clones of a function would all increment its coverage counters.
+        if !sess.opts.unstable_opts.specialize_const_args || sess.instrument_coverage() {
+            return false;
+        }
+
+        match mir_opt_level {
+            0 | 1 => false,
+            2 => matches!(sess.opts.optimize, OptLevel::Default | OptLevel::Aggressive),
+            _ => true,
+        }
+    }
+
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        let caller = body.source.def_id();
+        if !is_only_used_locally(tcx, caller) {
+            return;
+        }
+
+        // Only the callees change, not the terminators.
+        for block in body.basic_blocks.as_mut_preserves_cfg().iter_mut() {
+            let TerminatorKind::Call { func, args, .. } = &mut block.terminator_mut().kind else {
+                continue;
+            };
+            if let Some(specialized) = specialized_callee(tcx, caller, func, args) {
+                *func = specialized;
+            }
+        }
+    }
+
+    fn is_required(&self) -> bool {
+        false
+    }
+}
+
+/// Returns whether the optimized MIR of `caller` is only used by this crate. The crates using it
+/// would have to build the clones of its callees, without having their MIR.
+///
+/// This can't use `cross_crate_inlinable`, which depends on the optimized MIR of `caller`. Since
+/// calls to clones are still calls, `caller` can only be cross-crate inlinable because of its
+/// attributes or of the options.
+fn is_only_used_locally(tcx: TyCtxt<'_>, caller: DefId) -> bool {
+    matches!(tcx.def_kind(caller), DefKind::Fn | DefKind::AssocFn)
+        && !tcx.generics_of(caller).requires_monomorphization(tcx)
+        && matches!(tcx.codegen_fn_attrs(caller).inline, InlineAttr::None | InlineAttr::Never)
+        && !tcx.has_attr(caller, sym::rustc_intrinsic)
+        && tcx.sess.opts.unstable_opts.cross_crate_inline_threshold != InliningThreshold::Always
+}
+
+/// Returns whether the calls to `callee` can be made to a clone of it, through a `fn` pointer.
+fn is_specializable(tcx: TyCtxt<'_>, callee: DefId) -> bool {
+    if !callee.is_local()
+        || !matches!(tcx.def_kind(callee), DefKind::Fn | DefKind::AssocFn)
+        || tcx.generics_of(callee).requires_monomorphization(tcx)
+        || tcx.intrinsic(callee).is_some()
+        || !tcx.is_mir_available(callee)
+    {
+        return false;
+    }
+
+    let sig = tcx.fn_sig(callee).skip_binder();
+    if sig.abi() != Abi::Rust || sig.c_variadic() {
+        return false;
+    }
+
+    // The symbol of a function with an extern indicator is its own, and the functions with these
+    // attributes can't be called through `fn` pointers like other functions.
+    let attrs = tcx.codegen_fn_attrs(callee);
+    !attrs.contains_extern_indicator()
+        && !attrs.flags.intersects(CodegenFnAttrFlags::TRACK_CALLER | CodegenFnAttrFlags::NAKED)
+        && attrs.target_features.is_empty()
+        && !attrs.optimize.do_not_optimize()
+}
+
+/// Returns a constant `fn` pointer to the clone of the callee `func` specialized for the constant
+/// arguments among `args`, or `None` if there is nothing to specialize.
+fn specialized_callee<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    caller: DefId,
+    func: &Operand<'tcx>,
+    args: &[Spanned<Operand<'tcx>>],
+) -> Option<Operand<'tcx>> {
+    let (callee, generic_args) = func.const_fn_def()?;
+    if callee == caller || !is_specializable(tcx, callee) {
+        return None;
+    }
+
+    // The MIR of the callee can't be used if it depends on the MIR of the caller.
+    let instance = Instance::new(callee, generic_args);
+    if tcx.mir_callgraph_reachable((instance, caller.expect_local())) {
+        return None;
+    }
+    let callee_body = tcx.optimized_mir(callee);
+    if callee_body.basic_blocks.len() > MAX_CALLEE_BLOCKS
+        // The constants are assigned at the start of the clone, which must only run once.
+        || !callee_body.basic_blocks.predecessors()[START_BLOCK].is_empty()
+    {
+        return None;
+    }
+
+    let mut max_clones: u64 = 1;
+    let specializable_args: Vec<_> = callee_body
+        .args_iter()
+        .map(|local| match specializable_values(callee_body.local_decls[local].ty) {
+            Some(values) if decides_branch(callee_body, local) => {
+                // Each argument is either specialized for one of its values or not.
+                max_clones = max_clones.saturating_mul(values + 1);
+                (local, true)
+            }
+            _ => (local, false),
+        })
+        .collect();
+    // The callee itself is the combination without specialized arguments.
+    if max_clones - 1 > MAX_CLONES_PER_CALLEE {
+        return None;
+    }
+
+    let const_args = iter::zip(&specializable_args, args).map(|(&(local, specializable), arg)| {
+        match arg.node.constant().and_then(|constant| constant.const_.try_to_scalar_int()) {
+            Some(int) if specializable => const_arg(tcx, int),
+            _ => callee_body.local_decls[local].ty.into(),
+        }
+    });
+    let const_args = tcx.mk_args_from_iter(const_args);
+    if const_args.iter().all(|arg| arg.as_const().is_none()) {
+        return None;
+    }
+
+    let clone =
+        Instance { def: ty::InstanceKind::ConstArgsShim(callee, const_args), args: generic_args };
+    let fn_ptr = Pointer::from(tcx.reserve_and_set_fn_alloc(clone));
+    let fn_ptr_ty =
+        Ty::new_fn_ptr(tcx, tcx.erase_regions(tcx.fn_sig(callee).instantiate(tcx, generic_args)));
+    Some(Operand::Constant(Box::new(ConstOperand {
+        span: func.constant()?.span,
+        user_ty: None,
+        const_: Const::Val(ConstValue::Scalar(Scalar::from_pointer(fn_ptr, &tcx)), fn_ptr_ty),
+    })))
+}
+
+/// Returns the number of values an argument of type `ty` can be specialized for, or `None` if
+/// arguments of that type are not specialized.
+fn specializable_values(ty: Ty<'_>) -> Option<u64> {
+    match ty.kind() {
+        ty::Bool => Some(2),
+        ty::Adt(adt_def, _) if adt_def.is_enum() && adt_def.is_payloadfree() => {
+            Some(adt_def.variants().len() as u64)
+        }
+        _ => None,
+    }
+}
+
+/// Returns the entry of a `ConstArgsShim` for an argument specialized for `int`.
+fn const_arg<'tcx>(tcx: TyCtxt<'tcx>, int: ScalarInt) -> GenericArg<'tcx> {
+    let ty = Integer::from_size(int.size()).unwrap().to_ty(tcx, false);
+    ty::Const::new_value(tcx, ty::ValTree::from_scalar_int(int), ty).into()
+}
+
+/// Returns whether the value of `arg` decides a branch of `body`, directly or through values
+/// computed from it, so that the clone of `body` specialized for a constant `arg` has branches
+/// that are never taken.
+fn decides_branch(body: &Body<'_>, arg: Local) -> bool {
+    fn is_derived(operand: &Operand<'_>, derived: &BitSet<Local>) -> bool {
+        operand.place().is_some_and(|place| derived.contains(place.local))
+    }
+
+    let mut derived = BitSet::new_empty(body.local_decls.len());
+    derived.insert(arg);
+    loop {
+        let mut changed = false;
+        for statement in body.basic_blocks.iter().flat_map(|data| &data.statements) {
+            let StatementKind::Assign(box (place, rvalue)) = &statement.kind else {
+                continue;
+            };
+            let from_derived = match rvalue {
+                Rvalue::Use(operand)
+                | Rvalue::UnaryOp(_, operand)
+                | Rvalue::Cast(_, operand, _) => is_derived(operand, &derived),
+                Rvalue::BinaryOp(_, box (lhs, rhs)) => {
+                    is_derived(lhs, &derived) || is_derived(rhs, &derived)
+                }
+                Rvalue::Discriminant(place) => derived.contains(place.local),
+                _ => false,
+            };
+            if from_derived && let Some(local) = place.as_local() {
+                changed |= derived.insert(local);
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    body.basic_blocks.iter().any(|data| match &data.terminator().kind {
+        TerminatorKind::SwitchInt { discr, .. } => is_derived(discr, &derived),
+        _ => false,
+    })
+}
+
+/// Builds the MIR of the clone of `def_id` specialized for `const_args`, see
+/// `InstanceKind::ConstArgsShim`.
+pub(crate) fn build_const_args_shim<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    def_id: DefId,
+    const_args: GenericArgsRef<'tcx>,
+) -> Body<'tcx> {
+    let mut body = tcx.optimized_mir(def_id).clone();
+    body.source = MirSource::from_instance(ty::InstanceKind::ConstArgsShim(def_id, const_args));
+
+    // The specialized arguments are replaced by locals initialized with their constants, which
+    // are assigned once, so that GVN propagates them.
+    let source_info = SourceInfo::outermost(body.span);
+    let mut assignments = Vec::new();
+    let args: Vec<_> = body.args_iter().collect();
+    for (arg, const_arg) in iter::zip(args, const_args) {
+        let Some(ct) = const_arg.as_const() else {
+            continue;
+        };
+        let ty = body.local_decls[arg].ty;
+        let local =
+            body.local_decls.push(LocalDecl::new(ty, body.local_decls[arg].source_info.span));
+        RenameLocal { tcx, from: arg, to: local }.visit_body_preserves_cfg(&mut body);
+
+        let const_ = Const::Val(ConstValue::Scalar(Scalar::Int(ct.to_valtree().unwrap_leaf())), ty);
+        let constant = ConstOperand { span: body.span, user_ty: None, const_ };
+        let rvalue = Rvalue::Use(Operand::Constant(Box::new(constant)));
+        assignments.push(Statement {
+            source_info,
+            kind: StatementKind::Assign(Box::new((local.into(), rvalue))),
+        });
+    }
+    body.basic_blocks_mut()[START_BLOCK].statements.splice(0..0, assignments);
+
+    pm::run_passes(
+        tcx,
+        &mut body,
+        &[
+            &gvn::GVN,
+            &dataflow_const_prop::DataflowConstProp,
+            &simplify_branches::SimplifyConstCondition::Final,
+            &simplify::SimplifyCfg::Final,
+            &simplify::SimplifyLocals::Final,
+            &add_call_guards::CriticalCallEdges,
+        ],
+        None,
+        pm::Optimizations::Allowed,
+    );
+    body
+}
+
+struct RenameLocal<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    from: Local,
+    to: Local,
+}
+
+impl<'tcx> MutVisitor<'tcx> for RenameLocal<'tcx> {
+    fn tcx(&self) -> TyCtxt<'tcx> {
+        self.tcx
+    }
+
+    fn visit_local(&mut self, local: &mut Local, _: PlaceContext, _: Location) {
+        if *local == self.from {
+            *local = self.to;
+        }
+    }
+}
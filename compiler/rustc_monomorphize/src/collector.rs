@@ -218,7 +218,9 @@
 use rustc_hir::lang_items::LangItem;
 use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
 use rustc_middle::mir::interpret::{AllocId, ErrorHandled, GlobalAlloc, Scalar};
-use rustc_middle::mir::mono::{InstantiationMode, MonoItem};
+use rustc_middle::mir::mono::{
+    InstantiationMode, MonoItem, MonoItemEdge, MonoItemGraph, MonoItemUseKind,
+};
 use rustc_middle::mir::visit::Visitor as MirVisitor;
 use rustc_middle::mir::{self, traversal, Location, MentionedItem};
 use rustc_middle::query::TyCtxtAt;
@@ -239,7 +241,10 @@
 use rustc_target::abi::Size;
 use tracing::{debug, instrument, trace};
 
-use crate::errors::{self, EncounteredErrorWhileInstantiating, NoOptimizedMir, RecursionLimit};
+use crate::errors::{
+    self, EncounteredErrorWhileInstantiating, NoOptimizedMir, RecursionLimit,
+    UnknownCguCollectionMode,
+};
 
 #[derive(PartialEq)]
 pub enum MonoItemCollectionStrategy {
@@ -251,11 +256,15 @@ pub struct UsageMap<'tcx> {
     // Maps every mono item to the mono items used by it.
     used_map: UnordMap<MonoItem<'tcx>, Vec<MonoItem<'tcx>>>,
 
+    // Maps every mono item to the span and the kind of each of its uses, in the order of
+    // `used_map`. Only kept for `mono_item_graph`, as codegen doesn't need them.
+    use_sites: Option<UnordMap<MonoItem<'tcx>, Vec<(Span, MonoItemUseKind)>>>,
+
     // Maps every mono item to the mono items that use it.
     user_map: UnordMap<MonoItem<'tcx>, Vec<MonoItem<'tcx>>>,
 }
 
-type MonoItems<'tcx> = Vec<Spanned<MonoItem<'tcx>>>;
+type MonoItems<'tcx> = Vec<(Spanned<MonoItem<'tcx>>, MonoItemUseKind)>;
 
 /// The state that is shared across the concurrent threads that are doing collection.
 struct SharedState<'tcx> {
@@ -287,18 +296,27 @@ enum CollectionMode {
 }
 
 impl<'tcx> UsageMap<'tcx> {
-    fn new() -> UsageMap<'tcx> {
-        UsageMap { used_map: Default::default(), user_map: Default::default() }
+    fn new(keep_use_sites: bool) -> UsageMap<'tcx> {
+        UsageMap {
+            used_map: Default::default(),
+            use_sites: keep_use_sites.then(Default::default),
+            user_map: Default::default(),
+        }
     }
 
     fn record_used<'a>(
         &mut self,
         user_item: MonoItem<'tcx>,
-        used_items: &'a [Spanned<MonoItem<'tcx>>],
+        used_items: &'a [(Spanned<MonoItem<'tcx>>, MonoItemUseKind)],
     ) where
         'tcx: 'a,
     {
-        let used_items: Vec<_> = used_items.iter().map(|item| item.node).collect();
+        if let Some(use_sites) = &mut self.use_sites {
+            let sites = used_items.iter().map(|&(item, kind)| (item.span, kind)).collect();
+            assert!(use_sites.insert(user_item, sites).is_none());
+        }
+
+        let used_items: Vec<_> = used_items.iter().map(|(item, _)| item.node).collect();
         for &used_item in used_items.iter() {
             self.user_map.entry(used_item).or_default().push(user_item);
         }
@@ -405,7 +423,7 @@ fn collect_items_rec<'tcx>(
                 // Nested statics have no type.
                 if !nested {
                     let ty = instance.ty(tcx, ty::ParamEnv::reveal_all());
-                    visit_drop_use(tcx, ty, true, starting_item.span, &mut used_items);
+                    visit_drop_use(tcx, ty, starting_item.span, &mut used_items);
                 }
 
                 if let Ok(alloc) = tcx.eval_static_initializer(def_id) {
@@ -415,12 +433,15 @@ fn collect_items_rec<'tcx>(
                 }
 
                 if tcx.needs_thread_local_shim(def_id) {
-                    used_items.push(respan(
-                        starting_item.span,
-                        MonoItem::Fn(Instance {
-                            def: InstanceKind::ThreadLocalShim(def_id),
-                            args: GenericArgs::empty(),
-                        }),
+                    used_items.push((
+                        respan(
+                            starting_item.span,
+                            MonoItem::Fn(Instance {
+                                def: InstanceKind::ThreadLocalShim(def_id),
+                                args: GenericArgs::empty(),
+                            }),
+                        ),
+                        MonoItemUseKind::Call,
                     ));
                 }
             }
@@ -477,7 +498,10 @@ fn collect_items_rec<'tcx>(
                             let instance = Instance::mono(tcx, *def_id);
                             if tcx.should_codegen_locally(instance) {
                                 trace!("collecting static {:?}", def_id);
-                                used_items.push(dummy_spanned(MonoItem::Static(*def_id)));
+                                used_items.push((
+                                    dummy_spanned(MonoItem::Static(*def_id)),
+                                    MonoItemUseKind::Static,
+                                ));
                             }
                         }
                         hir::InlineAsmOperand::In { .. }
@@ -521,7 +545,7 @@ fn collect_items_rec<'tcx>(
     if mode == CollectionMode::MentionedItems {
         assert!(used_items.is_empty(), "'mentioned' collection should never encounter used items");
     } else {
-        for used_item in used_items {
+        for (used_item, _) in used_items {
             collect_items_rec(
                 tcx,
                 used_item,
@@ -535,7 +559,7 @@ fn collect_items_rec<'tcx>(
 
     // Walk over mentioned items *after* used items, so that if an item is both mentioned and used then
     // the loop above has fully collected it, so this loop will skip it.
-    for mentioned_item in mentioned_items {
+    for (mentioned_item, _) in mentioned_items {
         collect_items_rec(
             tcx,
             mentioned_item,
@@ -713,7 +737,8 @@ fn visit_rvalue(&mut self, rvalue: &mir::Rvalue<'tcx>, location: Location) {
                     let instance =
                         Instance::resolve_closure(self.tcx, def_id, args, ty::ClosureKind::FnOnce);
                     if self.tcx.should_codegen_locally(instance) {
-                        self.used_items.push(create_fn_mono_item(self.tcx, instance, span));
+                        let item = create_fn_mono_item(self.tcx, instance, span);
+                        self.used_items.push((item, MonoItemUseKind::FnPtr));
                     }
                 } else {
                     bug!()
@@ -724,7 +749,8 @@ fn visit_rvalue(&mut self, rvalue: &mir::Rvalue<'tcx>, location: Location) {
                 let instance = Instance::mono(self.tcx, def_id);
                 if self.tcx.should_codegen_locally(instance) {
                     trace!("collecting thread-local static {:?}", def_id);
-                    self.used_items.push(respan(span, MonoItem::Static(def_id)));
+                    let item = respan(span, MonoItem::Static(def_id));
+                    self.used_items.push((item, MonoItemUseKind::Static));
                 }
             }
             _ => { /* not interesting */ }
@@ -750,7 +776,8 @@ fn visit_terminator(&mut self, terminator: &mir::Terminator<'tcx>, location: Loc
         let push_mono_lang_item = |this: &mut Self, lang_item: LangItem| {
             let instance = Instance::mono(tcx, tcx.require_lang_item(lang_item, Some(source)));
             if tcx.should_codegen_locally(instance) {
-                this.used_items.push(create_fn_mono_item(tcx, instance, source));
+                let item = create_fn_mono_item(tcx, instance, source);
+                this.used_items.push((item, MonoItemUseKind::Call));
             }
         };
 
@@ -769,7 +796,7 @@ fn visit_terminator(&mut self, terminator: &mir::Terminator<'tcx>, location: Loc
                 // *Before* monomorphizing, record that we already handled this mention.
                 self.used_mentioned_items.insert(MentionedItem::Drop(ty));
                 let ty = self.monomorphize(ty);
-                visit_drop_use(self.tcx, ty, true, source, self.used_items);
+                visit_drop_use(self.tcx, ty, source, self.used_items);
             }
             mir::TerminatorKind::InlineAsm { ref operands, .. } => {
                 for op in operands {
@@ -785,7 +812,8 @@ fn visit_terminator(&mut self, terminator: &mir::Terminator<'tcx>, location: Loc
                             let instance = Instance::mono(self.tcx, def_id);
                             if self.tcx.should_codegen_locally(instance) {
                                 trace!("collecting asm sym static {:?}", def_id);
-                                self.used_items.push(respan(source, MonoItem::Static(def_id)));
+                                let item = respan(source, MonoItem::Static(def_id));
+                                self.used_items.push((item, MonoItemUseKind::Static));
                             }
                         }
                         _ => {}
@@ -835,12 +863,11 @@ fn visit_operand(&mut self, operand: &mir::Operand<'tcx>, location: Location) {
 fn visit_drop_use<'tcx>(
     tcx: TyCtxt<'tcx>,
     ty: Ty<'tcx>,
-    is_direct_call: bool,
     source: Span,
     output: &mut MonoItems<'tcx>,
 ) {
     let instance = Instance::resolve_drop_in_place(tcx, ty);
-    visit_instance_use(tcx, instance, is_direct_call, source, output);
+    visit_instance_use(tcx, instance, true, MonoItemUseKind::DropGlue, source, output);
 }
 
 /// For every call of this function in the visitor, make sure there is a matching call in the
@@ -861,7 +888,8 @@ fn visit_fn_use<'tcx>(
                 _ => bug!("failed to resolve instance for {ty}"),
             }
         };
-        visit_instance_use(tcx, instance, is_direct_call, source, output);
+        let kind = if is_direct_call { MonoItemUseKind::Call } else { MonoItemUseKind::FnPtr };
+        visit_instance_use(tcx, instance, is_direct_call, kind, source, output);
     }
 }
 
@@ -869,6 +897,7 @@ fn visit_instance_use<'tcx>(
     tcx: TyCtxt<'tcx>,
     instance: ty::Instance<'tcx>,
     is_direct_call: bool,
+    kind: MonoItemUseKind,
     source: Span,
     output: &mut MonoItems<'tcx>,
 ) {
@@ -886,13 +915,14 @@ fn visit_instance_use<'tcx>(
             let def_id = tcx.require_lang_item(LangItem::PanicNounwind, None);
             let panic_instance = Instance::mono(tcx, def_id);
             if tcx.should_codegen_locally(panic_instance) {
-                output.push(create_fn_mono_item(tcx, panic_instance, source));
+                let item = create_fn_mono_item(tcx, panic_instance, source);
+                output.push((item, MonoItemUseKind::Call));
             }
         } else if tcx.has_attr(def_id, sym::rustc_intrinsic) {
             // Codegen the fallback body of intrinsics with fallback bodies
             let instance = ty::Instance::new(def_id, instance.args);
             if tcx.should_codegen_locally(instance) {
-                output.push(create_fn_mono_item(tcx, instance, source));
+                output.push((create_fn_mono_item(tcx, instance, source), kind));
             }
         }
     }
@@ -909,7 +939,7 @@ fn visit_instance_use<'tcx>(
         ty::InstanceKind::DropGlue(_, None) | ty::InstanceKind::AsyncDropGlueCtorShim(_, None) => {
             // Don't need to emit noop drop glue if we are calling directly.
             if !is_direct_call {
-                output.push(create_fn_mono_item(tcx, instance, source));
+                output.push((create_fn_mono_item(tcx, instance, source), kind));
             }
         }
         ty::InstanceKind::DropGlue(_, Some(_))
@@ -922,8 +952,10 @@ fn visit_instance_use<'tcx>(
         | ty::InstanceKind::Item(..)
         | ty::InstanceKind::FnPtrShim(..)
         | ty::InstanceKind::CloneShim(..)
-        | ty::InstanceKind::FnPtrAddrShim(..) => {
-            output.push(create_fn_mono_item(tcx, instance, source));
+        | ty::InstanceKind::FnPtrAddrShim(..)
+        | ty::InstanceKind::ConstArgsShim(..)
+        | ty::InstanceKind::ColdPathShim { .. } => {
+            output.push((create_fn_mono_item(tcx, instance, source), kind));
         }
     }
 }
@@ -1130,12 +1162,13 @@ fn create_mono_items_for_vtable_methods<'tcx>(
                     Some(*instance).filter(|instance| tcx.should_codegen_locally(*instance))
                 }
             })
-            .map(|item| create_fn_mono_item(tcx, item, source));
+            .map(|item| (create_fn_mono_item(tcx, item, source), MonoItemUseKind::Vtable));
         output.extend(methods);
     }
 
     // Also add the destructor.
-    visit_drop_use(tcx, impl_ty, false, source, output);
+    let drop_glue = Instance::resolve_drop_in_place(tcx, impl_ty);
+    visit_instance_use(tcx, drop_glue, false, MonoItemUseKind::Vtable, source, output);
 }
 
 /// Scans the CTFE alloc in order to find function pointers and statics that must be monomorphized.
@@ -1146,7 +1179,7 @@ fn collect_alloc<'tcx>(tcx: TyCtxt<'tcx>, alloc_id: AllocId, output: &mut MonoIt
             let instance = Instance::mono(tcx, def_id);
             if tcx.should_codegen_locally(instance) {
                 trace!("collecting static {:?}", def_id);
-                output.push(dummy_spanned(MonoItem::Static(def_id)));
+                output.push((dummy_spanned(MonoItem::Static(def_id)), MonoItemUseKind::Static));
             }
         }
         GlobalAlloc::Memory(alloc) => {
@@ -1164,12 +1197,15 @@ fn collect_alloc<'tcx>(tcx: TyCtxt<'tcx>, alloc_id: AllocId, output: &mut MonoIt
         GlobalAlloc::Function { instance, .. } => {
             if tcx.should_codegen_locally(instance) {
                 trace!("collecting {:?} with {:#?}", alloc_id, instance);
-                output.push(create_fn_mono_item(tcx, instance, DUMMY_SP));
+                let item = create_fn_mono_item(tcx, instance, DUMMY_SP);
+                output.push((item, MonoItemUseKind::FnPtr));
             }
         }
         GlobalAlloc::VTable(ty, trait_ref) => {
             let alloc_id = tcx.vtable_allocation((ty, trait_ref));
-            collect_alloc(tcx, alloc_id, output)
+            let mut entries = MonoItems::new();
+            collect_alloc(tcx, alloc_id, &mut entries);
+            output.extend(entries.into_iter().map(|(item, _)| (item, MonoItemUseKind::Vtable)));
         }
     }
 }
@@ -1262,11 +1298,18 @@ fn visit_mentioned_item<'tcx>(
                 // for "mentioned" item collection.
                 // We can set `is_direct_call`; that just means we'll skip a bunch of shims that anyway
                 // can't have their own failing constants.
-                visit_instance_use(tcx, instance, /*is_direct_call*/ true, span, output);
+                visit_instance_use(
+                    tcx,
+                    instance,
+                    /*is_direct_call*/ true,
+                    MonoItemUseKind::Call,
+                    span,
+                    output,
+                );
             }
         }
         MentionedItem::Drop(ty) => {
-            visit_drop_use(tcx, ty, /*is_direct_call*/ true, span, output);
+            visit_drop_use(tcx, ty, span, output);
         }
         MentionedItem::UnsizeCast { source_ty, target_ty } => {
             let (source_ty, target_ty) =
@@ -1285,7 +1328,7 @@ fn visit_mentioned_item<'tcx>(
                 let instance =
                     Instance::resolve_closure(tcx, def_id, args, ty::ClosureKind::FnOnce);
                 if tcx.should_codegen_locally(instance) {
-                    output.push(create_fn_mono_item(tcx, instance, span));
+                    output.push((create_fn_mono_item(tcx, instance, span), MonoItemUseKind::FnPtr));
                 }
             } else {
                 bug!()
@@ -1359,7 +1402,7 @@ fn collect_roots(tcx: TyCtxt<'_>, mode: MonoItemCollectionStrategy) -> Vec<MonoI
 struct RootCollector<'a, 'tcx> {
     tcx: TyCtxt<'tcx>,
     strategy: MonoItemCollectionStrategy,
-    output: &'a mut MonoItems<'tcx>,
+    output: &'a mut Vec<Spanned<MonoItem<'tcx>>>,
     entry_fn: Option<(DefId, EntryFnType)>,
 }
 
@@ -1382,7 +1425,9 @@ fn process_item(&mut self, id: hir::ItemId) {
                     }
 
                     let ty = self.tcx.type_of(id.owner_id.to_def_id()).no_bound_vars().unwrap();
-                    visit_drop_use(self.tcx, ty, true, DUMMY_SP, self.output);
+                    let mut drop_glue = MonoItems::new();
+                    visit_drop_use(self.tcx, ty, DUMMY_SP, &mut drop_glue);
+                    self.output.extend(drop_glue.into_iter().map(|(item, _)| item));
                 }
             }
             DefKind::GlobalAsm => {
@@ -1493,7 +1538,7 @@ fn push_extra_entry_roots(&mut self) {
 fn create_mono_items_for_default_impls<'tcx>(
     tcx: TyCtxt<'tcx>,
     item: hir::ItemId,
-    output: &mut MonoItems<'tcx>,
+    output: &mut Vec<Spanned<MonoItem<'tcx>>>,
 ) {
     let Some(impl_) = tcx.impl_trait_header(item.owner_id) else {
         return;
@@ -1567,10 +1612,38 @@ fn create_mono_items_for_default_impls<'tcx>(
 // Top-level entry point, tying it all together
 //=-----------------------------------------------------------------------------
 
+/// Returns the strategy to collect the mono items of the crate with, `-Z print-mono-items` or
+/// `-C link-dead-code` making it eager.
+pub(crate) fn collection_strategy(tcx: TyCtxt<'_>) -> MonoItemCollectionStrategy {
+    match tcx.sess.opts.unstable_opts.print_mono_items {
+        Some(ref s) => {
+            let mode = s.to_lowercase();
+            let mode = mode.trim();
+            if mode == "eager" {
+                MonoItemCollectionStrategy::Eager
+            } else {
+                if mode != "lazy" {
+                    tcx.dcx().emit_warn(UnknownCguCollectionMode { mode });
+                }
+
+                MonoItemCollectionStrategy::Lazy
+            }
+        }
+        None => {
+            if tcx.sess.link_dead_code() {
+                MonoItemCollectionStrategy::Eager
+            } else {
+                MonoItemCollectionStrategy::Lazy
+            }
+        }
+    }
+}
+
 #[instrument(skip(tcx, strategy), level = "debug")]
 pub(crate) fn collect_crate_mono_items<'tcx>(
     tcx: TyCtxt<'tcx>,
     strategy: MonoItemCollectionStrategy,
+    keep_use_sites: bool,
 ) -> (Vec<MonoItem<'tcx>>, UsageMap<'tcx>) {
     let _prof_timer = tcx.prof.generic_activity("monomorphization_collector");
 
@@ -1583,7 +1656,7 @@ pub(crate) fn collect_crate_mono_items<'tcx>(
     let mut state = SharedState {
         visited: MTLock::new(UnordSet::default()),
         mentioned: MTLock::new(UnordSet::default()),
-        usage_map: MTLock::new(UsageMap::new()),
+        usage_map: MTLock::new(UsageMap::new(keep_use_sites)),
     };
     let recursion_limit = tcx.recursion_limit();
 
@@ -1614,6 +1687,27 @@ pub(crate) fn collect_crate_mono_items<'tcx>(
     (mono_items, state.usage_map.into_inner())
 }
 
+fn mono_item_graph(tcx: TyCtxt<'_>, (): ()) -> MonoItemGraph<'_> {
+    let (items, usage_map) = collect_crate_mono_items(tcx, collection_strategy(tcx), true);
+    tcx.dcx().abort_if_errors();
+
+    let use_sites = usage_map.use_sites.as_ref().unwrap();
+    let edges = items
+        .iter()
+        .flat_map(|&user| {
+            let used_items = usage_map.used_map[&user].iter();
+            used_items.zip(&use_sites[&user]).map(move |(&used, &(span, kind))| MonoItemEdge {
+                user,
+                used,
+                kind,
+                span,
+            })
+        })
+        .collect();
+    MonoItemGraph { items, edges }
+}
+
 pub fn provide(providers: &mut Providers) {
     providers.hooks.should_codegen_locally = should_codegen_locally;
+    providers.mono_item_graph = mono_item_graph;
 }
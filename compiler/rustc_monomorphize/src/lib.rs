@@ -16,6 +16,8 @@
 mod polymorphize;
 mod util;
 
+pub use partitioning::emit_mono_report;
+
 rustc_fluent_macro::fluent_messages! { "../messages.ftl" }
 
 fn custom_coerce_unsize_info<'tcx>(
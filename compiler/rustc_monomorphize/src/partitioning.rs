@@ -116,13 +116,13 @@
 use rustc_middle::ty::visit::TypeVisitableExt;
 use rustc_middle::ty::{self, InstanceKind, TyCtxt};
 use rustc_middle::util::Providers;
-use rustc_session::config::{DumpMonoStatsFormat, SwitchWithOptPath};
+use rustc_session::config::{DumpMonoStatsFormat, OutputType, SwitchWithOptPath};
 use rustc_session::CodegenUnits;
 use rustc_span::symbol::Symbol;
 use tracing::debug;
 
-use crate::collector::{self, MonoItemCollectionStrategy, UsageMap};
-use crate::errors::{CouldntDumpMonoStats, SymbolAlreadyDefined, UnknownCguCollectionMode};
+use crate::collector::{self, UsageMap};
+use crate::errors::{CouldntDumpMonoStats, SymbolAlreadyDefined};
 
 struct PartitioningCx<'a, 'tcx> {
     tcx: TyCtxt<'tcx>,
@@ -620,7 +620,10 @@ fn characteristic_def_id_of_mono_item<'tcx>(
     match mono_item {
         MonoItem::Fn(instance) => {
             let def_id = match instance.def {
-                ty::InstanceKind::Item(def) => def,
+                // Specialized clones and cold paths go with the original functions.
+                ty::InstanceKind::Item(def)
+                | ty::InstanceKind::ConstArgsShim(def, _)
+                | ty::InstanceKind::ColdPathShim { outlined_from: def, .. } => def,
                 ty::InstanceKind::VTableShim(..)
                 | ty::InstanceKind::ReifyShim(..)
                 | ty::InstanceKind::FnPtrShim(..)
@@ -798,7 +801,9 @@ fn mono_item_visibility<'tcx>(
         | InstanceKind::DropGlue(..)
         | InstanceKind::AsyncDropGlueCtorShim(..)
         | InstanceKind::CloneShim(..)
-        | InstanceKind::FnPtrAddrShim(..) => return Visibility::Hidden,
+        | InstanceKind::FnPtrAddrShim(..)
+        | InstanceKind::ConstArgsShim(..)
+        | InstanceKind::ColdPathShim { .. } => return Visibility::Hidden,
     };
 
     // The `start_fn` lang item is actually a monomorphized instance of a
@@ -1098,30 +1103,8 @@ fn assert_symbols_are_distinct<'a, 'tcx, I>(tcx: TyCtxt<'tcx>, mono_items: I)
 }
 
 fn collect_and_partition_mono_items(tcx: TyCtxt<'_>, (): ()) -> (&DefIdSet, &[CodegenUnit<'_>]) {
-    let collection_strategy = match tcx.sess.opts.unstable_opts.print_mono_items {
-        Some(ref s) => {
-            let mode = s.to_lowercase();
-            let mode = mode.trim();
-            if mode == "eager" {
-                MonoItemCollectionStrategy::Eager
-            } else {
-                if mode != "lazy" {
-                    tcx.dcx().emit_warn(UnknownCguCollectionMode { mode });
-                }
-
-                MonoItemCollectionStrategy::Lazy
-            }
-        }
-        None => {
-            if tcx.sess.link_dead_code() {
-                MonoItemCollectionStrategy::Eager
-            } else {
-                MonoItemCollectionStrategy::Lazy
-            }
-        }
-    };
-
-    let (items, usage_map) = collector::collect_crate_mono_items(tcx, collection_strategy);
+    let collection_strategy = collector::collection_strategy(tcx);
+    let (items, usage_map) = collector::collect_crate_mono_items(tcx, collection_strategy, false);
 
     // If there was an error during collection (e.g. from one of the constants we evaluated),
     // then we stop here. This way codegen does not have to worry about failing constants.
@@ -1300,6 +1283,118 @@ struct MonoItem {
     Ok(())
 }
 
+/// Writes the `--emit=mono-report` report: the instantiations of each generic function, the
+/// items that requested them and their estimated size, from the heaviest function to the
+/// lightest. The size estimates are the ones partitioning uses, based on the number of MIR
+/// statements, which roughly corresponds to the amount of LLVM IR generated for an item.
+///
+/// The uses of the instantiations come from `mono_item_graph`, which collects the mono items
+/// again, so that codegen doesn't have to keep their spans.
+pub fn emit_mono_report(tcx: TyCtxt<'_>) {
+    #[derive(serde::Serialize)]
+    struct MonoReport {
+        crate_name: String,
+        functions: Vec<GenericFunction>,
+    }
+
+    #[derive(serde::Serialize)]
+    struct GenericFunction {
+        name: String,
+        #[serde(rename = "crate")]
+        krate: String,
+        instantiation_count: usize,
+        total_size_estimate: usize,
+        instantiations: Vec<Instantiation>,
+    }
+
+    #[derive(serde::Serialize)]
+    struct Instantiation {
+        name: String,
+        size_estimate: usize,
+        codegen_units: Vec<String>,
+        requested_by: Vec<Request>,
+    }
+
+    #[derive(serde::Serialize, PartialEq, Eq, PartialOrd, Ord)]
+    struct Request {
+        item: String,
+        #[serde(rename = "crate")]
+        krate: String,
+        span: String,
+    }
+
+    let (_, codegen_units) = tcx.collect_and_partition_mono_items(());
+    let mut item_to_cgus: UnordMap<_, Vec<_>> = Default::default();
+    for cgu in codegen_units {
+        for mono_item in cgu.items().keys() {
+            item_to_cgus.entry(*mono_item).or_default().push(cgu.name().to_string());
+        }
+    }
+
+    let graph = tcx.mono_item_graph(());
+    let mut use_sites: UnordMap<_, Vec<_>> = Default::default();
+    for edge in &graph.edges {
+        use_sites.entry(edge.used).or_default().push((edge.user, edge.span));
+    }
+
+    let source_map = tcx.sess.source_map();
+    let mut instantiations_per_def_id: FxIndexMap<DefId, Vec<Instantiation>> = Default::default();
+    for &mono_item in &graph.items {
+        let MonoItem::Fn(instance) = mono_item else {
+            continue;
+        };
+        let def_id = instance.def_id();
+        if !tcx.generics_of(def_id).requires_monomorphization(tcx) {
+            continue;
+        }
+
+        let mut codegen_units = item_to_cgus.remove(&mono_item).unwrap_or_default();
+        codegen_units.sort();
+        let mut requested_by: Vec<_> = use_sites
+            .remove(&mono_item)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(user, span)| Request {
+                item: with_no_trimmed_paths!(user.to_string()),
+                krate: tcx.crate_name(user.krate()).to_string(),
+                span: source_map.span_to_embeddable_string(span),
+            })
+            .collect();
+        requested_by.sort();
+        requested_by.dedup();
+        instantiations_per_def_id.entry(def_id).or_default().push(Instantiation {
+            name: with_no_trimmed_paths!(mono_item.to_string()),
+            size_estimate: mono_item.size_estimate(tcx),
+            codegen_units,
+            requested_by,
+        });
+    }
+
+    let mut functions: Vec<_> = instantiations_per_def_id
+        .into_iter()
+        .map(|(def_id, mut instantiations)| {
+            instantiations.sort_by(|a, b| {
+                b.size_estimate.cmp(&a.size_estimate).then_with(|| a.name.cmp(&b.name))
+            });
+            GenericFunction {
+                name: with_no_trimmed_paths!(tcx.def_path_str(def_id)),
+                krate: tcx.crate_name(def_id.krate).to_string(),
+                instantiation_count: instantiations.len(),
+                total_size_estimate: instantiations.iter().map(|i| i.size_estimate).sum(),
+                instantiations,
+            }
+        })
+        .collect();
+    functions.sort_by(|a, b| {
+        b.total_size_estimate.cmp(&a.total_size_estimate).then_with(|| a.name.cmp(&b.name))
+    });
+
+    let report = MonoReport { crate_name: tcx.crate_name(LOCAL_CRATE).to_string(), functions };
+    let mut json = serde_json::to_string(&report).unwrap();
+    json.push('\n');
+    tcx.output_filenames(()).path(OutputType::MonoReport).overwrite(&json, tcx.sess);
+}
+
 pub fn provide(providers: &mut Providers) {
     providers.collect_and_partition_mono_items = collect_and_partition_mono_items;
 
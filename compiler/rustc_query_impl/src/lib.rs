@@ -40,7 +40,7 @@
 pub use crate::plumbing::{query_key_hash_verify_all, QueryCtxt};
 
 mod profiling_support;
-pub use self::profiling_support::alloc_self_profile_query_strings;
+pub use self::profiling_support::{alloc_self_profile_query_strings, resolve_time_report_items};
 
 struct DynamicConfig<
     'tcx,
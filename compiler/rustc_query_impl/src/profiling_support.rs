@@ -5,7 +5,8 @@
 use rustc_data_structures::fx::FxHashMap;
 use rustc_data_structures::profiling::SelfProfiler;
 use rustc_hir::def_id::{CrateNum, DefId, DefIndex, LocalDefId, LOCAL_CRATE};
-use rustc_hir::definitions::DefPathData;
+use rustc_hir::definitions::{DefPathData, DefPathHash};
+use rustc_middle::ty::print::with_no_trimmed_paths;
 use rustc_middle::ty::TyCtxt;
 use rustc_query_system::query::QueryCache;
 
@@ -258,3 +259,16 @@ pub fn alloc_self_profile_query_strings(tcx: TyCtxt<'_>) {
         alloc(tcx, &mut string_cache)
     }
 }
+
+/// Replaces the `DefPathHash`es recorded for `-Z time-report` with the paths of the items
+/// they belong to, which is not possible anymore once the `TyCtxt` is gone.
+pub fn resolve_time_report_items(tcx: TyCtxt<'_>) {
+    let Some(time_report) = tcx.prof.time_report() else {
+        return;
+    };
+
+    time_report.resolve_def_path_hashes(|hash| {
+        let def_id = tcx.def_path_hash_to_def_id(DefPathHash(hash))?;
+        Some(with_no_trimmed_paths!(tcx.def_path_str(def_id)))
+    });
+}
@@ -199,12 +199,18 @@ pub fn is_fully_enabled(&self) -> bool {
         self.data.is_some()
     }
 
-    pub fn with_query(&self, f: impl Fn(&DepGraphQuery)) {
+    pub fn with_query(&self, f: impl FnOnce(&DepGraphQuery)) {
         if let Some(data) = &self.data {
             data.current.encoder.with_query(f)
         }
     }
 
+    /// The dependency graph of the previous compilation session, if incremental compilation is
+    /// enabled.
+    pub fn previous_graph(&self) -> Option<&SerializedDepGraph> {
+        self.data.as_ref().map(|data| &*data.previous)
+    }
+
     pub fn assert_ignored(&self) {
         if let Some(..) = self.data {
             D::read_deps(|task_deps| {
@@ -851,7 +857,11 @@ fn try_mark_previous_green<Qcx: QueryContext<Deps = D>>(
         let prev_deps = self.previous.edge_targets_from(prev_dep_node_index);
 
         for dep_dep_node_index in prev_deps {
-            self.try_mark_parent_green(qcx, dep_dep_node_index, Some(&frame))?;
+            if self.try_mark_parent_green(qcx, dep_dep_node_index, Some(&frame)).is_none() {
+                let dep_dep_node = self.previous.index_to_node(dep_dep_node_index);
+                self.current.encoder.record_red_dependency(*dep_node, dep_dep_node);
+                return None;
+            }
         }
 
         // If we got here without hitting a `return` that means that all
@@ -1,3 +1,4 @@
+use rustc_data_structures::fingerprint::Fingerprint;
 use rustc_data_structures::fx::FxHashMap;
 use rustc_data_structures::graph::implementation::{Direction, Graph, NodeIndex, INCOMING};
 use rustc_index::IndexVec;
@@ -8,6 +9,14 @@ pub struct DepGraphQuery {
     pub graph: Graph<DepNode, ()>,
     pub indices: FxHashMap<DepNode, NodeIndex>,
     pub dep_index_to_index: IndexVec<DepNodeIndex, Option<NodeIndex>>,
+    /// The fingerprint of the result of each node of `graph`.
+    pub fingerprints: Vec<Fingerprint>,
+    /// Whether each node of `graph` was promoted from the previous session, as opposed to
+    /// being executed in this session.
+    pub promoted: Vec<bool>,
+    /// For the nodes of the previous session that could not be marked green, the dependency
+    /// that was red or could not be recomputed.
+    pub red_dependencies: FxHashMap<DepNode, DepNode>,
 }
 
 impl DepGraphQuery {
@@ -19,13 +28,29 @@ pub fn new(prev_node_count: usize) -> DepGraphQuery {
         let indices = FxHashMap::default();
         let dep_index_to_index = IndexVec::new();
 
-        DepGraphQuery { graph, indices, dep_index_to_index }
+        DepGraphQuery {
+            graph,
+            indices,
+            dep_index_to_index,
+            fingerprints: Vec::with_capacity(node_count),
+            promoted: Vec::with_capacity(node_count),
+            red_dependencies: FxHashMap::default(),
+        }
     }
 
-    pub fn push(&mut self, index: DepNodeIndex, node: DepNode, edges: &[DepNodeIndex]) {
+    pub fn push(
+        &mut self,
+        index: DepNodeIndex,
+        node: DepNode,
+        fingerprint: Fingerprint,
+        promoted: bool,
+        edges: &[DepNodeIndex],
+    ) {
         let source = self.graph.add_node(node);
         self.dep_index_to_index.insert(index, source);
         self.indices.insert(node, source);
+        self.fingerprints.push(fingerprint);
+        self.promoted.push(promoted);
 
         for &target in edges.iter() {
             let target = self.dep_index_to_index[target];
@@ -37,6 +62,12 @@ pub fn push(&mut self, index: DepNodeIndex, node: DepNode, edges: &[DepNodeIndex
         }
     }
 
+    /// Records that `node`, which existed in the previous session, could not be marked green
+    /// because of `dependency`.
+    pub fn push_red_dependency(&mut self, node: DepNode, dependency: DepNode) {
+        self.red_dependencies.entry(node).or_insert(dependency);
+    }
+
     pub fn nodes(&self) -> Vec<&DepNode> {
         self.graph.all_nodes().iter().map(|n| &n.data).collect()
     }
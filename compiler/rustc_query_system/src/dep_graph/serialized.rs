@@ -496,6 +496,8 @@ fn new(encoder: FileEncoder, record_stats: bool, previous: Arc<SerializedDepGrap
     fn record(
         &mut self,
         node: DepNode,
+        fingerprint: Fingerprint,
+        promoted: bool,
         edge_count: usize,
         edges: impl FnOnce(&mut Self) -> Vec<DepNodeIndex>,
         record_graph: &Option<Lock<DepGraphQuery>>,
@@ -514,7 +516,7 @@ fn record(
             outline(move || {
                 // Do not ICE when a query is called from within `with_query`.
                 if let Some(record_graph) = &mut record_graph.try_lock() {
-                    record_graph.push(index, node, &edges);
+                    record_graph.push(index, node, fingerprint, promoted, &edges);
                 }
             });
         }
@@ -541,7 +543,14 @@ fn encode_node(
         record_graph: &Option<Lock<DepGraphQuery>>,
     ) -> DepNodeIndex {
         node.encode::<D>(&mut self.encoder);
-        self.record(node.node, node.edges.len(), |_| node.edges[..].to_vec(), record_graph)
+        self.record(
+            node.node,
+            node.fingerprint,
+            false,
+            node.edges.len(),
+            |_| node.edges[..].to_vec(),
+            record_graph,
+        )
     }
 
     /// Encodes a node that was promoted from the previous graph. It reads the information directly from
@@ -571,6 +580,8 @@ fn encode_promoted_node(
 
         self.record(
             node,
+            fingerprint,
+            true,
             edge_count,
             |this| {
                 this.previous
@@ -637,12 +648,25 @@ pub fn new(
         GraphEncoder { status, record_graph, profiler: profiler.clone() }
     }
 
-    pub(crate) fn with_query(&self, f: impl Fn(&DepGraphQuery)) {
+    pub(crate) fn with_query(&self, f: impl FnOnce(&DepGraphQuery)) {
         if let Some(record_graph) = &self.record_graph {
             f(&record_graph.lock())
         }
     }
 
+    /// Records that `node` could not be marked green because of `dependency`, if the graph is
+    /// being recorded.
+    pub(crate) fn record_red_dependency(&self, node: DepNode, dependency: DepNode) {
+        if let Some(record_graph) = &self.record_graph {
+            outline(|| {
+                // Do not ICE when a query is called from within `with_query`.
+                if let Some(record_graph) = &mut record_graph.try_lock() {
+                    record_graph.push_red_dependency(node, dependency);
+                }
+            });
+        }
+    }
+
     pub(crate) fn print_incremental_info(
         &self,
         total_read_count: u64,
@@ -10,6 +10,7 @@
 
 use rustc_data_structures::fingerprint::Fingerprint;
 use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::profiling::TimeReportItem;
 use rustc_data_structures::sharded::Sharded;
 use rustc_data_structures::stack::ensure_sufficient_stack;
 use rustc_data_structures::sync::Lock;
@@ -21,7 +22,9 @@
 use tracing::instrument;
 
 use super::QueryConfig;
-use crate::dep_graph::{DepContext, DepGraphData, DepNode, DepNodeIndex, DepNodeParams};
+use crate::dep_graph::{
+    DepContext, DepGraphData, DepNode, DepNodeIndex, DepNodeParams, FingerprintStyle,
+};
 use crate::ich::StableHashingContext;
 use crate::query::caches::QueryCache;
 #[cfg(parallel_compiler)]
@@ -394,6 +397,15 @@ fn execute_job<Q, Qcx, const INCR: bool>(
 
     debug_assert_eq!(qcx.dep_context().dep_graph().is_fully_enabled(), INCR);
 
+    let time_report_guard = qcx.dep_context().profiler().time_report_activity(query.name(), || {
+        match qcx.dep_context().fingerprint_style(query.dep_kind()) {
+            FingerprintStyle::DefPathHash => {
+                TimeReportItem::DefPathHash(key.to_fingerprint(*qcx.dep_context()))
+            }
+            _ => TimeReportItem::None,
+        }
+    });
+
     let (result, dep_node_index) = if INCR {
         execute_job_incr(
             query,
@@ -406,6 +418,7 @@ fn execute_job<Q, Qcx, const INCR: bool>(
     } else {
         execute_job_non_incr(query, qcx, key, id)
     };
+    drop(time_report_guard);
 
     let cache = query.query_cache(qcx);
     if query.feedable() {
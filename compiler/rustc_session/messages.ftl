@@ -82,6 +82,13 @@ session_octal_float_literal_not_supported = octal float literal is not supported
 
 session_optimization_fuel_exhausted = optimization-fuel-exhausted: {$msg}
 
+session_overlapping_suggestions_skipped =
+    skipped {$count} {$count ->
+        [one] suggestion
+        *[other] suggestions
+    } in `{$path}` that overlapped other suggestions
+    .note = compiling again applies the suggestions that are still relevant
+
 session_profile_sample_use_file_does_not_exist = file `{$path}` passed to `-C profile-sample-use` does not exist
 
 session_profile_use_file_does_not_exist = file `{$path}` passed to `-C profile-use` does not exist
@@ -107,6 +114,8 @@ session_split_debuginfo_unstable_platform = `-Csplit-debuginfo={$debuginfo}` is
 
 session_split_lto_unit_requires_lto = `-Zsplit-lto-unit` requires `-Clto`, `-Clto=thin`, or `-Clinker-plugin-lto`
 
+session_suggestions_not_applied = not applying suggestions to `{$path}` because it changed since it was compiled
+
 session_target_requires_unwind_tables = target requires unwind tables, they cannot be disabled with `-C force-unwind-tables=no`
 
 session_target_stack_protector_not_supported = `-Z stack-protector={$stack_protector}` is not supported for target {$target_triple} and will be ignored
@@ -17,6 +17,7 @@
 use rustc_data_structures::fx::{FxHashSet, FxIndexMap};
 use rustc_data_structures::stable_hasher::{StableOrd, ToStableHashKey};
 use rustc_errors::emitter::HumanReadableErrorType;
+use rustc_errors::json::JSON_DIAGNOSTIC_VERSIONS;
 use rustc_errors::{ColorConfig, DiagArgValue, DiagCtxtFlags, IntoDiagArg};
 use rustc_feature::UnstableFeatures;
 use rustc_macros::{Decodable, Encodable, HashStable_Generic};
@@ -29,6 +30,7 @@
 use tracing::debug;
 
 use crate::errors::FileWriteFail;
+use crate::lint_baseline::LintBaselineFile;
 pub use crate::options::*;
 use crate::search_paths::SearchPath;
 use crate::utils::{CanonicalizedPath, NativeLib, NativeLibKind};
@@ -81,7 +83,7 @@ pub enum CFProtection {
     Full,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Hash, HashStable_Generic)]
+#[derive(Clone, Copy, Debug, PartialEq, Hash, HashStable_Generic, Encodable, Decodable)]
 pub enum OptLevel {
     No,         // -O0
     Less,       // -O1
@@ -376,6 +378,31 @@ pub(crate) fn handle_cli_feature(&mut self, feature: &str) -> Option<()> {
     }
 }
 
+/// The linker implementations that can be selected with `-C linker-implementation`, regardless of
+/// the linker flavor: they are invoked directly, or through the C/C++ compiler with `-fuse-ld`.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum LinkerImplementation {
+    /// The GNU linker, `ld.bfd`.
+    Bfd,
+    /// The GNU gold linker, `ld.gold`.
+    Gold,
+    /// LLVM's linker, `ld.lld` or one of its other flavors, like `lld-link` or `wasm-ld`.
+    Lld,
+    /// The mold linker, `ld.mold`.
+    Mold,
+}
+
+impl LinkerImplementation {
+    pub fn desc(self) -> &'static str {
+        match self {
+            LinkerImplementation::Bfd => "bfd",
+            LinkerImplementation::Gold => "gold",
+            LinkerImplementation::Lld => "lld",
+            LinkerImplementation::Mold => "mold",
+        }
+    }
+}
+
 /// Used with `-Z assert-incr-state`.
 #[derive(Clone, Copy, PartialEq, Hash, Debug)]
 pub enum IncrementalStateAssertion {
@@ -495,6 +522,12 @@ pub enum OutputType {
     Object,
     Exe,
     DepInfo,
+    UnusedDeps,
+    ApiSurface,
+    MonoReport,
+    SizeMap,
+    OptRemarks,
+    UnsafeReport,
 }
 
 impl StableOrd for OutputType {
@@ -515,7 +548,15 @@ fn to_stable_hash_key(&self, _: &HCX) -> Self::KeyType {
 impl OutputType {
     fn is_compatible_with_codegen_units_and_single_output_file(&self) -> bool {
         match *self {
-            OutputType::Exe | OutputType::DepInfo | OutputType::Metadata => true,
+            OutputType::Exe
+            | OutputType::DepInfo
+            | OutputType::Metadata
+            | OutputType::UnusedDeps
+            | OutputType::ApiSurface
+            | OutputType::MonoReport
+            | OutputType::SizeMap
+            | OutputType::OptRemarks
+            | OutputType::UnsafeReport => true,
             OutputType::Bitcode
             | OutputType::ThinLinkBitcode
             | OutputType::Assembly
@@ -536,6 +577,33 @@ pub fn shorthand(&self) -> &'static str {
             OutputType::Metadata => "metadata",
             OutputType::Exe => "link",
             OutputType::DepInfo => "dep-info",
+            OutputType::UnusedDeps => "unused-deps",
+            OutputType::ApiSurface => "api-surface",
+            OutputType::MonoReport => "mono-report",
+            OutputType::SizeMap => "size-map",
+            OutputType::OptRemarks => "opt-remarks",
+            OutputType::UnsafeReport => "unsafe-report",
+        }
+    }
+
+    /// Whether emitting this output type requires `-Z unstable-options`.
+    fn is_unstable(&self) -> bool {
+        match *self {
+            OutputType::ThinLinkBitcode
+            | OutputType::UnusedDeps
+            | OutputType::ApiSurface
+            | OutputType::MonoReport
+            | OutputType::SizeMap
+            | OutputType::UnsafeReport => true,
+            OutputType::Bitcode
+            | OutputType::Assembly
+            | OutputType::LlvmAssembly
+            | OutputType::Mir
+            | OutputType::Object
+            | OutputType::Metadata
+            | OutputType::Exe
+            | OutputType::DepInfo
+            | OutputType::OptRemarks => false,
         }
     }
 
@@ -550,13 +618,20 @@ fn from_shorthand(shorthand: &str) -> Option<Self> {
             "metadata" => OutputType::Metadata,
             "link" => OutputType::Exe,
             "dep-info" => OutputType::DepInfo,
+            "unused-deps" => OutputType::UnusedDeps,
+            "api-surface" => OutputType::ApiSurface,
+            "mono-report" => OutputType::MonoReport,
+            "size-map" => OutputType::SizeMap,
+            "opt-remarks" => OutputType::OptRemarks,
+            "unsafe-report" => OutputType::UnsafeReport,
             _ => return None,
         })
     }
 
     fn shorthands_display() -> String {
         format!(
-            "`{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`",
+            "`{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, \
+            `{}`",
             OutputType::Bitcode.shorthand(),
             OutputType::ThinLinkBitcode.shorthand(),
             OutputType::Assembly.shorthand(),
@@ -566,6 +641,12 @@ fn shorthands_display() -> String {
             OutputType::Metadata.shorthand(),
             OutputType::Exe.shorthand(),
             OutputType::DepInfo.shorthand(),
+            OutputType::UnusedDeps.shorthand(),
+            OutputType::ApiSurface.shorthand(),
+            OutputType::MonoReport.shorthand(),
+            OutputType::SizeMap.shorthand(),
+            OutputType::OptRemarks.shorthand(),
+            OutputType::UnsafeReport.shorthand(),
         )
     }
 
@@ -579,6 +660,12 @@ pub fn extension(&self) -> &'static str {
             OutputType::Object => "o",
             OutputType::Metadata => "rmeta",
             OutputType::DepInfo => "d",
+            OutputType::UnusedDeps => "unused-deps.json",
+            OutputType::ApiSurface => "api-surface.txt",
+            OutputType::MonoReport => "mono-report.json",
+            OutputType::SizeMap => "size-map.json",
+            OutputType::OptRemarks => "opt-remarks.json",
+            OutputType::UnsafeReport => "unsafe-report.json",
             OutputType::Exe => "",
         }
     }
@@ -588,7 +675,13 @@ pub fn is_text_output(&self) -> bool {
             OutputType::Assembly
             | OutputType::LlvmAssembly
             | OutputType::Mir
-            | OutputType::DepInfo => true,
+            | OutputType::DepInfo
+            | OutputType::UnusedDeps
+            | OutputType::ApiSurface
+            | OutputType::MonoReport
+            | OutputType::SizeMap
+            | OutputType::OptRemarks
+            | OutputType::UnsafeReport => true,
             OutputType::Bitcode
             | OutputType::ThinLinkBitcode
             | OutputType::Object
@@ -611,6 +704,8 @@ pub enum ErrorOutputType {
         /// human output.
         json_rendered: HumanReadableErrorType,
     },
+    /// A single SARIF log for the whole session, consumed by code-scanning tools.
+    Sarif,
 }
 
 impl Default for ErrorOutputType {
@@ -681,8 +776,15 @@ pub fn should_codegen(&self) -> bool {
             | OutputType::LlvmAssembly
             | OutputType::Mir
             | OutputType::Object
-            | OutputType::Exe => true,
-            OutputType::Metadata | OutputType::DepInfo => false,
+            | OutputType::Exe
+            | OutputType::SizeMap
+            | OutputType::OptRemarks => true,
+            OutputType::Metadata
+            | OutputType::DepInfo
+            | OutputType::UnusedDeps
+            | OutputType::ApiSurface
+            | OutputType::MonoReport
+            | OutputType::UnsafeReport => false,
         })
     }
 
@@ -696,8 +798,13 @@ pub fn should_link(&self) -> bool {
             | OutputType::Mir
             | OutputType::Metadata
             | OutputType::Object
-            | OutputType::DepInfo => false,
-            OutputType::Exe => true,
+            | OutputType::DepInfo
+            | OutputType::UnusedDeps
+            | OutputType::ApiSurface
+            | OutputType::MonoReport
+            | OutputType::OptRemarks
+            | OutputType::UnsafeReport => false,
+            OutputType::Exe | OutputType::SizeMap => true,
         })
     }
 }
@@ -793,6 +900,7 @@ pub struct PrintRequest {
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum PrintKind {
     FileNames,
+    IncrementalCacheUsage,
     Sysroot,
     TargetLibdir,
     CrateName,
@@ -1099,6 +1207,17 @@ pub fn host_triple() -> &'static str {
     (option_env!("CFG_COMPILER_HOST_TRIPLE")).expect("CFG_COMPILER_HOST_TRIPLE")
 }
 
+/// The `--remap-path-prefix` mappings, from a prefix of local paths to the prefix they are
+/// replaced with, in the order they were given.
+///
+/// Only the prefixes the paths are replaced with are part of the dependency tracking hash, the
+/// local prefixes depend on where the sources are checked out. This makes it possible to reuse
+/// the incremental compilation cache from another checkout location that is remapped to the same
+/// prefix. Which files are remapped changes their names, which the incremental compilation cache
+/// already tracks.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RemapPathPrefix(pub Vec<(PathBuf, PathBuf)>);
+
 fn file_path_mapping(
     remap_path_prefix: Vec<(PathBuf, PathBuf)>,
     unstable_opts: &UnstableOptions,
@@ -1125,6 +1244,7 @@ fn default() -> Options {
             debuginfo_compression: DebugInfoCompression::None,
             lint_opts: Vec::new(),
             lint_cap: None,
+            lint_baseline: None,
             describe_lints: false,
             output_types: OutputTypes(BTreeMap::new()),
             search_paths: vec![],
@@ -1148,7 +1268,7 @@ fn default() -> Options {
             trimmed_def_paths: false,
             cli_forced_codegen_units: None,
             cli_forced_local_thinlto_off: false,
-            remap_path_prefix: Vec::new(),
+            remap_path_prefix: RemapPathPrefix::default(),
             real_rust_source_base_dir: None,
             edition: DEFAULT_EDITION,
             json_artifact_notifications: false,
@@ -1172,7 +1292,7 @@ pub fn build_dep_graph(&self) -> bool {
     }
 
     pub fn file_path_mapping(&self) -> FilePathMapping {
-        file_path_mapping(self.remap_path_prefix.clone(), &self.unstable_opts)
+        file_path_mapping(self.remap_path_prefix.0.clone(), &self.unstable_opts)
     }
 
     /// Returns `true` if there will be an output file generated.
@@ -1269,6 +1389,13 @@ fn is_empty(&self) -> bool {
         }
     }
 
+    pub fn contains(&self, pass: &str) -> bool {
+        match *self {
+            Passes::Some(ref v) => v.iter().any(|p| p == pass),
+            Passes::All => true,
+        }
+    }
+
     pub(crate) fn extend(&mut self, passes: impl IntoIterator<Item = String>) {
         match *self {
             Passes::Some(ref mut v) => v.extend(passes),
@@ -1420,7 +1547,7 @@ pub(crate) fn flagmulti_s(a: S, b: S, c: S) -> R {
         stable(longer(a, b), move |opts| opts.optflagmulti(a, b, c))
     }
 
-    fn opt(a: S, b: S, c: S, d: S) -> R {
+    pub(crate) fn opt(a: S, b: S, c: S, d: S) -> R {
         unstable(longer(a, b), move |opts| opts.optopt(a, b, c, d))
     }
     pub(crate) fn multi(a: S, b: S, c: S, d: S) -> R {
@@ -1476,7 +1603,7 @@ pub fn rustc_short_optgroups() -> Vec<RustcOptGroup> {
             "emit",
             "Comma separated list of types of output for \
              the compiler to emit",
-            "[asm|llvm-bc|llvm-ir|obj|metadata|link|dep-info|mir]",
+            "[asm|llvm-bc|llvm-ir|obj|metadata|link|dep-info|mir|opt-remarks]",
         ),
         opt::multi_s(
             "",
@@ -1485,7 +1612,8 @@ pub fn rustc_short_optgroups() -> Vec<RustcOptGroup> {
             "[crate-name|file-names|sysroot|target-libdir|cfg|check-cfg|calling-conventions|\
              target-list|target-cpus|target-features|relocation-models|code-models|\
              tls-models|target-spec-json|all-target-specs-json|native-static-libs|\
-             stack-protector-strategies|link-args|deployment-target]",
+             stack-protector-strategies|link-args|deployment-target|\
+             incremental-cache-usage]",
         ),
         opt::flagmulti_s("g", "", "Equivalent to -C debuginfo=2"),
         opt::flagmulti_s("O", "", "Equivalent to -C opt-level=2"),
@@ -1544,7 +1672,7 @@ pub fn rustc_optgroups() -> Vec<RustcOptGroup> {
             "",
             "error-format",
             "How errors and other messages are produced",
-            "human|json|short",
+            "human|json|short|sarif",
         ),
         opt::multi_s("", "json", "Configure the JSON output of the compiler", "CONFIG"),
         opt::opt_s(
@@ -1569,6 +1697,12 @@ pub fn rustc_optgroups() -> Vec<RustcOptGroup> {
             "FROM=TO",
         ),
         opt::multi("", "env-set", "Inject an environment variable", "VAR=VALUE"),
+        opt::opt(
+            "",
+            "lint-baseline",
+            "Do not report the lint warnings recorded in this file",
+            "PATH",
+        ),
     ]);
     opts
 }
@@ -1718,14 +1852,15 @@ pub fn parse_error_format(
             Some("json") => ErrorOutputType::Json { pretty: false, json_rendered },
             Some("pretty-json") => ErrorOutputType::Json { pretty: true, json_rendered },
             Some("short") => ErrorOutputType::HumanReadable(HumanReadableErrorType::Short(color)),
+            Some("sarif") => ErrorOutputType::Sarif,
 
             Some(arg) => {
                 early_dcx.abort_if_error_and_set_error_format(ErrorOutputType::HumanReadable(
                     HumanReadableErrorType::Default(color),
                 ));
                 early_dcx.early_fatal(format!(
-                    "argument for `--error-format` must be `human`, `json` or \
-                     `short` (instead was `{arg}`)"
+                    "argument for `--error-format` must be `human`, `json`, `short` or \
+                     `sarif` (instead was `{arg}`)"
                 ))
             }
         }
@@ -1789,6 +1924,9 @@ fn check_error_format_stability(
         {
             early_dcx.early_fatal("`--error-format=human-annotate-rs` is unstable");
         }
+        if let ErrorOutputType::Sarif = error_format {
+            early_dcx.early_fatal("`--error-format=sarif` is unstable");
+        }
     }
 }
 
@@ -1808,10 +1946,10 @@ fn parse_output_types(
                         display = OutputType::shorthands_display(),
                     ))
                 });
-                if output_type == OutputType::ThinLinkBitcode && !unstable_opts.unstable_options {
+                if output_type.is_unstable() && !unstable_opts.unstable_options {
                     early_dcx.early_fatal(format!(
                         "{} requested but -Zunstable-options not specified",
-                        OutputType::ThinLinkBitcode.shorthand()
+                        output_type.shorthand()
                     ));
                 }
                 output_types.insert(output_type, path);
@@ -1903,6 +2041,7 @@ fn collect_print_requests(
         ("crate-name", PrintKind::CrateName),
         ("deployment-target", PrintKind::DeploymentTarget),
         ("file-names", PrintKind::FileNames),
+        ("incremental-cache-usage", PrintKind::IncrementalCacheUsage),
         ("link-args", PrintKind::LinkArgs),
         ("native-static-libs", PrintKind::NativeStaticLibs),
         ("relocation-models", PrintKind::RelocationModels),
@@ -1958,6 +2097,21 @@ fn collect_print_requests(
                     );
                 }
             }
+            Some((_, PrintKind::IncrementalCacheUsage)) => {
+                if !unstable_opts.unstable_options {
+                    early_dcx.early_fatal(
+                        "the `-Z unstable-options` flag must also be passed to \
+                         enable the incremental-cache-usage print option",
+                    );
+                }
+                if cg.incremental.is_none() {
+                    early_dcx.early_fatal(
+                        "the incremental-cache-usage print option requires also using \
+                         `-C incremental`",
+                    );
+                }
+                PrintKind::IncrementalCacheUsage
+            }
             Some(&(_, print_kind)) => print_kind,
             None => {
                 let prints =
@@ -2407,6 +2561,48 @@ pub fn build_session_options(early_dcx: &mut EarlyDiagCtxt, matches: &getopts::M
 
     check_error_format_stability(early_dcx, &unstable_opts, error_format);
 
+    let lint_baseline = matches.opt_str("lint-baseline").map(|path| {
+        let path = PathBuf::from(path);
+        let contents = (!unstable_opts.update_lint_baseline).then(|| {
+            fs::read_to_string(&path).unwrap_or_else(|err| {
+                early_dcx.early_fatal(format!(
+                    "failed to read lint baseline `{}`: {err}",
+                    path.display()
+                ))
+            })
+        });
+        LintBaselineFile { path, contents }
+    });
+    if unstable_opts.update_lint_baseline && lint_baseline.is_none() {
+        early_dcx.early_fatal("`-Z update-lint-baseline` requires also using `--lint-baseline`");
+    }
+
+    if unstable_opts.apply_suggestions_dry_run.is_some()
+        && unstable_opts.apply_suggestions.is_none()
+    {
+        early_dcx.early_fatal(
+            "`-Z apply-suggestions-dry-run` requires also using `-Z apply-suggestions`",
+        );
+    }
+
+    if let Some(version) = unstable_opts.json_diagnostic_version {
+        if !matches!(error_format, ErrorOutputType::Json { .. }) {
+            early_dcx.early_fatal(
+                "`-Z json-diagnostic-version` requires also using `--error-format=json`",
+            );
+        }
+        if !JSON_DIAGNOSTIC_VERSIONS.contains(&version) {
+            early_dcx.early_fatal(format!(
+                "unsupported JSON diagnostic version `{version}` (supported versions: {})",
+                JSON_DIAGNOSTIC_VERSIONS
+                    .iter()
+                    .map(|v| v.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+    }
+
     let output_types = parse_output_types(early_dcx, &unstable_opts, matches);
 
     let mut cg = CodegenOptions::build(early_dcx, matches);
@@ -2433,9 +2629,40 @@ pub fn build_session_options(early_dcx: &mut EarlyDiagCtxt, matches: &getopts::M
 
     let assert_incr_state = parse_assert_incr_state(early_dcx, &unstable_opts.assert_incr_state);
 
+    if unstable_opts.export_dep_graph.is_some() && incremental.is_none() {
+        early_dcx.early_fatal("`-Z export-dep-graph` requires also using `-C incremental`");
+    }
+
+    if (unstable_opts.incremental_cache_max_age.is_some()
+        || unstable_opts.incremental_cache_max_size.is_some())
+        && incremental.is_none()
+    {
+        early_dcx.early_fatal(
+            "`-Z incremental-cache-max-age` and `-Z incremental-cache-max-size` require also \
+             using `-C incremental`",
+        );
+    }
+
     if unstable_opts.profile && incremental.is_some() {
         early_dcx.early_fatal("can't instrument with gcov profiling when compiling incrementally");
     }
+    if unstable_opts.update_lint_baseline && incremental.is_some() {
+        // The lint diagnostics replayed from the incremental compilation cache are not recorded.
+        early_dcx
+            .early_fatal("`-Z update-lint-baseline` is incompatible with incremental compilation");
+    }
+    if output_types.contains_key(&OutputType::OptRemarks) && incremental.is_some() {
+        // The remarks of the codegen backend are recorded while optimizing, which doesn't happen
+        // again for the codegen units reused from the incremental compilation cache.
+        early_dcx.early_warn(
+            "`--emit=opt-remarks` is incomplete when compiling incrementally, it lacks the \
+             remarks of LLVM on the code reused from the incremental compilation cache",
+        );
+    }
+    if output_types.contains_key(&OutputType::OptRemarks) && unstable_opts.remark_dir.is_some() {
+        // The remarks of LLVM are written to the remark directory instead of being recorded.
+        early_dcx.early_fatal("`--emit=opt-remarks` and `-Z remark-dir` are incompatible");
+    }
     if unstable_opts.profile {
         match codegen_units {
             Some(1) => {}
@@ -2535,8 +2762,8 @@ pub fn build_session_options(early_dcx: &mut EarlyDiagCtxt, matches: &getopts::M
     }
 
     // For testing purposes, until we have more feedback about these options: ensure `-Z
-    // unstable-options` is required when using the unstable `-C link-self-contained` and `-C
-    // linker-flavor` options.
+    // unstable-options` is required when using the unstable `-C link-self-contained`, `-C
+    // linker-flavor` and `-C linker-implementation` options.
     if !nightly_options::is_unstable_enabled(matches) {
         let uses_unstable_self_contained_option =
             cg.link_self_contained.are_unstable_variants_set();
@@ -2556,6 +2783,13 @@ pub fn build_session_options(early_dcx: &mut EarlyDiagCtxt, matches: &getopts::M
                 ));
             }
         }
+
+        if cg.linker_implementation.is_some() {
+            early_dcx.early_fatal(
+                "`-C linker-implementation` is unstable, the `-Z unstable-options` flag must also \
+                be passed to use it",
+            );
+        }
     }
 
     // Check `-C link-self-contained` for consistency: individual components cannot be both enabled
@@ -2661,6 +2895,7 @@ pub fn build_session_options(early_dcx: &mut EarlyDiagCtxt, matches: &getopts::M
         debuginfo_compression,
         lint_opts,
         lint_cap,
+        lint_baseline,
         describe_lints,
         output_types,
         search_paths,
@@ -2684,7 +2919,7 @@ pub fn build_session_options(early_dcx: &mut EarlyDiagCtxt, matches: &getopts::M
         trimmed_def_paths: false,
         cli_forced_codegen_units: codegen_units,
         cli_forced_local_thinlto_off: disable_local_thinlto,
-        remap_path_prefix,
+        remap_path_prefix: RemapPathPrefix(remap_path_prefix),
         real_rust_source_base_dir,
         edition,
         json_artifact_notifications,
@@ -2981,11 +3216,12 @@ pub(crate) mod dep_tracking {
         CrateType, DebugInfo, DebugInfoCompression, ErrorOutputType, FunctionReturn,
         InliningThreshold, InstrumentCoverage, InstrumentXRay, LinkerPluginLto, LocationDetail,
         LtoCli, NextSolverConfig, OomStrategy, OptLevel, OutFileName, OutputType, OutputTypes,
-        PatchableFunctionEntry, Polonius, RemapPathScopeComponents, ResolveDocLinks,
-        SourceFileHashAlgorithm, SplitDwarfKind, SwitchWithOptPath, SymbolManglingVersion,
-        WasiExecModel,
+        PatchableFunctionEntry, Polonius, RemapPathPrefix, RemapPathScopeComponents,
+        ResolveDocLinks, SourceFileHashAlgorithm, SplitDwarfKind, SwitchWithOptPath,
+        SymbolManglingVersion, WasiExecModel,
     };
     use crate::lint;
+    use crate::lint_baseline::LintBaselineFile;
     use crate::utils::NativeLib;
 
     pub(crate) trait DepTrackingHash {
@@ -3081,8 +3317,16 @@ fn hash(
         InliningThreshold,
         FunctionReturn,
         WasmCAbi,
+        LintBaselineFile,
     );
 
+    impl DepTrackingHash for RemapPathPrefix {
+        fn hash(&self, hasher: &mut DefaultHasher, _: ErrorOutputType, _for_crate_hash: bool) {
+            let remapped_prefixes: Vec<&PathBuf> = self.0.iter().map(|(_from, to)| to).collect();
+            Hash::hash(&remapped_prefixes, hasher);
+        }
+    }
+
     impl<T1, T2> DepTrackingHash for (T1, T2)
     where
         T1: DepTrackingHash,
@@ -195,6 +195,20 @@ pub(crate) struct FileWriteFail<'a> {
     pub(crate) err: String,
 }
 
+#[derive(Diagnostic)]
+#[diag(session_suggestions_not_applied)]
+pub(crate) struct SuggestionsNotApplied<'a> {
+    pub(crate) path: &'a std::path::Path,
+}
+
+#[derive(Diagnostic)]
+#[diag(session_overlapping_suggestions_skipped)]
+#[note]
+pub(crate) struct OverlappingSuggestionsSkipped<'a> {
+    pub(crate) path: &'a std::path::Path,
+    pub(crate) count: usize,
+}
+
 #[derive(Diagnostic)]
 #[diag(session_crate_name_does_not_match)]
 pub(crate) struct CrateNameDoesNotMatch {
@@ -19,6 +19,8 @@
 pub mod config;
 pub mod cstore;
 pub mod filesearch;
+pub mod lint_baseline;
+pub mod opt_remarks;
 mod options;
 pub mod search_paths;
 
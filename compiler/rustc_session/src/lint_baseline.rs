@@ -0,0 +1,137 @@
+//! Lint baselines, used by `--lint-baseline`.
+//!
+//! A baseline file lists lint diagnostics that already exist in a crate. The lints listed in it
+//! are allowed where they occur, so that lints can be denied for new code without fixing all
+//! the existing warnings first. Each entry is keyed by the lint name, the file of the primary
+//! span and a fingerprint of the source code the primary span points to. The position of the span
+//! is deliberately not part of the key, so that unrelated edits moving the code around don't
+//! invalidate the baseline. The number of diagnostics each entry allows is recorded, so
+//! that a second occurrence of an already baselined warning in the same file is still reported.
+//!
+//! The file is a list of tab-separated `lint fingerprint count path` lines, sorted so that it
+//! produces readable diffs when checked in. Lines starting with `#` are comments.
+
+use std::fmt::Write;
+use std::hash::Hash;
+use std::path::PathBuf;
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::stable_hasher::{Hash64, StableHasher};
+use rustc_span::source_map::SourceMap;
+use rustc_span::Span;
+
+#[cfg(test)]
+mod tests;
+
+/// The `--lint-baseline` option. The contents of the file are part of the options, so that
+/// changing them invalidates the incremental compilation cache, whose lint diagnostics were
+/// reported with the previous baseline.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct LintBaselineFile {
+    pub path: PathBuf,
+    /// The contents of the file, unless it is rewritten with `-Z update-lint-baseline`.
+    pub contents: Option<String>,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+struct LintBaselineKey {
+    lint: String,
+    path: String,
+    fingerprint: Hash64,
+}
+
+pub struct LintBaseline {
+    /// With `recording`, the number of diagnostics seen for each key. Otherwise, the number of
+    /// diagnostics each key can still allow.
+    entries: FxHashMap<LintBaselineKey, usize>,
+    /// Whether the baseline is being regenerated with `-Z update-lint-baseline`, in which case
+    /// all lint diagnostics are recorded and allowed.
+    recording: bool,
+}
+
+impl LintBaseline {
+    /// Parses the contents of a baseline file.
+    pub fn parse(contents: &str) -> Result<LintBaseline, String> {
+        let mut entries = FxHashMap::default();
+        for (i, line) in contents.lines().enumerate() {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let malformed = || format!("malformed entry on line {}: `{line}`", i + 1);
+            let mut fields = line.splitn(4, '\t');
+            let (Some(lint), Some(fingerprint), Some(count), Some(path)) =
+                (fields.next(), fields.next(), fields.next(), fields.next())
+            else {
+                return Err(malformed());
+            };
+            let fingerprint = u64::from_str_radix(fingerprint, 16).map_err(|_| malformed())?;
+            let count = count.parse::<usize>().map_err(|_| malformed())?;
+            let key = LintBaselineKey {
+                lint: lint.to_owned(),
+                path: path.to_owned(),
+                fingerprint: Hash64::new(fingerprint),
+            };
+            *entries.entry(key).or_default() += count;
+        }
+        Ok(LintBaseline { entries, recording: false })
+    }
+
+    /// Creates an empty baseline that records every lint diagnostic it is asked about.
+    pub fn recording() -> LintBaseline {
+        LintBaseline { entries: FxHashMap::default(), recording: true }
+    }
+
+    /// Returns whether the `lint` diagnostic whose primary span is `span` is covered by the
+    /// baseline, and must be allowed.
+    pub fn covers(&mut self, lint: String, span: Option<Span>, sm: &SourceMap) -> bool {
+        let key = LintBaselineKey::new(lint, span, sm);
+
+        if self.recording {
+            *self.entries.entry(key).or_default() += 1;
+            return true;
+        }
+        match self.entries.get_mut(&key) {
+            Some(remaining) if *remaining > 0 => {
+                *remaining -= 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Renders the entries in the format of a baseline file.
+    pub fn to_file_contents(&self) -> String {
+        let mut entries: Vec<_> = self.entries.iter().filter(|&(_, &count)| count > 0).collect();
+        entries.sort();
+
+        let mut contents = String::from(
+            "# rustc lint baseline, regenerate with `-Z update-lint-baseline`\n\
+             # lint\tfingerprint\tcount\tpath\n",
+        );
+        for (LintBaselineKey { lint, path, fingerprint }, count) in entries {
+            writeln!(contents, "{lint}\t{fingerprint:016x}\t{count}\t{path}").unwrap();
+        }
+        contents
+    }
+}
+
+impl LintBaselineKey {
+    fn new(lint: String, span: Option<Span>, sm: &SourceMap) -> LintBaselineKey {
+        let (path, snippet) = match span {
+            Some(span) if !span.is_dummy() => {
+                let file = sm.lookup_char_pos(span.lo()).file;
+                let path = sm.filename_for_diagnostics(&file.name).to_string();
+                (path, sm.span_to_snippet(span).unwrap_or_default())
+            }
+            _ => (String::new(), String::new()),
+        };
+
+        // Hash the snippet with normalized whitespace, so that re-indenting the code doesn't
+        // invalidate the entry either.
+        let mut hasher = StableHasher::new();
+        for word in snippet.split_whitespace() {
+            word.hash(&mut hasher);
+        }
+        LintBaselineKey { lint, path, fingerprint: hasher.finish() }
+    }
+}
@@ -0,0 +1,61 @@
+use std::path::Path;
+
+use rustc_span::source_map::FilePathMapping;
+use rustc_span::{BytePos, Span};
+
+use super::*;
+
+/// Asks `lint_baseline` about an `unused_variables` lint at each of the `spans` of `code`, and
+/// returns how many of them are not covered, so would be reported.
+fn reported(code: &str, lint_baseline: &mut LintBaseline, spans: &[(u32, u32)]) -> usize {
+    rustc_span::create_default_session_globals_then(|| {
+        let sm = SourceMap::new(FilePathMapping::empty());
+        sm.new_source_file(Path::new("test.rs").to_owned().into(), code.to_owned());
+        spans
+            .iter()
+            .filter(|&&(lo, hi)| {
+                let span = Span::with_root_ctxt(BytePos(lo), BytePos(hi));
+                !lint_baseline.covers("unused_variables".to_owned(), Some(span), &sm)
+            })
+            .count()
+    })
+}
+
+#[test]
+fn record_and_allow() {
+    let code = "fn main() {\n    let x = 1;\n}\n";
+    let mut recording = LintBaseline::recording();
+    assert_eq!(reported(code, &mut recording, &[(20, 21)]), 0);
+    let recorded = recording.to_file_contents();
+    assert_eq!(recorded.lines().filter(|line| !line.starts_with('#')).count(), 1);
+
+    // The lint is allowed wherever it moves in the file.
+    let code = "fn main() {\n    let y = 2;\n        let x = 1;\n}\n";
+    let mut lint_baseline = LintBaseline::parse(&recorded).unwrap();
+    assert_eq!(reported(code, &mut lint_baseline, &[(39, 40)]), 0);
+
+    // Other occurrences are still reported.
+    let mut lint_baseline = LintBaseline::parse(&recorded).unwrap();
+    assert_eq!(reported(code, &mut lint_baseline, &[(39, 40), (39, 40), (20, 21)]), 2);
+}
+
+#[test]
+fn counts() {
+    let code = "fn main() {\n    let x = 1;\n}\n";
+    let mut recording = LintBaseline::recording();
+    assert_eq!(reported(code, &mut recording, &[(20, 21); 2]), 0);
+    let recorded = recording.to_file_contents();
+    assert!(recorded.lines().last().unwrap().ends_with("\t2\ttest.rs"));
+
+    let mut lint_baseline = LintBaseline::parse(&recorded).unwrap();
+    assert_eq!(reported(code, &mut lint_baseline, &[(20, 21); 3]), 1);
+}
+
+#[test]
+fn malformed() {
+    assert_eq!(
+        LintBaseline::parse("# comment\n\nunused_variables\tnot-hex\t1\ttest.rs\n").err().unwrap(),
+        "malformed entry on line 3: `unused_variables\tnot-hex\t1\ttest.rs`"
+    );
+    assert!(LintBaseline::parse("unused_variables\t0\t1").is_err());
+}
@@ -0,0 +1,83 @@
+//! The optimization remarks of the MIR and of the codegen backend, emitted with
+//! `--emit=opt-remarks`.
+
+use rustc_data_structures::sync::Lock;
+use rustc_macros::{Decodable, Encodable, HashStable_Generic};
+use serde::Serialize;
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize)]
+#[derive(Encodable, Decodable, HashStable_Generic)]
+pub struct OptRemarkLocation {
+    /// The path of the file, remapped with `--remap-path-prefix`.
+    pub file: String,
+    pub line: u32,
+    pub column: u32,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize)]
+#[derive(Encodable, Decodable, HashStable_Generic)]
+#[serde(rename_all = "snake_case")]
+pub enum OptRemarkOrigin {
+    Mir,
+    Llvm,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize)]
+#[derive(Encodable, Decodable, HashStable_Generic)]
+#[serde(rename_all = "snake_case")]
+pub enum OptRemarkKind {
+    /// The optimization was applied.
+    Success,
+    /// The optimization was not applied.
+    Missed,
+    /// Information gathered by the pass, like the cost of a call.
+    Analysis,
+    Other,
+}
+
+impl OptRemarkKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            OptRemarkKind::Success => "success",
+            OptRemarkKind::Missed => "missed",
+            OptRemarkKind::Analysis => "analysis",
+            OptRemarkKind::Other => "other",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize)]
+#[derive(Encodable, Decodable, HashStable_Generic)]
+pub struct OptRemark {
+    /// The location of the remark, or `None` if it is unknown, like without debuginfo.
+    pub location: Option<OptRemarkLocation>,
+    pub origin: OptRemarkOrigin,
+    pub pass: String,
+    pub kind: OptRemarkKind,
+    /// The demangled path of the function the remark is about.
+    pub function: String,
+    pub message: String,
+}
+
+/// The optimization remarks of the crate, recorded only when they are emitted. The remarks of the
+/// MIR are recorded from the optimized MIR of the functions, those of the codegen backend while
+/// generating code.
+#[derive(Default)]
+pub struct OptRemarks {
+    remarks: Lock<Vec<OptRemark>>,
+}
+
+impl OptRemarks {
+    pub fn record(&self, remark: OptRemark) {
+        self.remarks.borrow_mut().push(remark);
+    }
+
+    /// Returns the recorded remarks, sorted by location and without duplicates, which are
+    /// expected when a function is optimized in several codegen units.
+    pub fn take(&self) -> Vec<OptRemark> {
+        let mut remarks = std::mem::take(&mut *self.remarks.borrow_mut());
+        remarks.sort();
+        remarks.dedup();
+        remarks
+    }
+}
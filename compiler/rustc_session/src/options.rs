@@ -18,6 +18,7 @@
 };
 
 use crate::config::*;
+use crate::lint_baseline::LintBaselineFile;
 use crate::search_paths::SearchPath;
 use crate::utils::NativeLib;
 use crate::{lint, EarlyDiagCtxt};
@@ -140,6 +141,8 @@ pub struct Options {
         debuginfo_compression: DebugInfoCompression [TRACKED],
         lint_opts: Vec<(String, lint::Level)> [TRACKED_NO_CRATE_HASH],
         lint_cap: Option<lint::Level> [TRACKED_NO_CRATE_HASH],
+        /// The baseline file of the lint diagnostics that are allowed.
+        lint_baseline: Option<LintBaselineFile> [TRACKED_NO_CRATE_HASH],
         describe_lints: bool [UNTRACKED],
         output_types: OutputTypes [TRACKED],
         search_paths: Vec<SearchPath> [UNTRACKED],
@@ -193,7 +196,7 @@ pub struct Options {
         cli_forced_local_thinlto_off: bool [UNTRACKED],
 
         /// Remap source path prefixes in all output (messages, object files, debug, etc.).
-        remap_path_prefix: Vec<(PathBuf, PathBuf)> [TRACKED_NO_CRATE_HASH],
+        remap_path_prefix: RemapPathPrefix [TRACKED_NO_CRATE_HASH],
         /// Base directory containing the `src/` for the Rust standard library, and
         /// potentially `rustc` as well, if we can find it. Right now it's always
         /// `$sysroot/lib/rustlib/src/rust` (i.e. the `rustup` `rust-src` component).
@@ -371,7 +374,13 @@ mod desc {
         "a comma-separated list of strings, with elements beginning with + or -";
     pub const parse_comma_list: &str = "a comma-separated list of strings";
     pub const parse_opt_comma_list: &str = parse_comma_list;
+    pub const parse_apply_suggestions: &str =
+        "a comma-separated list of lint names, or nothing to apply all suggestions";
     pub const parse_number: &str = "a number";
+    pub const parse_opt_byte_size: &str =
+        "a number of bytes, optionally followed by `K`, `M`, `G` or `T` (powers of 1024)";
+    pub const parse_opt_duration_secs: &str =
+        "a number of seconds, optionally followed by `s`, `m`, `h` or `d`";
     pub const parse_opt_number: &str = parse_number;
     pub const parse_frame_pointer: &str = "one of `true`/`yes`/`on`, `false`/`no`/`off`, or (with -Zunstable-options) `non-leaf` or `always`";
     pub const parse_threads: &str = parse_number;
@@ -430,6 +439,7 @@ mod desc {
         components: `crto`, `libc`, `unwind`, `linker`, `sanitizers`, `mingw`";
     pub const parse_linker_features: &str =
         "a list of enabled (`+` prefix) and disabled (`-` prefix) features: `lld`";
+    pub const parse_linker_implementation: &str = "one of: `bfd`, `gold`, `lld`, `mold`";
     pub const parse_polonius: &str = "either no value or `legacy` (the default), or `next`";
     pub const parse_stack_protector: &str =
         "one of (`none` (default), `basic`, `strong`, or `all`)";
@@ -635,6 +645,15 @@ pub(crate) fn parse_opt_comma_list(slot: &mut Option<Vec<String>>, v: Option<&st
         }
     }
 
+    pub(crate) fn parse_apply_suggestions(slot: &mut Option<Vec<String>>, v: Option<&str>) -> bool {
+        let lints = match v {
+            Some(s) => s.split(',').map(|lint| lint.replace('-', "_")).collect(),
+            None => Vec::new(),
+        };
+        *slot = Some(lints);
+        true
+    }
+
     pub(crate) fn parse_threads(slot: &mut usize, v: Option<&str>) -> bool {
         match v.and_then(|s| s.parse().ok()) {
             Some(0) => {
@@ -674,6 +693,31 @@ pub(crate) fn parse_opt_number<T: Copy + FromStr>(
         }
     }
 
+    /// Parses a number optionally followed by one of the given unit suffixes, into the number of
+    /// base units.
+    fn parse_number_with_unit(v: &str, units: &[(char, u64)]) -> Option<u64> {
+        let (number, unit) = v
+            .char_indices()
+            .last()
+            .and_then(|(i, suffix)| {
+                units.iter().find(|&&(c, _)| c == suffix).map(|&(_, unit)| (&v[..i], unit))
+            })
+            .unwrap_or((v, 1));
+        number.parse::<u64>().ok()?.checked_mul(unit)
+    }
+
+    pub(crate) fn parse_opt_byte_size(slot: &mut Option<u64>, v: Option<&str>) -> bool {
+        let units = [('K', 1 << 10), ('M', 1 << 20), ('G', 1 << 30), ('T', 1 << 40)];
+        *slot = v.and_then(|v| parse_number_with_unit(v, &units));
+        slot.is_some()
+    }
+
+    pub(crate) fn parse_opt_duration_secs(slot: &mut Option<u64>, v: Option<&str>) -> bool {
+        let units = [('s', 1), ('m', 60), ('h', 60 * 60), ('d', 24 * 60 * 60)];
+        *slot = v.and_then(|v| parse_number_with_unit(v, &units));
+        slot.is_some()
+    }
+
     pub(crate) fn parse_frame_pointer(slot: &mut FramePointer, v: Option<&str>) -> bool {
         let mut yes = false;
         match v {
@@ -1319,6 +1363,20 @@ pub(crate) fn parse_linker_features(slot: &mut LinkerFeaturesCli, v: Option<&str
         }
     }
 
+    pub(crate) fn parse_linker_implementation(
+        slot: &mut Option<LinkerImplementation>,
+        v: Option<&str>,
+    ) -> bool {
+        *slot = Some(match v {
+            Some("bfd") => LinkerImplementation::Bfd,
+            Some("gold") => LinkerImplementation::Gold,
+            Some("lld") => LinkerImplementation::Lld,
+            Some("mold") => LinkerImplementation::Mold,
+            _ => return false,
+        });
+        true
+    }
+
     pub(crate) fn parse_wasi_exec_model(slot: &mut Option<WasiExecModel>, v: Option<&str>) -> bool {
         match v {
             Some("command") => *slot = Some(WasiExecModel::Command),
@@ -1548,6 +1606,8 @@ pub(crate) fn parse_wasm_c_abi(slot: &mut WasmCAbi, v: Option<&str>) -> bool {
         "system linker to link outputs with"),
     linker_flavor: Option<LinkerFlavorCli> = (None, parse_linker_flavor, [UNTRACKED],
         "linker flavor"),
+    linker_implementation: Option<LinkerImplementation> = (None, parse_linker_implementation, [UNTRACKED],
+        "linker implementation to use, directly or through the C compiler (`bfd`, `gold`, `lld`, or `mold`)"),
     linker_plugin_lto: LinkerPluginLto = (LinkerPluginLto::Disabled,
         parse_linker_plugin_lto, [TRACKED],
         "generate build artifacts that are compatible with linker-based LTO"),
@@ -1631,6 +1691,12 @@ pub(crate) fn parse_wasm_c_abi(slot: &mut WasmCAbi, v: Option<&str>) -> bool {
         "only allow the listed language features to be enabled in code (comma separated)"),
     always_encode_mir: bool = (false, parse_bool, [TRACKED],
         "encode MIR of all functions into the crate metadata (default: no)"),
+    apply_suggestions: Option<Vec<String>> = (None, parse_apply_suggestions, [UNTRACKED],
+        "apply the machine-applicable suggestions of the listed lints (comma separated), \
+         or of all diagnostics, to the source files"),
+    apply_suggestions_dry_run: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "write the changes of `-Z apply-suggestions` to this file as a diff instead of applying \
+         them"),
     assert_incr_state: Option<String> = (None, parse_opt_string, [UNTRACKED],
         "assert that the incremental cache is in given state: \
          either `loaded` or `not-loaded`."),
@@ -1714,6 +1780,9 @@ pub(crate) fn parse_wasm_c_abi(slot: &mut WasmCAbi, v: Option<&str>) -> bool {
         "emit the bc module with thin LTO info (default: yes)"),
     enforce_type_length_limit: bool = (false, parse_bool, [TRACKED],
         "enforce the type length limit when monomorphizing instances in codegen"),
+    export_dep_graph: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "write the dependency graphs of the previous and the current incremental session, and \
+         why each node was re-executed, to the given file as JSON"),
     export_executable_symbols: bool = (false, parse_bool, [TRACKED],
         "export symbols from executables, as if they were dynamic libraries"),
     external_clangrt: bool = (false, parse_bool, [UNTRACKED],
@@ -1754,6 +1823,12 @@ pub(crate) fn parse_wasm_c_abi(slot: &mut WasmCAbi, v: Option<&str>) -> bool {
         "display unnamed regions as `'<id>`, using a non-ident unique id (default: no)"),
     ignore_directory_in_diagnostics_source_blocks: Vec<String> = (Vec::new(), parse_string_push, [UNTRACKED],
         "do not display the source code block in diagnostics for files in the directory"),
+    incremental_cache_max_age: Option<u64> = (None, parse_opt_duration_secs, [UNTRACKED],
+        "delete the incremental caches of all crates in the incremental directory that were \
+         not used for longer than this"),
+    incremental_cache_max_size: Option<u64> = (None, parse_opt_byte_size, [UNTRACKED],
+        "delete the least recently used incremental caches of the crates in the incremental \
+         directory until it is smaller than this"),
     incremental_ignore_spans: bool = (false, parse_bool, [TRACKED],
         "ignore spans during ICH computation -- used for testing (default: no)"),
     incremental_info: bool = (false, parse_bool, [UNTRACKED],
@@ -1773,9 +1848,14 @@ pub(crate) fn parse_wasm_c_abi(slot: &mut WasmCAbi, v: Option<&str>) -> bool {
         "inlining threshold when the caller is a simple forwarding function (default: 30)"),
     inline_mir_hint_threshold: Option<usize> = (None, parse_opt_number, [TRACKED],
         "inlining threshold for functions with inline hint (default: 100)"),
+    inline_mir_hot_threshold: Option<usize> = (None, parse_opt_number, [TRACKED],
+        "inlining threshold for functions that are hot according to `-Z inline-mir-profile` \
+        (default: 400)"),
     inline_mir_preserve_debug: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "when MIR inlining, whether to preserve debug info for callee variables \
         (default: preserve for debuginfo != None, otherwise remove)"),
+    inline_mir_profile: Option<PathBuf> = (None, parse_opt_pathbuf, [TRACKED],
+        "a file with the number of calls to functions, used to inline the hot ones more in MIR"),
     inline_mir_threshold: Option<usize> = (None, parse_opt_number, [TRACKED],
         "a default MIR inlining threshold (default: 50)"),
     input_stats: bool = (false, parse_bool, [UNTRACKED],
@@ -1792,6 +1872,9 @@ pub(crate) fn parse_wasm_c_abi(slot: &mut WasmCAbi, v: Option<&str>) -> bool {
          `=skip-entry`
          `=skip-exit`
          Multiple options can be combined with commas."),
+    json_diagnostic_version: Option<u32> = (None, parse_opt_number, [UNTRACKED],
+        "emit JSON diagnostics in the given version of their schema, with a `$version` field
+        (currently only `1` is supported)"),
     layout_seed: Option<u64> = (None, parse_opt_number, [TRACKED],
         "seed layout randomization"),
     link_directives: bool = (true, parse_bool, [TRACKED],
@@ -1800,6 +1883,9 @@ pub(crate) fn parse_wasm_c_abi(slot: &mut WasmCAbi, v: Option<&str>) -> bool {
         "link native libraries in the linker invocation (default: yes)"),
     link_only: bool = (false, parse_bool, [TRACKED],
         "link the `.rlink` file generated by `-Z no-link` (default: no)"),
+    link_reproducer: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "write a reproducer of the linker invocation, with copies of its input files and a script \
+        replaying it, to a subdirectory of this directory"),
     linker_features: LinkerFeaturesCli = (LinkerFeaturesCli::default(), parse_linker_features, [UNTRACKED],
         "a comma-separated list of linker features to enable (+) or disable (-): `lld`"),
     lint_mir: bool = (false, parse_bool, [UNTRACKED],
@@ -1840,6 +1926,9 @@ pub(crate) fn parse_wasm_c_abi(slot: &mut WasmCAbi, v: Option<&str>) -> bool {
     mir_keep_place_mention: bool = (false, parse_bool, [TRACKED],
         "keep place mention MIR statements, interpreted e.g., by miri; implies -Zmir-opt-level=0 \
         (default: no)"),
+    mir_opt_bisect_limit: Option<usize> = (None, parse_opt_number, [TRACKED],
+        "only run the first N MIR optimization passes applied to the bodies of the crate, and \
+        print which ones are run (used for bisecting miscompilations)"),
     #[rustc_lint_opt_deny_field_access("use `Session::mir_opt_level` instead of this field")]
     mir_opt_level: Option<usize> = (None, parse_opt_number, [TRACKED],
         "MIR optimization level (0-4; default: 1 in non optimized builds and 2 in optimized builds)"),
@@ -1883,6 +1972,9 @@ pub(crate) fn parse_wasm_c_abi(slot: &mut WasmCAbi, v: Option<&str>) -> bool {
         "panic strategy for out-of-memory handling"),
     osx_rpath_install_name: bool = (false, parse_bool, [TRACKED],
         "pass `-install_name @rpath/...` to the macOS linker (default: no)"),
+    outline_cold_paths: bool = (false, parse_bool, [TRACKED],
+        "outline the cold paths of functions, like the formatting of panic messages, into \
+        separate functions in MIR at mir-opt-level 2 and higher (default: no)"),
     packed_bundled_libs: bool = (false, parse_bool, [TRACKED],
         "change rlib format to store native libraries as archives"),
     panic_abort_tests: bool = (false, parse_bool, [TRACKED],
@@ -1999,6 +2091,9 @@ pub(crate) fn parse_wasm_c_abi(slot: &mut WasmCAbi, v: Option<&str>) -> bool {
     /// o/w tests have closure@path
     span_free_formats: bool = (false, parse_bool, [UNTRACKED],
         "exclude spans when debug-printing compiler state (default: no)"),
+    specialize_const_args: bool = (false, parse_bool, [TRACKED],
+        "specialize functions for the constant flags and enum arguments of their calls in MIR, at \
+        mir-opt-level 2 and higher (default: no)"),
     split_dwarf_inlining: bool = (false, parse_bool, [TRACKED],
         "provide minimal debug info in the object/executable to facilitate online \
          symbolication/stack traces in the absence of .dwo/.dwp files when using Split DWARF"),
@@ -2046,6 +2141,8 @@ pub(crate) fn parse_wasm_c_abi(slot: &mut WasmCAbi, v: Option<&str>) -> bool {
         "measure time of each rustc pass (default: no)"),
     time_passes_format: TimePassesFormat = (TimePassesFormat::Text, parse_time_passes_format, [UNTRACKED],
         "the format to use for -Z time-passes (`text` (default) or `json`)"),
+    time_report: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "write a JSON report of the time spent per query, LLVM pass and item to the given file"),
     tiny_const_eval_limit: bool = (false, parse_bool, [TRACKED],
         "sets a tiny, non-configurable limit for const eval; useful for compiler tests"),
     #[rustc_lint_opt_deny_field_access("use `Session::tls_model` instead of this field")]
@@ -2104,6 +2201,8 @@ pub(crate) fn parse_wasm_c_abi(slot: &mut WasmCAbi, v: Option<&str>) -> bool {
     #[rustc_lint_opt_deny_field_access("use `Session::unstable_options` instead of this field")]
     unstable_options: bool = (false, parse_bool, [UNTRACKED],
         "adds unstable command line options to rustc interface (default: no)"),
+    update_lint_baseline: bool = (false, parse_bool, [UNTRACKED],
+        "rewrite the `--lint-baseline` file with the lints of this compilation (default: no)"),
     use_ctors_section: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "use legacy .ctors section for initializers rather than .init_array"),
     use_sync_unwind: Option<bool> = (None, parse_opt_bool, [TRACKED],
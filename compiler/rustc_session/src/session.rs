@@ -2,23 +2,25 @@
 use std::ops::{Div, Mul};
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
-use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicBool, AtomicUsize};
 use std::sync::atomic::Ordering::SeqCst;
 use std::sync::Arc;
-use std::{env, fmt, io};
+use std::{env, fmt, fs, io};
 
 use rustc_data_structures::flock;
 use rustc_data_structures::fx::{FxHashMap, FxIndexSet};
 use rustc_data_structures::jobserver::{self, Client};
-use rustc_data_structures::profiling::{SelfProfiler, SelfProfilerRef};
+use rustc_data_structures::profiling::{SelfProfiler, SelfProfilerRef, TimeReport};
 use rustc_data_structures::sync::{
     AtomicU64, DynSend, DynSync, Lock, Lrc, MappedReadGuard, ReadGuard, RwLock,
 };
 use rustc_errors::annotate_snippet_emitter_writer::AnnotateSnippetEmitter;
+use rustc_errors::apply_suggestions::SuggestionApplier;
 use rustc_errors::codes::*;
 use rustc_errors::emitter::{stderr_destination, DynEmitter, HumanEmitter, HumanReadableErrorType};
 use rustc_errors::json::JsonEmitter;
 use rustc_errors::registry::Registry;
+use rustc_errors::sarif::SarifEmitter;
 use rustc_errors::{
     fallback_fluent_bundle, Diag, DiagCtxt, DiagCtxtHandle, DiagMessage, Diagnostic,
     ErrorGuaranteed, FatalAbort, FluentBundle, LazyFallbackBundle, TerminalUrl,
@@ -40,6 +42,8 @@
     self, CoverageLevel, CrateType, ErrorOutputType, FunctionReturn, Input, InstrumentCoverage,
     OptLevel, OutFileName, OutputType, RemapPathScopeComponents, SwitchWithOptPath,
 };
+use crate::lint_baseline::{LintBaseline, LintBaselineFile};
+use crate::opt_remarks::OptRemarks;
 use crate::parse::{add_feature_diagnostics, ParseSess};
 use crate::search_paths::{PathKind, SearchPath};
 use crate::{errors, filesearch, lint};
@@ -158,6 +162,15 @@ pub struct Session {
     /// Data about code being compiled, gathered during compilation.
     pub code_stats: CodeStats,
 
+    /// The optimization remarks gathered during compilation, for `--emit=opt-remarks`.
+    pub opt_remarks: OptRemarks,
+
+    /// The lints that are allowed because of `--lint-baseline`.
+    lint_baseline: Option<Lock<LintBaseline>>,
+
+    /// The number of MIR optimization passes applied so far, for `-Z mir-opt-bisect-limit`.
+    pub mir_opt_bisect_count: AtomicUsize,
+
     /// Tracks fuel info if `-zfuel=crate=n` is specified.
     optimization_fuel: Lock<OptimizationFuel>,
 
@@ -286,6 +299,18 @@ pub fn finish_diagnostics(&self, registry: &Registry) -> Option<ErrorGuaranteed>
         let mut guar = None;
         guar = guar.or(self.check_miri_unleashed_features());
         guar = guar.or(self.dcx().emit_stashed_diagnostics());
+        if self.opts.unstable_opts.update_lint_baseline
+            && let Some(lint_baseline) = &self.lint_baseline
+            && let Some(LintBaselineFile { path, .. }) = &self.opts.lint_baseline
+            && let Err(err) = fs::write(path, lint_baseline.lock().to_file_contents())
+        {
+            guar = guar.or(Some(
+                self.dcx().emit_err(errors::FileWriteFail { path, err: err.to_string() }),
+            ));
+        }
+        if let Some(suggestion_applier) = self.dcx().take_suggestion_applier() {
+            guar = guar.or(self.apply_suggestions(&suggestion_applier));
+        }
         self.dcx().print_error_count(registry);
         if self.opts.json_future_incompat {
             self.dcx().emit_future_breakage_report();
@@ -293,6 +318,47 @@ pub fn finish_diagnostics(&self, registry: &Registry) -> Option<ErrorGuaranteed>
         guar
     }
 
+    /// Applies the suggestions collected for `-Z apply-suggestions` to the source files, or
+    /// writes them as a diff to the file given with `-Z apply-suggestions-dry-run`.
+    fn apply_suggestions(&self, suggestion_applier: &SuggestionApplier) -> Option<ErrorGuaranteed> {
+        let dry_run = self.opts.unstable_opts.apply_suggestions_dry_run.as_deref();
+        let mut diff = String::new();
+        let mut guar = None;
+        for path in suggestion_applier.paths() {
+            let Some(fixed) =
+                fs::read_to_string(path).ok().and_then(|src| suggestion_applier.fix(path, &src))
+            else {
+                self.dcx().emit_warn(errors::SuggestionsNotApplied { path });
+                continue;
+            };
+            if fixed.skipped > 0 {
+                self.dcx().emit_note(errors::OverlappingSuggestionsSkipped {
+                    path,
+                    count: fixed.skipped,
+                });
+            }
+            if fixed.applied == 0 {
+                continue;
+            }
+            if dry_run.is_some() {
+                diff.push_str(&fixed.diff);
+            } else if let Err(err) = fs::write(path, &fixed.fixed) {
+                guar = guar.or(Some(
+                    self.dcx().emit_err(errors::FileWriteFail { path, err: err.to_string() }),
+                ));
+            }
+        }
+        // The diff is written even if it is empty, so that it doesn't stay that of a previous run.
+        if let Some(path) = dry_run
+            && let Err(err) = fs::write(path, diff)
+        {
+            guar = guar.or(Some(
+                self.dcx().emit_err(errors::FileWriteFail { path, err: err.to_string() }),
+            ));
+        }
+        guar
+    }
+
     /// Returns true if the crate is a testing one.
     pub fn is_test_crate(&self) -> bool {
         self.opts.test
@@ -335,6 +401,16 @@ pub fn source_map(&self) -> &SourceMap {
         self.psess.source_map()
     }
 
+    /// Returns whether the `lint` diagnostic whose primary span is `span` is recorded in the
+    /// `--lint-baseline`, in which case the lint is allowed there. With
+    /// `-Z update-lint-baseline`, every lint diagnostic is recorded.
+    pub fn lint_baseline_covers(&self, lint: &'static lint::Lint, span: Option<Span>) -> bool {
+        let Some(lint_baseline) = &self.lint_baseline else {
+            return false;
+        };
+        lint_baseline.lock().covers(lint.name_lower(), span, self.source_map())
+    }
+
     /// Returns `true` if internal lints should be added to the lint store - i.e. if
     /// `-Zunstable-options` is provided and this isn't rustdoc (internal lints can trigger errors
     /// to be emitted under rustdoc).
@@ -995,7 +1071,18 @@ fn default_emitter(
             .diagnostic_width(sopts.diagnostic_width)
             .macro_backtrace(macro_backtrace)
             .track_diagnostics(track_diagnostics)
-            .terminal_url(terminal_url),
+            .terminal_url(terminal_url)
+            .diagnostic_version(sopts.unstable_opts.json_diagnostic_version),
+        ),
+        config::ErrorOutputType::Sarif => Box::new(
+            SarifEmitter::new(
+                Box::new(io::BufWriter::new(io::stderr())),
+                source_map,
+                fallback_bundle,
+            )
+            .registry(Some(registry))
+            .fluent_bundle(bundle)
+            .tool_version(option_env!("CFG_RELEASE").map(str::to_owned)),
         ),
     }
 }
@@ -1049,6 +1136,19 @@ pub fn build_session(
     if let Some(ice_file) = ice_file {
         dcx = dcx.with_ice_file(ice_file);
     }
+    if let Some(lints) = &sopts.unstable_opts.apply_suggestions {
+        dcx = dcx.with_suggestion_applier(SuggestionApplier::new(lints));
+    }
+
+    let lint_baseline = sopts.lint_baseline.as_ref().map(|file| match &file.contents {
+        Some(contents) => Lock::new(LintBaseline::parse(contents).unwrap_or_else(|err| {
+            early_dcx.early_fatal(format!(
+                "failed to read lint baseline `{}`: {err}",
+                file.path.display()
+            ))
+        })),
+        None => Lock::new(LintBaseline::recording()),
+    });
 
     // Now that the proper handler has been constructed, drop early_dcx to
     // prevent accidental use.
@@ -1099,6 +1199,7 @@ pub fn build_session(
     let prof = SelfProfilerRef::new(
         self_profiler,
         sopts.unstable_opts.time_passes.then(|| sopts.unstable_opts.time_passes_format),
+        sopts.unstable_opts.time_report.is_some().then(|| Arc::new(TimeReport::default())),
     );
 
     let ctfe_backtrace = Lock::new(match env::var("RUSTC_CTFE_BACKTRACE") {
@@ -1121,6 +1222,9 @@ pub fn build_session(
         incr_comp_session: RwLock::new(IncrCompSession::NotInitialized),
         prof,
         code_stats: Default::default(),
+        opt_remarks: Default::default(),
+        lint_baseline,
+        mir_opt_bisect_count: AtomicUsize::new(0),
         optimization_fuel,
         print_fuel,
         jobserver: jobserver::client(),
@@ -1439,6 +1543,15 @@ fn mk_emitter(output: ErrorOutputType) -> Box<DynEmitter> {
             pretty,
             json_rendered,
         )),
+        config::ErrorOutputType::Sarif => Box::new(
+            SarifEmitter::new(
+                Box::new(io::BufWriter::new(io::stderr())),
+                Lrc::new(SourceMap::new(FilePathMapping::empty())),
+                fallback_bundle,
+            )
+            // Most compilations emit no early diagnostic, and the session writes its own log.
+            .skip_empty_log(true),
+        ),
     };
     emitter
 }
@@ -5,12 +5,20 @@
 
 // Prefer importing stable_mir over internal rustc constructs to make this file more readable.
 
+use rustc_middle::bug;
 use rustc_middle::ty::{self as rustc_ty, Const as InternalConst, Ty as InternalTy, TyCtxt};
+use rustc_span::source_map::Spanned;
 use rustc_span::Symbol;
 use stable_mir::abi::Layout;
 use stable_mir::mir::alloc::AllocId;
 use stable_mir::mir::mono::{Instance, MonoItem, StaticDef};
-use stable_mir::mir::{BinOp, Mutability, Place, ProjectionElem, Safety, UnOp};
+use stable_mir::mir::{
+    AggregateKind, AssertMessage, BasicBlock, BinOp, BorrowKind, CastKind, ConstOperand,
+    CopyNonOverlapping, CoroutineDesugaring, CoroutineKind, CoroutineSource, FakeBorrowKind,
+    LocalDecl, MutBorrowKind, Mutability, NonDivergingIntrinsic, NullOp, Operand, Place,
+    PointerCoercion, ProjectionElem, RetagKind, Rvalue, Safety, Statement, StatementKind,
+    Terminator, TerminatorKind, UnOp, UnwindAction, VarDebugInfo, VarDebugInfoContents,
+};
 use stable_mir::ty::{
     Abi, AdtDef, Binder, BoundRegionKind, BoundTyKind, BoundVariableKind, ClosureKind, DynKind,
     ExistentialPredicate, ExistentialProjection, ExistentialTraitRef, FloatTy, FnSig,
@@ -592,6 +600,521 @@ fn internal<'tcx>(&self, _tables: &mut Tables<'_>, _tcx: TyCtxt<'tcx>) -> Self::
     }
 }
 
+impl RustcInternal for BasicBlock {
+    type T<'tcx> = rustc_middle::mir::BasicBlockData<'tcx>;
+
+    fn internal<'tcx>(&self, tables: &mut Tables<'_>, tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        // Stable MIR doesn't tell cleanup blocks apart, see `transform::internal_body`.
+        rustc_middle::mir::BasicBlockData {
+            statements: self.statements.internal(tables, tcx),
+            terminator: Some(self.terminator.internal(tables, tcx)),
+            is_cleanup: false,
+        }
+    }
+}
+
+impl RustcInternal for Statement {
+    type T<'tcx> = rustc_middle::mir::Statement<'tcx>;
+
+    fn internal<'tcx>(&self, tables: &mut Tables<'_>, tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        rustc_middle::mir::Statement {
+            source_info: rustc_middle::mir::SourceInfo::outermost(self.span.internal(tables, tcx)),
+            kind: self.kind.internal(tables, tcx),
+        }
+    }
+}
+
+impl RustcInternal for StatementKind {
+    type T<'tcx> = rustc_middle::mir::StatementKind<'tcx>;
+
+    fn internal<'tcx>(&self, tables: &mut Tables<'_>, tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        match self {
+            StatementKind::Assign(place, rvalue) => rustc_middle::mir::StatementKind::Assign(
+                Box::new((place.internal(tables, tcx), rvalue.internal(tables, tcx))),
+            ),
+            StatementKind::SetDiscriminant { place, variant_index } => {
+                rustc_middle::mir::StatementKind::SetDiscriminant {
+                    place: Box::new(place.internal(tables, tcx)),
+                    variant_index: variant_index.internal(tables, tcx),
+                }
+            }
+            StatementKind::Deinit(place) => {
+                rustc_middle::mir::StatementKind::Deinit(Box::new(place.internal(tables, tcx)))
+            }
+            StatementKind::StorageLive(local) => {
+                rustc_middle::mir::StatementKind::StorageLive((*local).into())
+            }
+            StatementKind::StorageDead(local) => {
+                rustc_middle::mir::StatementKind::StorageDead((*local).into())
+            }
+            StatementKind::Retag(kind, place) => rustc_middle::mir::StatementKind::Retag(
+                kind.internal(tables, tcx),
+                Box::new(place.internal(tables, tcx)),
+            ),
+            StatementKind::PlaceMention(place) => rustc_middle::mir::StatementKind::PlaceMention(
+                Box::new(place.internal(tables, tcx)),
+            ),
+            StatementKind::Intrinsic(intrinsic) => rustc_middle::mir::StatementKind::Intrinsic(
+                Box::new(intrinsic.internal(tables, tcx)),
+            ),
+            StatementKind::ConstEvalCounter => rustc_middle::mir::StatementKind::ConstEvalCounter,
+            // These have no effect after borrow checking.
+            StatementKind::FakeRead(..)
+            | StatementKind::AscribeUserType { .. }
+            | StatementKind::Nop => rustc_middle::mir::StatementKind::Nop,
+            // Rejected by `transform::optimized_mir`.
+            StatementKind::Coverage(_) => {
+                bug!("coverage statements are opaque in stable MIR")
+            }
+        }
+    }
+}
+
+impl RustcInternal for RetagKind {
+    type T<'tcx> = rustc_middle::mir::RetagKind;
+
+    fn internal<'tcx>(&self, _tables: &mut Tables<'_>, _tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        match self {
+            RetagKind::FnEntry => rustc_middle::mir::RetagKind::FnEntry,
+            RetagKind::TwoPhase => rustc_middle::mir::RetagKind::TwoPhase,
+            RetagKind::Raw => rustc_middle::mir::RetagKind::Raw,
+            RetagKind::Default => rustc_middle::mir::RetagKind::Default,
+        }
+    }
+}
+
+impl RustcInternal for NonDivergingIntrinsic {
+    type T<'tcx> = rustc_middle::mir::NonDivergingIntrinsic<'tcx>;
+
+    fn internal<'tcx>(&self, tables: &mut Tables<'_>, tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        match self {
+            NonDivergingIntrinsic::Assume(op) => {
+                rustc_middle::mir::NonDivergingIntrinsic::Assume(op.internal(tables, tcx))
+            }
+            NonDivergingIntrinsic::CopyNonOverlapping(CopyNonOverlapping { src, dst, count }) => {
+                rustc_middle::mir::NonDivergingIntrinsic::CopyNonOverlapping(
+                    rustc_middle::mir::CopyNonOverlapping {
+                        src: src.internal(tables, tcx),
+                        dst: dst.internal(tables, tcx),
+                        count: count.internal(tables, tcx),
+                    },
+                )
+            }
+        }
+    }
+}
+
+impl RustcInternal for Rvalue {
+    type T<'tcx> = rustc_middle::mir::Rvalue<'tcx>;
+
+    fn internal<'tcx>(&self, tables: &mut Tables<'_>, tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        match self {
+            Rvalue::AddressOf(mutability, place) => rustc_middle::mir::Rvalue::AddressOf(
+                mutability.internal(tables, tcx),
+                place.internal(tables, tcx),
+            ),
+            Rvalue::Aggregate(kind, operands) => rustc_middle::mir::Rvalue::Aggregate(
+                Box::new(kind.internal(tables, tcx)),
+                operands.iter().map(|op| op.internal(tables, tcx)).collect(),
+            ),
+            Rvalue::BinaryOp(bin_op, lhs, rhs) => rustc_middle::mir::Rvalue::BinaryOp(
+                bin_op.internal(tables, tcx),
+                Box::new((lhs.internal(tables, tcx), rhs.internal(tables, tcx))),
+            ),
+            Rvalue::CheckedBinaryOp(bin_op, lhs, rhs) => rustc_middle::mir::Rvalue::BinaryOp(
+                bin_op.internal(tables, tcx).wrapping_to_overflowing().unwrap(),
+                Box::new((lhs.internal(tables, tcx), rhs.internal(tables, tcx))),
+            ),
+            Rvalue::Cast(kind, op, ty) => rustc_middle::mir::Rvalue::Cast(
+                kind.internal(tables, tcx),
+                op.internal(tables, tcx),
+                ty.internal(tables, tcx),
+            ),
+            Rvalue::CopyForDeref(place) => {
+                rustc_middle::mir::Rvalue::CopyForDeref(place.internal(tables, tcx))
+            }
+            Rvalue::Discriminant(place) => {
+                rustc_middle::mir::Rvalue::Discriminant(place.internal(tables, tcx))
+            }
+            Rvalue::Len(place) => rustc_middle::mir::Rvalue::Len(place.internal(tables, tcx)),
+            Rvalue::Ref(region, kind, place) => rustc_middle::mir::Rvalue::Ref(
+                region.internal(tables, tcx),
+                kind.internal(tables, tcx),
+                place.internal(tables, tcx),
+            ),
+            Rvalue::Repeat(op, count) => rustc_middle::mir::Rvalue::Repeat(
+                op.internal(tables, tcx),
+                count.internal(tables, tcx),
+            ),
+            Rvalue::ShallowInitBox(op, ty) => rustc_middle::mir::Rvalue::ShallowInitBox(
+                op.internal(tables, tcx),
+                ty.internal(tables, tcx),
+            ),
+            Rvalue::ThreadLocalRef(item) => {
+                rustc_middle::mir::Rvalue::ThreadLocalRef(item.internal(tables, tcx))
+            }
+            Rvalue::NullaryOp(null_op, ty) => rustc_middle::mir::Rvalue::NullaryOp(
+                null_op.internal(tables, tcx),
+                ty.internal(tables, tcx),
+            ),
+            Rvalue::UnaryOp(un_op, op) => rustc_middle::mir::Rvalue::UnaryOp(
+                un_op.internal(tables, tcx),
+                op.internal(tables, tcx),
+            ),
+            Rvalue::Use(op) => rustc_middle::mir::Rvalue::Use(op.internal(tables, tcx)),
+        }
+    }
+}
+
+impl RustcInternal for AggregateKind {
+    type T<'tcx> = rustc_middle::mir::AggregateKind<'tcx>;
+
+    fn internal<'tcx>(&self, tables: &mut Tables<'_>, tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        match self {
+            AggregateKind::Array(ty) => {
+                rustc_middle::mir::AggregateKind::Array(ty.internal(tables, tcx))
+            }
+            AggregateKind::Tuple => rustc_middle::mir::AggregateKind::Tuple,
+            AggregateKind::Adt(def, variant, args, user_ty, field) => {
+                rustc_middle::mir::AggregateKind::Adt(
+                    def.0.internal(tables, tcx),
+                    variant.internal(tables, tcx),
+                    args.internal(tables, tcx),
+                    user_ty.map(rustc_middle::ty::UserTypeAnnotationIndex::from_usize),
+                    field.map(rustc_target::abi::FieldIdx::from_usize),
+                )
+            }
+            AggregateKind::Closure(def, args) => rustc_middle::mir::AggregateKind::Closure(
+                def.0.internal(tables, tcx),
+                args.internal(tables, tcx),
+            ),
+            AggregateKind::Coroutine(def, args, _movability) => {
+                rustc_middle::mir::AggregateKind::Coroutine(
+                    def.0.internal(tables, tcx),
+                    args.internal(tables, tcx),
+                )
+            }
+            AggregateKind::RawPtr(ty, mutability) => rustc_middle::mir::AggregateKind::RawPtr(
+                ty.internal(tables, tcx),
+                mutability.internal(tables, tcx),
+            ),
+        }
+    }
+}
+
+impl RustcInternal for CastKind {
+    type T<'tcx> = rustc_middle::mir::CastKind;
+
+    fn internal<'tcx>(&self, tables: &mut Tables<'_>, tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        use rustc_middle::mir::CastKind as InternalCastKind;
+        match self {
+            CastKind::PointerExposeAddress => InternalCastKind::PointerExposeProvenance,
+            CastKind::PointerWithExposedProvenance => {
+                InternalCastKind::PointerWithExposedProvenance
+            }
+            CastKind::PointerCoercion(coercion) => {
+                InternalCastKind::PointerCoercion(coercion.internal(tables, tcx))
+            }
+            CastKind::DynStar => InternalCastKind::DynStar,
+            CastKind::IntToInt => InternalCastKind::IntToInt,
+            CastKind::FloatToInt => InternalCastKind::FloatToInt,
+            CastKind::FloatToFloat => InternalCastKind::FloatToFloat,
+            CastKind::IntToFloat => InternalCastKind::IntToFloat,
+            CastKind::PtrToPtr => InternalCastKind::PtrToPtr,
+            CastKind::FnPtrToPtr => InternalCastKind::FnPtrToPtr,
+            CastKind::Transmute => InternalCastKind::Transmute,
+        }
+    }
+}
+
+impl RustcInternal for PointerCoercion {
+    type T<'tcx> = rustc_middle::ty::adjustment::PointerCoercion;
+
+    fn internal<'tcx>(&self, tables: &mut Tables<'_>, tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        use rustc_middle::ty::adjustment::PointerCoercion as InternalCoercion;
+        match self {
+            PointerCoercion::ReifyFnPointer => InternalCoercion::ReifyFnPointer,
+            PointerCoercion::UnsafeFnPointer => InternalCoercion::UnsafeFnPointer,
+            PointerCoercion::ClosureFnPointer(safety) => {
+                InternalCoercion::ClosureFnPointer(safety.internal(tables, tcx))
+            }
+            PointerCoercion::MutToConstPointer => InternalCoercion::MutToConstPointer,
+            PointerCoercion::ArrayToPointer => InternalCoercion::ArrayToPointer,
+            PointerCoercion::Unsize => InternalCoercion::Unsize,
+        }
+    }
+}
+
+impl RustcInternal for NullOp {
+    type T<'tcx> = rustc_middle::mir::NullOp<'tcx>;
+
+    fn internal<'tcx>(&self, tables: &mut Tables<'_>, tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        match self {
+            NullOp::SizeOf => rustc_middle::mir::NullOp::SizeOf,
+            NullOp::AlignOf => rustc_middle::mir::NullOp::AlignOf,
+            NullOp::OffsetOf(indices) => rustc_middle::mir::NullOp::OffsetOf(
+                tcx.mk_offset_of_from_iter(indices.iter().map(|(variant, field)| {
+                    (variant.internal(tables, tcx), rustc_target::abi::FieldIdx::from_usize(*field))
+                })),
+            ),
+            NullOp::UbChecks => rustc_middle::mir::NullOp::UbChecks,
+        }
+    }
+}
+
+impl RustcInternal for BorrowKind {
+    type T<'tcx> = rustc_middle::mir::BorrowKind;
+
+    fn internal<'tcx>(&self, tables: &mut Tables<'_>, tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        match self {
+            BorrowKind::Shared => rustc_middle::mir::BorrowKind::Shared,
+            BorrowKind::Fake(kind) => {
+                rustc_middle::mir::BorrowKind::Fake(kind.internal(tables, tcx))
+            }
+            BorrowKind::Mut { kind } => {
+                rustc_middle::mir::BorrowKind::Mut { kind: kind.internal(tables, tcx) }
+            }
+        }
+    }
+}
+
+impl RustcInternal for MutBorrowKind {
+    type T<'tcx> = rustc_middle::mir::MutBorrowKind;
+
+    fn internal<'tcx>(&self, _tables: &mut Tables<'_>, _tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        match self {
+            MutBorrowKind::Default => rustc_middle::mir::MutBorrowKind::Default,
+            MutBorrowKind::TwoPhaseBorrow => rustc_middle::mir::MutBorrowKind::TwoPhaseBorrow,
+            MutBorrowKind::ClosureCapture => rustc_middle::mir::MutBorrowKind::ClosureCapture,
+        }
+    }
+}
+
+impl RustcInternal for FakeBorrowKind {
+    type T<'tcx> = rustc_middle::mir::FakeBorrowKind;
+
+    fn internal<'tcx>(&self, _tables: &mut Tables<'_>, _tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        match self {
+            FakeBorrowKind::Deep => rustc_middle::mir::FakeBorrowKind::Deep,
+            FakeBorrowKind::Shallow => rustc_middle::mir::FakeBorrowKind::Shallow,
+        }
+    }
+}
+
+impl RustcInternal for Operand {
+    type T<'tcx> = rustc_middle::mir::Operand<'tcx>;
+
+    fn internal<'tcx>(&self, tables: &mut Tables<'_>, tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        match self {
+            Operand::Copy(place) => rustc_middle::mir::Operand::Copy(place.internal(tables, tcx)),
+            Operand::Move(place) => rustc_middle::mir::Operand::Move(place.internal(tables, tcx)),
+            Operand::Constant(constant) => {
+                rustc_middle::mir::Operand::Constant(Box::new(constant.internal(tables, tcx)))
+            }
+        }
+    }
+}
+
+impl RustcInternal for ConstOperand {
+    type T<'tcx> = rustc_middle::mir::ConstOperand<'tcx>;
+
+    fn internal<'tcx>(&self, tables: &mut Tables<'_>, tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        rustc_middle::mir::ConstOperand {
+            span: self.span.internal(tables, tcx),
+            user_ty: self.user_ty.map(rustc_middle::ty::UserTypeAnnotationIndex::from_usize),
+            const_: self.const_.internal(tables, tcx),
+        }
+    }
+}
+
+impl RustcInternal for Terminator {
+    type T<'tcx> = rustc_middle::mir::Terminator<'tcx>;
+
+    fn internal<'tcx>(&self, tables: &mut Tables<'_>, tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        let span = self.span.internal(tables, tcx);
+        let kind = match &self.kind {
+            TerminatorKind::Goto { target } => {
+                rustc_middle::mir::TerminatorKind::Goto { target: (*target).into() }
+            }
+            TerminatorKind::SwitchInt { discr, targets } => {
+                rustc_middle::mir::TerminatorKind::SwitchInt {
+                    discr: discr.internal(tables, tcx),
+                    targets: rustc_middle::mir::SwitchTargets::new(
+                        targets.branches().map(|(value, target)| (value, target.into())),
+                        targets.otherwise().into(),
+                    ),
+                }
+            }
+            TerminatorKind::Resume => rustc_middle::mir::TerminatorKind::UnwindResume,
+            TerminatorKind::Abort => rustc_middle::mir::TerminatorKind::UnwindTerminate(
+                rustc_middle::mir::UnwindTerminateReason::Abi,
+            ),
+            TerminatorKind::Return => rustc_middle::mir::TerminatorKind::Return,
+            TerminatorKind::Unreachable => rustc_middle::mir::TerminatorKind::Unreachable,
+            TerminatorKind::Drop { place, target, unwind } => {
+                rustc_middle::mir::TerminatorKind::Drop {
+                    place: place.internal(tables, tcx),
+                    target: (*target).into(),
+                    unwind: unwind.internal(tables, tcx),
+                    replace: false,
+                }
+            }
+            TerminatorKind::Call { func, args, destination, target, unwind } => {
+                rustc_middle::mir::TerminatorKind::Call {
+                    func: func.internal(tables, tcx),
+                    args: args
+                        .iter()
+                        .map(|arg| Spanned { node: arg.internal(tables, tcx), span })
+                        .collect(),
+                    destination: destination.internal(tables, tcx),
+                    target: target.map(Into::into),
+                    unwind: unwind.internal(tables, tcx),
+                    call_source: rustc_middle::mir::CallSource::Misc,
+                    fn_span: span,
+                }
+            }
+            TerminatorKind::Assert { cond, expected, msg, target, unwind } => {
+                rustc_middle::mir::TerminatorKind::Assert {
+                    cond: cond.internal(tables, tcx),
+                    expected: *expected,
+                    msg: Box::new(msg.internal(tables, tcx)),
+                    target: (*target).into(),
+                    unwind: unwind.internal(tables, tcx),
+                }
+            }
+            // Rejected by `transform::optimized_mir`.
+            TerminatorKind::InlineAsm { .. } => {
+                bug!("stable MIR only has a textual representation of inline assembly")
+            }
+        };
+        rustc_middle::mir::Terminator {
+            source_info: rustc_middle::mir::SourceInfo::outermost(span),
+            kind,
+        }
+    }
+}
+
+impl RustcInternal for UnwindAction {
+    type T<'tcx> = rustc_middle::mir::UnwindAction;
+
+    fn internal<'tcx>(&self, _tables: &mut Tables<'_>, _tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        match self {
+            UnwindAction::Continue => rustc_middle::mir::UnwindAction::Continue,
+            UnwindAction::Unreachable => rustc_middle::mir::UnwindAction::Unreachable,
+            UnwindAction::Terminate => rustc_middle::mir::UnwindAction::Terminate(
+                rustc_middle::mir::UnwindTerminateReason::Abi,
+            ),
+            UnwindAction::Cleanup(target) => {
+                rustc_middle::mir::UnwindAction::Cleanup((*target).into())
+            }
+        }
+    }
+}
+
+impl RustcInternal for AssertMessage {
+    type T<'tcx> = rustc_middle::mir::AssertMessage<'tcx>;
+
+    fn internal<'tcx>(&self, tables: &mut Tables<'_>, tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        use rustc_middle::mir::AssertKind;
+        match self {
+            AssertMessage::BoundsCheck { len, index } => AssertKind::BoundsCheck {
+                len: len.internal(tables, tcx),
+                index: index.internal(tables, tcx),
+            },
+            AssertMessage::Overflow(bin_op, lhs, rhs) => AssertKind::Overflow(
+                bin_op.internal(tables, tcx),
+                lhs.internal(tables, tcx),
+                rhs.internal(tables, tcx),
+            ),
+            AssertMessage::OverflowNeg(op) => AssertKind::OverflowNeg(op.internal(tables, tcx)),
+            AssertMessage::DivisionByZero(op) => {
+                AssertKind::DivisionByZero(op.internal(tables, tcx))
+            }
+            AssertMessage::RemainderByZero(op) => {
+                AssertKind::RemainderByZero(op.internal(tables, tcx))
+            }
+            AssertMessage::ResumedAfterReturn(coroutine) => {
+                AssertKind::ResumedAfterReturn(coroutine.internal(tables, tcx))
+            }
+            AssertMessage::ResumedAfterPanic(coroutine) => {
+                AssertKind::ResumedAfterPanic(coroutine.internal(tables, tcx))
+            }
+            AssertMessage::MisalignedPointerDereference { required, found } => {
+                AssertKind::MisalignedPointerDereference {
+                    required: required.internal(tables, tcx),
+                    found: found.internal(tables, tcx),
+                }
+            }
+        }
+    }
+}
+
+impl RustcInternal for CoroutineKind {
+    type T<'tcx> = rustc_hir::CoroutineKind;
+
+    fn internal<'tcx>(&self, tables: &mut Tables<'_>, tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        match self {
+            CoroutineKind::Desugared(desugaring, source) => rustc_hir::CoroutineKind::Desugared(
+                match desugaring {
+                    CoroutineDesugaring::Async => rustc_hir::CoroutineDesugaring::Async,
+                    CoroutineDesugaring::Gen => rustc_hir::CoroutineDesugaring::Gen,
+                    CoroutineDesugaring::AsyncGen => rustc_hir::CoroutineDesugaring::AsyncGen,
+                },
+                match source {
+                    CoroutineSource::Block => rustc_hir::CoroutineSource::Block,
+                    CoroutineSource::Closure => rustc_hir::CoroutineSource::Closure,
+                    CoroutineSource::Fn => rustc_hir::CoroutineSource::Fn,
+                },
+            ),
+            CoroutineKind::Coroutine(movability) => {
+                rustc_hir::CoroutineKind::Coroutine(movability.internal(tables, tcx))
+            }
+        }
+    }
+}
+
+impl RustcInternal for LocalDecl {
+    type T<'tcx> = rustc_middle::mir::LocalDecl<'tcx>;
+
+    fn internal<'tcx>(&self, tables: &mut Tables<'_>, tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        let mut decl = rustc_middle::mir::LocalDecl::new(
+            self.ty.internal(tables, tcx),
+            self.span.internal(tables, tcx),
+        );
+        decl.mutability = self.mutability.internal(tables, tcx);
+        decl
+    }
+}
+
+impl RustcInternal for VarDebugInfo {
+    type T<'tcx> = rustc_middle::mir::VarDebugInfo<'tcx>;
+
+    fn internal<'tcx>(&self, tables: &mut Tables<'_>, tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        rustc_middle::mir::VarDebugInfo {
+            name: Symbol::intern(&self.name),
+            source_info: rustc_middle::mir::SourceInfo {
+                span: self.source_info.span.internal(tables, tcx),
+                scope: rustc_middle::mir::SourceScope::from_u32(self.source_info.scope),
+            },
+            composite: self.composite.as_ref().map(|composite| {
+                Box::new(rustc_middle::mir::VarDebugInfoFragment {
+                    ty: composite.ty.internal(tables, tcx),
+                    projection: composite.projection.internal(tables, tcx),
+                })
+            }),
+            value: match &self.value {
+                VarDebugInfoContents::Place(place) => {
+                    rustc_middle::mir::VarDebugInfoContents::Place(place.internal(tables, tcx))
+                }
+                VarDebugInfoContents::Const(constant) => {
+                    rustc_middle::mir::VarDebugInfoContents::Const(constant.internal(tables, tcx))
+                }
+            },
+            argument_index: self.argument_index,
+        }
+    }
+}
+
 impl<T> RustcInternal for &T
 where
     T: RustcInternal,
@@ -24,7 +24,9 @@
 use crate::rustc_smir::{Stable, Tables};
 
 mod internal;
+pub mod plugin;
 pub mod pretty;
+pub mod transform;
 
 /// Convert an internal Rust compiler item into its stable counterpart, if one exists.
 ///
@@ -209,7 +211,25 @@ pub fn run<F, T>(tcx: TyCtxt<'_>, f: F) -> Result<T, Error>
 where
     F: FnOnce() -> T,
 {
-    let tables = TablesWrapper(RefCell::new(Tables {
+    let tables = new_tables(tcx);
+    stable_mir::compiler_interface::run(&tables, || init(&tables, f))
+}
+
+/// Like [run], but when StableMIR is already running, `f` runs in a context of its own, whose
+/// items can't be mixed with those of the current context.
+pub(crate) fn run_nested<F, T>(tcx: TyCtxt<'_>, f: F) -> T
+where
+    F: FnOnce() -> T,
+{
+    let tables = new_tables(tcx);
+    stable_mir::compiler_interface::run_nested(&tables, || {
+        let ptr = &tables as *const _ as *const ();
+        TLV.set(&Cell::new(ptr), f)
+    })
+}
+
+fn new_tables(tcx: TyCtxt<'_>) -> TablesWrapper<'_> {
+    TablesWrapper(RefCell::new(Tables {
         tcx,
         def_ids: IndexMap::default(),
         alloc_ids: IndexMap::default(),
@@ -219,13 +239,15 @@ pub fn run<F, T>(tcx: TyCtxt<'_>, f: F) -> Result<T, Error>
         ty_consts: IndexMap::default(),
         mir_consts: IndexMap::default(),
         layouts: IndexMap::default(),
-    }));
-    stable_mir::compiler_interface::run(&tables, || init(&tables, f))
+    }))
 }
 
 /// Instantiate and run the compiler with the provided arguments and callback.
 ///
 /// The callback will be invoked after the compiler ran all its analyses, but before code generation.
+/// The MIR transformations registered with [transform::register] are applied to the functions of
+/// the crate before code generation too.
+///
 /// Note that this macro accepts two different formats for the callback:
 /// 1. An ident that resolves to a function that accepts no argument and returns `ControlFlow<B, C>`
 /// ```ignore(needs-extern-crate)
@@ -368,6 +390,12 @@ impl<B, C, F> Callbacks for StableMir<B, C, F>
             C: Send,
             F: FnOnce($(optional!($with_tcx TyCtxt))?) -> ControlFlow<B, C> + Send,
         {
+            /// Applies the MIR transformations registered with
+            /// `rustc_internal::transform::register` to the functions of the crate.
+            fn config(&mut self, config: &mut interface::Config) {
+                config.override_queries = Some(rustc_internal::transform::override_queries);
+            }
+
             /// Called after analysis. Return value instructs the compiler whether to
             /// continue the compilation afterwards (defaults to `Compilation::Continue`)
             fn after_analysis<'tcx>(
@@ -397,6 +425,119 @@ fn after_analysis<'tcx>(
     }};
 }
 
+/// Instantiate and run the compiler with the provided arguments, calling back the given
+/// [stable_mir::plugin::Plugin] at the phases of the compilation.
+///
+/// The compilation goes on to code generation unless a callback of the plugin breaks. The MIR
+/// transformations registered with [transform::register] are applied like with [run!].
+///
+/// ```ignore(needs-extern-crate)
+/// # extern crate rustc_driver;
+/// # extern crate rustc_interface;
+/// # #[macro_use]
+/// # extern crate rustc_smir;
+/// # extern crate stable_mir;
+/// #
+/// # fn main() {
+/// #   use std::ops::ControlFlow;
+/// #   use stable_mir::CompilerError;
+///     use stable_mir::diagnostic::Diagnostic;
+///     use stable_mir::plugin::Plugin;
+///     use stable_mir::CrateDef;
+///
+///     struct NoFunctionsNamedFoo;
+///
+///     impl Plugin for NoFunctionsNamedFoo {
+///         fn after_type_checking(&mut self) -> ControlFlow<()> {
+///             for item in stable_mir::all_local_items() {
+///                 if item.name() == "foo" {
+///                     Diagnostic::warning("function named `foo`").with_span(item.span()).emit();
+///                 }
+///             }
+///             ControlFlow::Continue(())
+///         }
+///     }
+/// #   let args = vec!["--verbose".to_string()];
+///     let result = run_plugin!(args, NoFunctionsNamedFoo);
+/// #   assert_eq!(result, Err(CompilerError::Skipped))
+/// # }
+/// ```
+#[macro_export]
+macro_rules! run_plugin {
+    ($args:expr, $plugin:expr) => {{
+        use rustc_driver::{Callbacks, Compilation, RunCompiler};
+        use rustc_interface::{interface, Queries};
+        use rustc_smir::rustc_internal::plugin::{run_phase, Phase};
+        use stable_mir::plugin::Plugin;
+        use stable_mir::CompilerError;
+        use std::ops::ControlFlow;
+
+        pub struct PluginDriver<P: Plugin + Send> {
+            plugin: P,
+            result: Option<ControlFlow<()>>,
+        }
+
+        impl<P: Plugin + Send> PluginDriver<P> {
+            /// Runs the compiler with the given arguments, calling back the plugin.
+            pub fn run(&mut self, args: &[String]) -> Result<(), CompilerError<()>> {
+                let compiler_result =
+                    rustc_driver::catch_fatal_errors(|| RunCompiler::new(args, self).run());
+                match (compiler_result, self.result.take()) {
+                    (Ok(Ok(())), Some(ControlFlow::Continue(()))) => Ok(()),
+                    (Ok(Ok(())), Some(ControlFlow::Break(()))) => {
+                        Err(CompilerError::Interrupted(()))
+                    }
+                    (Ok(Ok(_)), None) => Err(CompilerError::Skipped),
+                    (Ok(Err(_)), _) | (Err(_), _) => Err(CompilerError::Failed),
+                }
+            }
+
+            fn run_phases<'tcx>(
+                &mut self,
+                queries: &'tcx Queries<'tcx>,
+                phases: &[Phase],
+            ) -> Compilation {
+                queries.global_ctxt().unwrap().enter(|tcx| {
+                    for &phase in phases {
+                        let result = run_phase(tcx, &mut self.plugin, phase);
+                        self.result = Some(result);
+                        if result.is_break() {
+                            return Compilation::Stop;
+                        }
+                    }
+                    Compilation::Continue
+                })
+            }
+        }
+
+        impl<P: Plugin + Send> Callbacks for PluginDriver<P> {
+            fn config(&mut self, config: &mut interface::Config) {
+                config.override_queries =
+                    Some(rustc_smir::rustc_internal::transform::override_queries);
+            }
+
+            fn after_expansion<'tcx>(
+                &mut self,
+                _compiler: &interface::Compiler,
+                queries: &'tcx Queries<'tcx>,
+            ) -> Compilation {
+                self.run_phases(queries, &[Phase::AfterParsing])
+            }
+
+            fn after_analysis<'tcx>(
+                &mut self,
+                _compiler: &interface::Compiler,
+                queries: &'tcx Queries<'tcx>,
+            ) -> Compilation {
+                self.run_phases(queries, &[Phase::AfterTypeChecking, Phase::AfterMirOptimization])
+            }
+        }
+
+        let args: Vec<String> = $args;
+        PluginDriver { plugin: $plugin, result: None }.run(&args)
+    }};
+}
+
 /// Simmilar to rustc's `FxIndexMap`, `IndexMap` with extra
 /// safety features added.
 pub struct IndexMap<K, V> {
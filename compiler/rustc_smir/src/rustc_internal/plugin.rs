@@ -0,0 +1,38 @@
+//! Running the callbacks of a [Plugin] at the phases of the compilation, for the drivers
+//! instantiated by [run_plugin!](crate::run_plugin!).
+
+use std::ops::ControlFlow;
+
+use rustc_middle::ty::TyCtxt;
+use stable_mir::plugin::Plugin;
+
+use super::run;
+
+/// A phase of the compilation at which the compiler calls back a plugin.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Phase {
+    AfterParsing,
+    AfterTypeChecking,
+    AfterMirOptimization,
+}
+
+/// Calls back `plugin` for `phase` in a StableMIR context, which the compiler must have reached.
+pub fn run_phase<P: Plugin + ?Sized>(
+    tcx: TyCtxt<'_>,
+    plugin: &mut P,
+    phase: Phase,
+) -> ControlFlow<()> {
+    if phase == Phase::AfterMirOptimization {
+        for &def_id in tcx.mir_keys(()) {
+            if tcx.def_kind(def_id).is_fn_like() {
+                tcx.ensure().optimized_mir(def_id);
+            }
+        }
+    }
+    run(tcx, || match phase {
+        Phase::AfterParsing => plugin.after_parsing(),
+        Phase::AfterTypeChecking => plugin.after_type_checking(),
+        Phase::AfterMirOptimization => plugin.after_mir_optimization(),
+    })
+    .unwrap()
+}
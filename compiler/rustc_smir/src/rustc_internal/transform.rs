@@ -0,0 +1,262 @@
+//! Transformations of the MIR of the local crate written against StableMIR, which the compiler
+//! applies before codegen, like to instrument functions with tracing probes.
+//!
+//! A transformation is registered with [register] before running the compiler, and applied to the
+//! optimized MIR of each function of the crate, which it gets as a stable [Body]. The body is then
+//! converted back to the MIR of the compiler, keeping what stable MIR doesn't have of the original
+//! body, like its source scopes, for the statements and terminators which keep their spans.
+
+use std::sync::{Mutex, OnceLock};
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_index::IndexVec;
+use rustc_middle::mir::visit::Visitor;
+use rustc_middle::mir::{self, traversal};
+use rustc_middle::ty::TyCtxt;
+use rustc_middle::util::Providers;
+use rustc_session::Session;
+use rustc_span::def_id::LocalDefId;
+use stable_mir::mir::{Body, StatementKind, TerminatorKind};
+use stable_mir::ty::Span;
+use stable_mir::CrateItem;
+
+use super::{run_nested, with_tables, RustcInternal};
+use crate::rustc_smir::{Stable, Tables};
+
+/// A transformation of the MIR of a function of the local crate.
+pub type MirTransform = fn(CrateItem, &mut Body);
+
+static TRANSFORMS: Mutex<Vec<(&'static str, MirTransform)>> = Mutex::new(Vec::new());
+
+static DEFAULT_OPTIMIZED_MIR: OnceLock<fn(TyCtxt<'_>, LocalDefId) -> &mir::Body<'_>> =
+    OnceLock::new();
+
+/// Registers a transformation of the MIR of the functions of the local crate under `name`, which
+/// the compiler applies to their optimized MIR, in the order of registration.
+///
+/// The transformations are applied by the compilers run with [run!](crate::run!), or by custom
+/// drivers which install [override_queries]. They must be registered before the compiler runs,
+/// and run in a StableMIR context of their own, so they can't use the items of other contexts.
+///
+/// The functions with inline assembly, or with the coverage instrumentation of
+/// `-C instrument-coverage`, are not transformed, since stable MIR doesn't represent them
+/// completely. A transformation can't change the arguments of a function, nor add such
+/// constructs to it: the compiler reports an error naming the transformation if it does.
+pub fn register(name: &'static str, transform: MirTransform) {
+    TRANSFORMS.lock().unwrap().push((name, transform));
+}
+
+/// Overrides the queries of the compiler to apply the registered MIR transformations. This is the
+/// signature of `rustc_interface::Config::override_queries`.
+pub fn override_queries(_: &Session, providers: &mut Providers) {
+    let _ = DEFAULT_OPTIMIZED_MIR.set(providers.optimized_mir);
+    providers.optimized_mir = optimized_mir;
+}
+
+fn optimized_mir(tcx: TyCtxt<'_>, def: LocalDefId) -> &mir::Body<'_> {
+    let body = DEFAULT_OPTIMIZED_MIR.get().unwrap()(tcx, def);
+    let transforms = TRANSFORMS.lock().unwrap().clone();
+    if transforms.is_empty() || !is_transformable(body) {
+        return body;
+    }
+
+    // The query may be computed for a StableMIR tool, while StableMIR is running.
+    let transformed = run_nested(tcx, || {
+        let (item, mut stable_body) =
+            with_tables(|tables| (tables.crate_item(def.to_def_id()), body.stable(tables)));
+        for (name, transform) in transforms {
+            transform(item, &mut stable_body);
+            if let Some((span, construct)) = unsupported_construct(body, &stable_body) {
+                let span = with_tables(|tables| span.internal(tables, tcx));
+                tcx.dcx().span_err(
+                    span,
+                    format!("the MIR transformation `{name}` {construct}, which is not supported"),
+                );
+                return None;
+            }
+        }
+        Some(with_tables(|tables| internal_body(tables, tcx, body, &stable_body)))
+    });
+    match transformed {
+        Some(transformed) => tcx.arena.alloc(transformed),
+        None => body,
+    }
+}
+
+/// Returns whether `body` can be converted to stable MIR and back.
+fn is_transformable(body: &mir::Body<'_>) -> bool {
+    body.basic_blocks.iter().all(|data| {
+        let statements = data.statements.iter().all(|statement| match &statement.kind {
+            mir::StatementKind::Coverage(_) => false,
+            mir::StatementKind::Assign(assign) => !matches!(
+                assign.1,
+                mir::Rvalue::Aggregate(ref kind, _)
+                    if matches!(**kind, mir::AggregateKind::CoroutineClosure(..))
+            ),
+            _ => true,
+        });
+        let terminator = !matches!(
+            data.terminator().kind,
+            mir::TerminatorKind::InlineAsm { .. } | mir::TerminatorKind::TailCall { .. }
+        );
+        statements && terminator
+    })
+}
+
+/// Returns the span and a description of the first change made to the `original` body by its
+/// transformation to `body` which can't be converted back to the MIR of the compiler, if any.
+fn unsupported_construct(original: &mir::Body<'_>, body: &Body) -> Option<(Span, &'static str)> {
+    if body.arg_locals().len() != original.arg_count {
+        return Some((body.span, "changed the arguments of the function"));
+    }
+    body.blocks.iter().find_map(|block| {
+        let statement = block.statements.iter().find_map(|statement| match statement.kind {
+            StatementKind::Coverage(_) => Some((statement.span, "added a coverage statement")),
+            _ => None,
+        });
+        statement.or_else(|| match block.terminator.kind {
+            TerminatorKind::InlineAsm { .. } => {
+                Some((block.terminator.span, "added inline assembly"))
+            }
+            _ => None,
+        })
+    })
+}
+
+/// Converts the transformed `body` back to the MIR of the compiler, taking what stable MIR doesn't
+/// have from the `original` body.
+fn internal_body<'tcx>(
+    tables: &mut Tables<'_>,
+    tcx: TyCtxt<'tcx>,
+    original: &mir::Body<'tcx>,
+    body: &Body,
+) -> mir::Body<'tcx> {
+    // The statements and terminators are identified by their spans.
+    let mut scopes = FxHashMap::default();
+    let mut terminators = FxHashMap::default();
+    for data in original.basic_blocks.iter() {
+        for statement in &data.statements {
+            scopes.entry(statement.source_info.span).or_insert(statement.source_info.scope);
+        }
+        let terminator = data.terminator();
+        scopes.entry(terminator.source_info.span).or_insert(terminator.source_info.scope);
+        terminators.entry(terminator.source_info.span).or_insert(&terminator.kind);
+    }
+
+    let mut blocks: IndexVec<mir::BasicBlock, _> =
+        body.blocks.iter().map(|block| block.internal(tables, tcx)).collect();
+    mark_cleanup_blocks(&mut blocks);
+    for data in blocks.iter_mut() {
+        for statement in &mut data.statements {
+            if let Some(&scope) = scopes.get(&statement.source_info.span) {
+                statement.source_info.scope = scope;
+            }
+        }
+        let is_cleanup = data.is_cleanup;
+        let terminator = data.terminator_mut();
+        let span = terminator.source_info.span;
+        if let Some(&scope) = scopes.get(&span) {
+            terminator.source_info.scope = scope;
+        }
+        restore_terminator(&mut terminator.kind, terminators.get(&span).copied(), is_cleanup);
+    }
+
+    let mut transformed = original.clone();
+    *transformed.basic_blocks_mut() = blocks;
+    transformed.local_decls = body
+        .local_decls()
+        .map(|(local, decl)| match original.local_decls.get(mir::Local::from_usize(local)) {
+            // Keep what stable MIR doesn't have of the original locals.
+            Some(original_decl) => mir::LocalDecl {
+                ty: decl.ty.internal(tables, tcx),
+                mutability: decl.mutability.internal(tables, tcx),
+                source_info: mir::SourceInfo {
+                    span: decl.span.internal(tables, tcx),
+                    ..original_decl.source_info
+                },
+                ..original_decl.clone()
+            },
+            None => decl.internal(tables, tcx),
+        })
+        .collect();
+    transformed.var_debug_info = body.var_debug_info.internal(tables, tcx);
+
+    let mut required_consts = RequiredConsts(Vec::new());
+    for (block, data) in traversal::reverse_postorder(&transformed) {
+        required_consts.visit_basic_block_data(block, data);
+    }
+    transformed.required_consts = Some(required_consts.0);
+    transformed
+}
+
+/// Marks the blocks reached by unwinding as cleanup blocks, which stable MIR doesn't tell apart.
+fn mark_cleanup_blocks(blocks: &mut IndexVec<mir::BasicBlock, mir::BasicBlockData<'_>>) {
+    let mut cleanup: Vec<_> = blocks
+        .iter()
+        .filter_map(|data| match data.terminator().unwind() {
+            Some(&mir::UnwindAction::Cleanup(target)) => Some(target),
+            _ => None,
+        })
+        .collect();
+    while let Some(block) = cleanup.pop() {
+        if !blocks[block].is_cleanup {
+            blocks[block].is_cleanup = true;
+            cleanup.extend(blocks[block].terminator().successors());
+        }
+    }
+}
+
+/// Restores what stable MIR doesn't have of a terminator from the `original` terminator with the
+/// same span, if any.
+fn restore_terminator<'tcx>(
+    kind: &mut mir::TerminatorKind<'tcx>,
+    original: Option<&mir::TerminatorKind<'tcx>>,
+    is_cleanup: bool,
+) {
+    match (&mut *kind, original) {
+        (
+            mir::TerminatorKind::Call { args, call_source, fn_span, .. },
+            Some(mir::TerminatorKind::Call {
+                args: original_args,
+                call_source: original_call_source,
+                fn_span: original_fn_span,
+                ..
+            }),
+        ) => {
+            *call_source = *original_call_source;
+            *fn_span = *original_fn_span;
+            if args.len() == original_args.len() {
+                for (arg, original_arg) in args.iter_mut().zip(original_args) {
+                    arg.span = original_arg.span;
+                }
+            }
+        }
+        (
+            mir::TerminatorKind::UnwindTerminate(reason),
+            Some(mir::TerminatorKind::UnwindTerminate(original_reason)),
+        ) => *reason = *original_reason,
+        (
+            mir::TerminatorKind::Drop { replace, .. },
+            Some(mir::TerminatorKind::Drop { replace: original_replace, .. }),
+        ) => *replace = *original_replace,
+        _ => {}
+    }
+
+    if let Some(mir::UnwindAction::Terminate(reason)) = kind.unwind_mut() {
+        *reason = if is_cleanup {
+            mir::UnwindTerminateReason::InCleanup
+        } else {
+            mir::UnwindTerminateReason::Abi
+        };
+    }
+}
+
+struct RequiredConsts<'tcx>(Vec<mir::ConstOperand<'tcx>>);
+
+impl<'tcx> Visitor<'tcx> for RequiredConsts<'tcx> {
+    fn visit_const_operand(&mut self, constant: &mir::ConstOperand<'tcx>, _: mir::Location) {
+        if constant.const_.is_required_const() {
+            self.0.push(*constant);
+        }
+    }
+}
@@ -9,6 +9,7 @@
 use std::iter;
 
 use rustc_abi::HasDataLayout;
+use rustc_errors::Diag;
 use rustc_hir::LangItem;
 use rustc_middle::ty::layout::{
     FnAbiOf, FnAbiOfHelpers, HasParamEnv, HasTyCtxt, LayoutOf, LayoutOfHelpers,
@@ -21,8 +22,9 @@
 use rustc_span::def_id::LOCAL_CRATE;
 use stable_mir::abi::{FnAbi, Layout, LayoutShape};
 use stable_mir::compiler_interface::Context;
+use stable_mir::diagnostic::{Diagnostic, Level};
 use stable_mir::mir::alloc::GlobalAlloc;
-use stable_mir::mir::mono::{InstanceDef, StaticDef};
+use stable_mir::mir::mono::{InstanceDef, MonoItemGraph, StaticDef};
 use stable_mir::mir::{BinOp, Body, Place, UnOp};
 use stable_mir::target::{MachineInfo, MachineSize};
 use stable_mir::ty::{
@@ -58,6 +60,11 @@ fn all_local_items(&self) -> stable_mir::CrateItems {
         tables.tcx.mir_keys(()).iter().map(|item| tables.crate_item(item.to_def_id())).collect()
     }
 
+    fn mono_item_graph(&self) -> MonoItemGraph {
+        let mut tables = self.0.borrow_mut();
+        tables.tcx.mono_item_graph(()).stable(&mut tables)
+    }
+
     fn mir_body(&self, item: stable_mir::DefId) -> stable_mir::mir::Body {
         let mut tables = self.0.borrow_mut();
         let def_id = tables[item];
@@ -570,6 +577,33 @@ fn instance_args(&self, def: InstanceDef) -> GenericArgs {
         instance.args.stable(&mut *tables)
     }
 
+    // The messages of the diagnostics of tools can't be translated.
+    #[allow(rustc::diagnostic_outside_of_impl)]
+    #[allow(rustc::untranslatable_diagnostic)]
+    fn emit_diagnostic(&self, diagnostic: &Diagnostic) {
+        let tables = self.0.borrow();
+        let level = match diagnostic.level {
+            Level::Error => rustc_errors::Level::Error,
+            Level::Warning => rustc_errors::Level::Warning,
+            Level::Note => rustc_errors::Level::Note,
+            Level::Help => rustc_errors::Level::Help,
+        };
+        let mut diag = Diag::<()>::new(tables.tcx.dcx(), level, diagnostic.message.clone());
+        if let Some(span) = diagnostic.span {
+            diag.span(tables[span]);
+        }
+        for (span, label) in &diagnostic.labels {
+            diag.span_label(tables[*span], label.clone());
+        }
+        for note in &diagnostic.notes {
+            diag.note(note.clone());
+        }
+        for help in &diagnostic.helps {
+            diag.help(help.clone());
+        }
+        diag.emit();
+    }
+
     fn instance_abi(&self, def: InstanceDef) -> Result<FnAbi, Error> {
         let mut tables = self.0.borrow_mut();
         let instance = tables.instances[def];
@@ -1,7 +1,7 @@
 //! Conversion of internal Rust compiler `mir` items to stable ones.
 
 use rustc_middle::mir::interpret::alloc_range;
-use rustc_middle::mir::mono::MonoItem;
+use rustc_middle::mir::mono::{MonoItem, MonoItemEdge, MonoItemGraph, MonoItemUseKind};
 use rustc_middle::{bug, mir};
 use stable_mir::mir::alloc::GlobalAlloc;
 use stable_mir::mir::{ConstOperand, Statement, UserTypeProjection, VarDebugInfoFragment};
@@ -779,3 +779,42 @@ fn stable(&self, tables: &mut Tables<'_>) -> Self::T {
         }
     }
 }
+
+impl<'tcx> Stable<'tcx> for MonoItemGraph<'tcx> {
+    type T = stable_mir::mir::mono::MonoItemGraph;
+
+    fn stable(&self, tables: &mut Tables<'_>) -> Self::T {
+        stable_mir::mir::mono::MonoItemGraph {
+            items: self.items.iter().map(|item| item.stable(tables)).collect(),
+            edges: self.edges.iter().map(|edge| edge.stable(tables)).collect(),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for MonoItemEdge<'tcx> {
+    type T = stable_mir::mir::mono::MonoItemEdge;
+
+    fn stable(&self, tables: &mut Tables<'_>) -> Self::T {
+        stable_mir::mir::mono::MonoItemEdge {
+            user: self.user.stable(tables),
+            used: self.used.stable(tables),
+            kind: self.kind.stable(tables),
+            span: self.span.stable(tables),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for MonoItemUseKind {
+    type T = stable_mir::mir::mono::MonoItemUseKind;
+
+    fn stable(&self, _: &mut Tables<'_>) -> Self::T {
+        use stable_mir::mir::mono::MonoItemUseKind as StableKind;
+        match self {
+            MonoItemUseKind::Call => StableKind::Call,
+            MonoItemUseKind::DropGlue => StableKind::DropGlue,
+            MonoItemUseKind::Vtable => StableKind::Vtable,
+            MonoItemUseKind::FnPtr => StableKind::FnPtr,
+            MonoItemUseKind::Static => StableKind::Static,
+        }
+    }
+}
@@ -1089,6 +1089,7 @@
         len,
         let_chains,
         let_else,
+        level,
         lhs,
         lib,
         libc,
@@ -95,6 +95,12 @@ pub(super) fn mangle<'tcx>(
         ty::InstanceKind::CoroutineKindShim { .. } => {
             printer.write_str("{{by-move-body-shim}}").unwrap();
         }
+        ty::InstanceKind::ConstArgsShim(..) => {
+            printer.write_str("{{const-args-shim}}").unwrap();
+        }
+        ty::InstanceKind::ColdPathShim { .. } => {
+            printer.write_str("{{cold-path-shim}}").unwrap();
+        }
         _ => {}
     }
 
@@ -154,6 +160,18 @@ fn get_symbol_hash<'tcx>(
             // Especially, `VTableShim`s and `ReifyShim`s may overlap with their original
             // instances without this.
             discriminant(&instance.def).hash_stable(hcx, &mut hasher);
+
+            // The clones of a function specialized for different constant arguments only differ
+            // by these arguments.
+            if let ty::InstanceKind::ConstArgsShim(_, const_args) = instance.def {
+                const_args.hash_stable(hcx, &mut hasher);
+            }
+            // Same for the cold paths calling a function, by the function they are outlined from
+            // and their index.
+            if let ty::InstanceKind::ColdPathShim { outlined_from, index, .. } = instance.def {
+                outlined_from.hash_stable(hcx, &mut hasher);
+                index.hash_stable(hcx, &mut hasher);
+            }
         });
 
         // 64 bits should be enough to avoid collisions.
@@ -232,11 +232,16 @@ fn compute_symbol_name<'tcx>(
         InstantiationMode::GloballyShared { may_conflict: true }
     );
 
+    // Cold paths are named after the functions they call, which are often in other crates, and
+    // the cold paths of several crates may call the same function.
+    let is_cold_path = matches!(instance.def, ty::InstanceKind::ColdPathShim { .. });
+
     // If this is an instance of a generic function, we also hash in
     // the ID of the instantiating crate. This avoids symbol conflicts
     // in case the same instances is emitted in two crates of the same
     // project.
-    let avoid_cross_crate_conflicts = is_generic(instance, tcx) || is_globally_shared_function;
+    let avoid_cross_crate_conflicts =
+        is_generic(instance, tcx) || is_globally_shared_function || is_cold_path;
 
     let instantiating_crate = avoid_cross_crate_conflicts.then(compute_instantiating_crate);
 
@@ -5,6 +5,7 @@
 use rustc_data_structures::base_n::ToBaseN;
 use rustc_data_structures::fx::FxHashMap;
 use rustc_data_structures::intern::Interned;
+use rustc_data_structures::stable_hasher::{Hash64, HashStable, StableHasher};
 use rustc_hir as hir;
 use rustc_hir::def::CtorKind;
 use rustc_hir::def_id::{CrateNum, DefId};
@@ -57,12 +58,36 @@ pub(super) fn mangle<'tcx>(
             Some("by_ref")
         }
         ty::InstanceKind::CoroutineKindShim { .. } => Some("by_move_body"),
+        ty::InstanceKind::ConstArgsShim(..) => Some("const_args"),
+        ty::InstanceKind::ColdPathShim { .. } => Some("cold_path"),
 
         _ => None,
     };
 
+    // The clones of a function specialized for different constant arguments are told apart by
+    // the hash of these arguments, and the cold paths calling a function by the hash of the
+    // function they are outlined from and their index.
+    let shim_disambiguator = match instance.def {
+        ty::InstanceKind::ConstArgsShim(_, const_args) => {
+            tcx.with_stable_hashing_context(|mut hcx| {
+                let mut hasher = StableHasher::new();
+                const_args.hash_stable(&mut hcx, &mut hasher);
+                hasher.finish::<Hash64>().as_u64()
+            })
+        }
+        ty::InstanceKind::ColdPathShim { outlined_from, index, .. } => tcx
+            .with_stable_hashing_context(|mut hcx| {
+                let mut hasher = StableHasher::new();
+                outlined_from.hash_stable(&mut hcx, &mut hasher);
+                index.hash_stable(&mut hcx, &mut hasher);
+                hasher.finish::<Hash64>().as_u64()
+            }),
+        _ => 0,
+    };
+
     if let Some(shim_kind) = shim_kind {
-        cx.path_append_ns(|cx| cx.print_def_path(def_id, args), 'S', 0, shim_kind).unwrap()
+        cx.path_append_ns(|cx| cx.print_def_path(def_id, args), 'S', shim_disambiguator, shim_kind)
+            .unwrap()
     } else {
         cx.print_def_path(def_id, args).unwrap()
     };
@@ -43,6 +43,18 @@ fn fn_sig_for_fn_abi<'tcx>(
         ));
     }
 
+    // The signature of a cold path is that of its body, not that of the function it calls.
+    if let InstanceKind::ColdPathShim { .. } = instance.def {
+        let body = tcx.instance_mir(instance.def);
+        return ty::Binder::dummy(tcx.mk_fn_sig(
+            body.args_iter().map(|local| body.local_decls[local].ty),
+            body.return_ty(),
+            false,
+            hir::Safety::Safe,
+            rustc_target::spec::abi::Abi::Rust,
+        ));
+    }
+
     let ty = instance.ty(tcx, param_env);
     match *ty.kind() {
         ty::FnDef(..) => {
@@ -379,12 +391,19 @@ fn fn_abi_of_instance<'tcx>(
     let caller_location =
         instance.def.requires_caller_location(tcx).then(|| tcx.caller_location_ty());
 
+    // The attributes of the function a cold path calls, like the deduced attributes of its
+    // parameters, don't apply to the cold path.
+    let fn_def_id = match instance.def {
+        ty::InstanceKind::ColdPathShim { .. } => None,
+        _ => Some(instance.def_id()),
+    };
+
     fn_abi_new_uncached(
         &LayoutCx { tcx, param_env },
         sig,
         extra_args,
         caller_location,
-        Some(instance.def_id()),
+        fn_def_id,
         matches!(instance.def, ty::InstanceKind::Virtual(..)),
     )
 }
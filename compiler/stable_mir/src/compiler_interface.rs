@@ -7,8 +7,9 @@
 
 use crate::abi::{FnAbi, Layout, LayoutShape};
 use crate::crate_def::Attribute;
+use crate::diagnostic::Diagnostic;
 use crate::mir::alloc::{AllocId, GlobalAlloc};
-use crate::mir::mono::{Instance, InstanceDef, StaticDef};
+use crate::mir::mono::{Instance, InstanceDef, MonoItemGraph, StaticDef};
 use crate::mir::{BinOp, Body, Place, UnOp};
 use crate::target::MachineInfo;
 use crate::ty::{
@@ -28,6 +29,8 @@ pub trait Context {
     fn entry_fn(&self) -> Option<CrateItem>;
     /// Retrieve all items of the local crate that have a MIR associated with them.
     fn all_local_items(&self) -> CrateItems;
+    /// Collect the graph of the mono items of the local crate.
+    fn mono_item_graph(&self) -> MonoItemGraph;
     /// Retrieve the body of a function.
     /// This function will panic if the body is not available.
     fn mir_body(&self, item: DefId) -> mir::Body;
@@ -222,6 +225,9 @@ fn resolve_closure(
     /// Return information about the target machine.
     fn target_info(&self) -> MachineInfo;
 
+    /// Emit a diagnostic of a tool.
+    fn emit_diagnostic(&self, diagnostic: &Diagnostic);
+
     /// Get an instance ABI.
     fn instance_abi(&self, def: InstanceDef) -> Result<FnAbi, Error>;
 
@@ -260,6 +266,19 @@ pub fn run<F, T>(context: &dyn Context, f: F) -> Result<T, Error>
     }
 }
 
+/// Like [run], but `context` may also replace the current context until `f` returns.
+///
+/// This lets the compiler run code written against StableMIR, like MIR transformations, while
+/// StableMIR is already running, since their queries may be computed for a StableMIR tool. The
+/// items of the two contexts can't be mixed.
+pub fn run_nested<F, T>(context: &dyn Context, f: F) -> T
+where
+    F: FnOnce() -> T,
+{
+    let ptr: *const () = std::ptr::addr_of!(context) as _;
+    TLV.set(&Cell::new(ptr), f)
+}
+
 /// Execute the given function with access the compiler [Context].
 ///
 /// I.e., This function will load the current context and calls a function with it.
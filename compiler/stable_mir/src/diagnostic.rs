@@ -0,0 +1,76 @@
+//! Diagnostics that tools emit through the compiler, like the warnings of custom lints.
+//!
+//! They are reported like the diagnostics of the compiler, so errors make the compilation fail,
+//! and warnings are affected by `--cap-lints` and `-A warnings`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::compiler_interface::with;
+use crate::ty::Span;
+
+/// A diagnostic, with a message, the span it is about and subdiagnostics.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub level: Level,
+    pub message: String,
+    /// The primary span of the diagnostic, if it's about a specific part of the code.
+    pub span: Option<Span>,
+    /// Labels shown at other spans, or the primary one.
+    pub labels: Vec<(Span, String)>,
+    pub notes: Vec<String>,
+    pub helps: Vec<String>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Level {
+    Error,
+    Warning,
+    Note,
+    Help,
+}
+
+impl Diagnostic {
+    pub fn new(level: Level, message: impl Into<String>) -> Diagnostic {
+        Diagnostic {
+            level,
+            message: message.into(),
+            span: None,
+            labels: vec![],
+            notes: vec![],
+            helps: vec![],
+        }
+    }
+
+    pub fn error(message: impl Into<String>) -> Diagnostic {
+        Diagnostic::new(Level::Error, message)
+    }
+
+    pub fn warning(message: impl Into<String>) -> Diagnostic {
+        Diagnostic::new(Level::Warning, message)
+    }
+
+    pub fn with_span(mut self, span: Span) -> Diagnostic {
+        self.span = Some(span);
+        self
+    }
+
+    pub fn with_label(mut self, span: Span, label: impl Into<String>) -> Diagnostic {
+        self.labels.push((span, label.into()));
+        self
+    }
+
+    pub fn with_note(mut self, note: impl Into<String>) -> Diagnostic {
+        self.notes.push(note.into());
+        self
+    }
+
+    pub fn with_help(mut self, help: impl Into<String>) -> Diagnostic {
+        self.helps.push(help.into());
+        self
+    }
+
+    /// Emit the diagnostic through the compiler.
+    pub fn emit(&self) {
+        with(|cx| cx.emit_diagnostic(self))
+    }
+}
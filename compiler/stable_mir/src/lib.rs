@@ -20,11 +20,12 @@
 use std::fmt::Debug;
 use std::{fmt, io};
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::compiler_interface::with;
 pub use crate::crate_def::{CrateDef, CrateDefType, DefId};
 pub use crate::error::*;
+use crate::mir::mono::MonoItemGraph;
 use crate::mir::{Body, Mutability};
 use crate::ty::{ForeignModuleDef, ImplDef, IndexedVal, Span, TraitDef, Ty};
 
@@ -32,9 +33,12 @@
 #[macro_use]
 pub mod crate_def;
 pub mod compiler_interface;
+pub mod diagnostic;
 #[macro_use]
 pub mod error;
 pub mod mir;
+pub mod plugin;
+pub mod snapshot;
 pub mod target;
 pub mod ty;
 pub mod visitor;
@@ -74,7 +78,7 @@ fn to_index(&self) -> usize {
 pub type ImplTraitDecls = Vec<ImplDef>;
 
 /// Holds information about a crate.
-#[derive(Clone, PartialEq, Eq, Debug, Serialize)]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct Crate {
     pub id: CrateNum,
     pub name: Symbol,
@@ -98,7 +102,7 @@ pub fn trait_impls(&self) -> ImplTraitDecls {
     }
 }
 
-#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash, Serialize)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash, Serialize, Deserialize)]
 pub enum ItemKind {
     Fn,
     Static,
@@ -106,7 +110,7 @@ pub enum ItemKind {
     Ctor(CtorKind),
 }
 
-#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash, Serialize)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash, Serialize, Deserialize)]
 pub enum CtorKind {
     Const,
     Fn,
@@ -116,7 +120,7 @@ pub enum CtorKind {
 
 crate_def_with_ty! {
     /// Holds information about an item in a crate.
-    #[derive(Serialize)]
+    #[derive(Serialize, Deserialize)]
     pub CrateItem;
 }
 
@@ -188,8 +192,16 @@ pub fn all_trait_impls() -> ImplTraitDecls {
     with(|cx| cx.all_trait_impls())
 }
 
+/// Collect the graph of the mono items of the local crate, like the compiler does for codegen.
+///
+/// The compiler collects the mono items again to compute the graph, so this is as costly as the
+/// collection, and reports its errors, like the ones of constants failing to evaluate.
+pub fn mono_item_graph() -> MonoItemGraph {
+    with(|cx| cx.mono_item_graph())
+}
+
 /// A type that provides internal information but that can still be used for debug purpose.
-#[derive(Clone, PartialEq, Eq, Hash, Serialize)]
+#[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Opaque(String);
 
 impl std::fmt::Display for Opaque {
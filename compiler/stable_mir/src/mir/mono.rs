@@ -1,22 +1,107 @@
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fmt::{Debug, Formatter};
 use std::io;
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::abi::FnAbi;
 use crate::crate_def::CrateDef;
 use crate::mir::Body;
-use crate::ty::{Allocation, ClosureDef, ClosureKind, FnDef, GenericArgs, IndexedVal, Ty};
+use crate::ty::{Allocation, ClosureDef, ClosureKind, FnDef, GenericArgs, IndexedVal, Span, Ty};
 use crate::{with, CrateItem, DefId, Error, ItemKind, Opaque, Symbol};
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum MonoItem {
     Fn(Instance),
     Static(StaticDef),
     GlobalAsm(Opaque),
 }
 
-#[derive(Copy, Clone, PartialEq, Eq, Hash, Serialize)]
+/// The graph of the mono items of the local crate, as computed by the monomorphization collector
+/// of the compiler, which is the call graph that codegen sees.
+///
+/// Its items are the functions and statics to codegen in the local crate, which includes the
+/// instances of items of other crates that aren't already codegened by them, like the ones of
+/// their generic functions. The items of other crates used by the local crate which are
+/// codegened by them are neither items nor the targets of edges.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MonoItemGraph {
+    /// The mono items, in a deterministic order.
+    pub items: Vec<MonoItem>,
+    /// The uses of mono items by other mono items, grouped by user in the order of `items`.
+    pub edges: Vec<MonoItemEdge>,
+}
+
+/// A use of a mono item by another.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MonoItemEdge {
+    pub user: MonoItem,
+    pub used: MonoItem,
+    pub kind: MonoItemUseKind,
+    /// The span of the use in the body of `user`, or a dummy span if it has none.
+    pub span: Span,
+}
+
+/// How a mono item uses another.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MonoItemUseKind {
+    /// A call of a function, including the calls inserted by codegen, like the ones of the panic
+    /// functions of failed assertions.
+    Call,
+    /// A drop of a value, which calls its drop glue.
+    DropGlue,
+    /// A method, or the drop glue, of a vtable built by an unsizing coercion to a trait object.
+    Vtable,
+    /// A function whose address is taken, like by a cast to a function pointer, or by a pointer
+    /// stored in a constant.
+    FnPtr,
+    /// A reference to a static.
+    Static,
+}
+
+impl MonoItemGraph {
+    /// Returns the instances of the functions of the graph.
+    pub fn instances(&self) -> impl Iterator<Item = &Instance> {
+        self.items.iter().filter_map(|item| match item {
+            MonoItem::Fn(instance) => Some(instance),
+            MonoItem::Static(_) | MonoItem::GlobalAsm(_) => None,
+        })
+    }
+
+    /// Returns the uses of other mono items by `item`.
+    pub fn uses<'a>(&'a self, item: &'a MonoItem) -> impl Iterator<Item = &'a MonoItemEdge> {
+        self.edges.iter().filter(move |edge| edge.user == *item)
+    }
+
+    /// Returns the uses of `item` by other mono items.
+    pub fn users<'a>(&'a self, item: &'a MonoItem) -> impl Iterator<Item = &'a MonoItemEdge> {
+        self.edges.iter().filter(move |edge| edge.used == *item)
+    }
+
+    /// Returns the mono items reachable from `root` through any kind of use, including `root`
+    /// itself, in breadth-first order.
+    pub fn reachable_from(&self, root: &MonoItem) -> Vec<MonoItem> {
+        let mut successors: HashMap<&MonoItem, Vec<&MonoItem>> = HashMap::new();
+        for edge in &self.edges {
+            successors.entry(&edge.user).or_default().push(&edge.used);
+        }
+
+        let mut visited = HashSet::from([root]);
+        let mut queue = VecDeque::from([root]);
+        let mut reachable = Vec::new();
+        while let Some(item) = queue.pop_front() {
+            reachable.push(item.clone());
+            for &used in successors.get(item).into_iter().flatten() {
+                if visited.insert(used) {
+                    queue.push_back(used);
+                }
+            }
+        }
+        reachable
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Instance {
     /// The type of instance.
     pub kind: InstanceKind,
@@ -25,7 +110,7 @@ pub struct Instance {
     pub def: InstanceDef,
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum InstanceKind {
     /// A user defined item.
     Item,
@@ -243,7 +328,7 @@ fn from(value: StaticDef) -> Self {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct InstanceDef(usize);
 
 impl CrateDef for InstanceDef {
@@ -254,7 +339,7 @@ fn def_id(&self) -> DefId {
 
 crate_def! {
     /// Holds information about a static variable definition.
-    #[derive(Serialize)]
+    #[derive(Serialize, Deserialize)]
     pub StaticDef;
 }
 
@@ -0,0 +1,33 @@
+//! A stable interface for tools that plug into the compiler, like custom lint tools.
+//!
+//! A [Plugin] has callbacks at well-defined phases of the compilation, and is run by
+//! `rustc_smir::run_plugin!`. Each callback can use the StableMIR APIs, and report its findings
+//! with [Diagnostic](crate::diagnostic::Diagnostic)s. Returning `ControlFlow::Break` from a
+//! callback stops the compilation after it.
+//!
+//! Each callback runs in a StableMIR context of its own, so the items it gets, like types or
+//! definitions, can't be used by the next callbacks.
+
+use std::ops::ControlFlow;
+
+/// The callbacks of a tool at the phases of the compilation, in the order they are called.
+pub trait Plugin {
+    /// Called after the crate is parsed and its macros are expanded, before type checking.
+    ///
+    /// The items of the crate are known, but querying their types or bodies type checks them.
+    fn after_parsing(&mut self) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+
+    /// Called after the crate is type checked, and its other analyses, like borrow checking, are
+    /// done without errors.
+    fn after_type_checking(&mut self) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+
+    /// Called after the MIR of all the functions of the crate is optimized, and transformed by the
+    /// registered MIR transformations, before code generation.
+    fn after_mir_optimization(&mut self) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+}
@@ -0,0 +1,688 @@
+//! Snapshots of the stable MIR of the local crate, which can be saved to a file and loaded without
+//! the compiler.
+//!
+//! A [CrateSnapshot] has the items of the crate with their bodies, the instances of its functions
+//! and of the functions they call, and the types, definitions, allocations and spans which these
+//! refer to, since StableMIR only gives out ids for them. Snapshots are saved as JSON, or in a
+//! compact binary format, and the `stable_mir_loader` crate reconstructs their items offline, so
+//! that verification and analysis tools can run without linking against rustc.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::{self, Display, Formatter};
+use std::io;
+use std::ops::ControlFlow;
+
+use bincode::Options;
+use serde::ser::{self, Serializer};
+use serde::{Deserialize, Serialize};
+
+use crate::compiler_interface::with;
+use crate::crate_def::CrateDef;
+use crate::mir::alloc::{AllocId, GlobalAlloc};
+use crate::mir::mono::{Instance, InstanceDef};
+use crate::mir::visit::Location;
+use crate::mir::{Body, MirVisitor, TerminatorKind};
+use crate::target::MachineInfo;
+use crate::ty::{
+    FnDef, GenericArgs, IndexedVal, LineInfo, MirConst, MirConstId, RigidTy, Span, Ty, TyConst,
+    TyConstId, TyKind,
+};
+use crate::visitor::{Visitable, Visitor};
+use crate::{Crate, CrateItem, CrateNum, DefId, Error, Filename, ItemKind, Symbol};
+
+/// The version of the format of snapshots, which changes with the types of StableMIR.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// The start of the snapshots saved in the binary format.
+const BINARY_MAGIC: &[u8; 8] = b"SMIRSNAP";
+
+/// The formats that snapshots are saved in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// JSON, which tools written in other languages can read too.
+    Json,
+    /// A compact binary format, for large crates.
+    Binary,
+}
+
+/// A snapshot of the stable MIR of the local crate.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CrateSnapshot {
+    /// The [FORMAT_VERSION] of the snapshot, which comes first so that it can be checked before
+    /// the rest is read.
+    pub format_version: u32,
+    pub local_crate: Crate,
+    /// The crates of the definitions in the snapshot.
+    pub crates: Vec<Crate>,
+    pub target: MachineInfo,
+    pub entry_fn: Option<CrateItem>,
+    pub items: Vec<ItemData>,
+    pub instances: Vec<InstanceData>,
+    pub resolutions: Vec<Resolution>,
+    pub defs: Vec<DefData>,
+    pub tys: Vec<TyData>,
+    pub mir_consts: Vec<MirConstData>,
+    pub ty_consts: Vec<TyConstData>,
+    pub allocs: Vec<AllocData>,
+    pub spans: Vec<SpanData>,
+}
+
+/// An item of the local crate.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ItemData {
+    pub item: CrateItem,
+    pub kind: ItemKind,
+    pub ty: Ty,
+    /// The body of the item, which is generic if the item is.
+    pub body: Option<Body>,
+}
+
+/// The instance of a function of the local crate which isn't generic, or of a function that such
+/// instances call, transitively.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct InstanceData {
+    pub instance: Instance,
+    pub def_id: DefId,
+    pub args: GenericArgs,
+    pub ty: Ty,
+    pub name: Symbol,
+    pub trimmed_name: Symbol,
+    pub mangled_name: Symbol,
+    pub intrinsic_name: Option<Symbol>,
+    /// The monomorphized body of the instance, which is only saved for the instances of the
+    /// functions of the local crate.
+    pub body: Option<Body>,
+}
+
+/// The instance that a call to a function item in the body of an instance resolves to.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Resolution {
+    pub def: FnDef,
+    pub args: GenericArgs,
+    pub instance: Instance,
+}
+
+/// A definition which the snapshot refers to.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct DefData {
+    pub def_id: DefId,
+    pub name: Symbol,
+    pub trimmed_name: Symbol,
+    pub krate: CrateNum,
+    pub span: Span,
+    pub is_foreign_item: bool,
+    pub requires_monomorphization: bool,
+}
+
+/// A type which the snapshot refers to.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TyData {
+    pub ty: Ty,
+    pub kind: TyKind,
+    pub pretty: String,
+}
+
+/// The pretty-printed form of a constant of the bodies of the snapshot.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct MirConstData {
+    pub id: MirConstId,
+    pub pretty: String,
+}
+
+/// The pretty-printed form of a constant of the types of the snapshot.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TyConstData {
+    pub id: TyConstId,
+    pub pretty: String,
+}
+
+/// An allocation which the snapshot refers to, like the memory of a string constant.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct AllocData {
+    pub id: AllocId,
+    pub alloc: GlobalAlloc,
+}
+
+/// A span which the snapshot refers to.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SpanData {
+    pub span: Span,
+    pub filename: Filename,
+    pub lines: LineInfo,
+    pub pretty: String,
+}
+
+/// What is read first from a snapshot saved as JSON.
+#[derive(Deserialize)]
+struct JsonHeader {
+    format_version: u32,
+}
+
+impl CrateSnapshot {
+    /// Takes a snapshot of the local crate.
+    ///
+    /// This must be called while StableMIR is running, like in the callback of `run!`.
+    pub fn capture() -> CrateSnapshot {
+        let mut capture = Capture::new();
+        for item in crate::all_local_items() {
+            capture.item(item);
+        }
+        capture.run();
+        capture.snapshot
+    }
+
+    /// Saves the snapshot to `writer` in the given format.
+    pub fn write<W: io::Write>(&self, mut writer: W, format: Format) -> Result<(), Error> {
+        match format {
+            Format::Json => serde_json::to_writer(writer, self)
+                .map_err(|err| error!("Failed to write snapshot: {err}")),
+            Format::Binary => {
+                writer.write_all(BINARY_MAGIC)?;
+                binary_options()
+                    .serialize_into(writer, self)
+                    .map_err(|err| error!("Failed to write snapshot: {err}"))
+            }
+        }
+    }
+
+    /// Reads a snapshot saved in any format from `reader`.
+    pub fn read<R: io::Read>(mut reader: R) -> Result<CrateSnapshot, Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        if let Some(bytes) = bytes.strip_prefix(BINARY_MAGIC) {
+            // The lengths in the snapshot can't add up to more bytes than it has, so that a
+            // malformed snapshot doesn't allocate more memory than it takes.
+            let limit = bytes.len() as u64;
+            let format_version: u32 = binary_options()
+                .with_limit(limit)
+                .allow_trailing_bytes()
+                .deserialize(bytes)
+                .map_err(|err| error!("Failed to read snapshot: {err}"))?;
+            check_format_version(format_version)?;
+            binary_options()
+                .with_limit(limit)
+                .deserialize(bytes)
+                .map_err(|err| error!("Failed to read snapshot: {err}"))
+        } else {
+            let header: JsonHeader = serde_json::from_slice(&bytes)
+                .map_err(|err| error!("Failed to read snapshot: {err}"))?;
+            check_format_version(header.format_version)?;
+            serde_json::from_slice(&bytes).map_err(|err| error!("Failed to read snapshot: {err}"))
+        }
+    }
+}
+
+fn binary_options() -> impl Options {
+    bincode::DefaultOptions::new()
+}
+
+fn check_format_version(format_version: u32) -> Result<(), Error> {
+    if format_version == FORMAT_VERSION {
+        Ok(())
+    } else {
+        Err(error!(
+            "Snapshot has format version {format_version}, but this version of StableMIR reads \
+            version {FORMAT_VERSION}"
+        ))
+    }
+}
+
+/// Takes a snapshot, adding what its items refer to until it refers to nothing else.
+struct Capture {
+    snapshot: CrateSnapshot,
+    ids: Ids,
+    consts: Consts,
+    instances: VecDeque<Instance>,
+    seen_instances: HashSet<InstanceDef>,
+    seen_tys: HashSet<Ty>,
+    seen_defs: HashSet<DefId>,
+    seen_allocs: HashSet<AllocId>,
+    seen_spans: HashSet<Span>,
+    resolved: HashMap<FnDef, Vec<GenericArgs>>,
+}
+
+impl Capture {
+    fn new() -> Capture {
+        Capture {
+            snapshot: CrateSnapshot {
+                format_version: FORMAT_VERSION,
+                local_crate: crate::local_crate(),
+                crates: Vec::new(),
+                target: MachineInfo::target(),
+                entry_fn: crate::entry_fn(),
+                items: Vec::new(),
+                instances: Vec::new(),
+                resolutions: Vec::new(),
+                defs: Vec::new(),
+                tys: Vec::new(),
+                mir_consts: Vec::new(),
+                ty_consts: Vec::new(),
+                allocs: Vec::new(),
+                spans: Vec::new(),
+            },
+            ids: Ids::default(),
+            consts: Consts::default(),
+            instances: VecDeque::new(),
+            seen_instances: HashSet::new(),
+            seen_tys: HashSet::new(),
+            seen_defs: HashSet::new(),
+            seen_allocs: HashSet::new(),
+            seen_spans: HashSet::new(),
+            resolved: HashMap::new(),
+        }
+    }
+
+    /// Adds what the items added so far refer to, transitively.
+    fn run(&mut self) {
+        loop {
+            if let Some(instance) = self.instances.pop_front() {
+                self.instance(instance);
+            } else if let Some(ty) = self.ids.tys.pop() {
+                self.ty(ty);
+            } else if let Some(alloc) = self.ids.allocs.pop() {
+                self.alloc(alloc);
+            } else if let Some(def_id) = self.ids.defs.pop() {
+                self.def(def_id);
+            } else if let Some(span) = self.ids.spans.pop() {
+                self.span(span);
+            } else {
+                break;
+            }
+        }
+        let consts = std::mem::take(&mut self.consts);
+        self.snapshot.mir_consts = consts.mir;
+        self.snapshot.ty_consts = consts.ty;
+    }
+
+    /// Collects the ids that `value` refers to.
+    fn walk<T: Serialize>(&mut self, value: &T) {
+        value.serialize(&mut self.ids).expect("collecting ids can't fail");
+    }
+
+    fn item(&mut self, item: CrateItem) {
+        let kind = item.kind();
+        let body = with(|cx| cx.has_body(item.def_id())).then(|| item.body());
+        if let Some(body) = &body {
+            self.consts.visit_body(body);
+        }
+        if kind == ItemKind::Fn {
+            if let Ok(instance) = Instance::try_from(item) {
+                self.instances.push_back(instance);
+            }
+        }
+
+        let data = ItemData { item, kind, ty: item.ty(), body };
+        self.walk(&data);
+        self.snapshot.items.push(data);
+    }
+
+    fn instance(&mut self, instance: Instance) {
+        if !self.seen_instances.insert(instance.def) {
+            return;
+        }
+        let body = if instance.def.krate().is_local { instance.body() } else { None };
+        if let Some(body) = &body {
+            self.consts.visit_body(body);
+            self.calls(body);
+        }
+
+        let data = InstanceData {
+            instance,
+            def_id: instance.def.def_id(),
+            args: instance.args(),
+            ty: instance.ty(),
+            name: instance.name(),
+            trimmed_name: instance.trimmed_name(),
+            mangled_name: instance.mangled_name(),
+            intrinsic_name: instance.intrinsic_name(),
+            body,
+        };
+        self.walk(&data);
+        self.snapshot.instances.push(data);
+    }
+
+    /// Resolves the calls to function items of the monomorphized `body`.
+    fn calls(&mut self, body: &Body) {
+        for block in &body.blocks {
+            let TerminatorKind::Call { func, .. } = &block.terminator.kind else {
+                continue;
+            };
+            let Ok(TyKind::RigidTy(RigidTy::FnDef(def, args))) =
+                func.ty(body.locals()).map(|ty| ty.kind())
+            else {
+                continue;
+            };
+            let resolved = self.resolved.entry(def).or_default();
+            if resolved.contains(&args) {
+                continue;
+            }
+            resolved.push(args.clone());
+            if let Ok(instance) = Instance::resolve(def, &args) {
+                let resolution = Resolution { def, args, instance };
+                self.walk(&resolution);
+                self.snapshot.resolutions.push(resolution);
+                self.instances.push_back(instance);
+            }
+        }
+    }
+
+    fn ty(&mut self, ty: Ty) {
+        if !self.seen_tys.insert(ty) {
+            return;
+        }
+        let kind = ty.kind();
+        self.consts.visit_ty_kind(&kind);
+        let data = TyData { ty, kind, pretty: ty.to_string() };
+        self.walk(&data);
+        self.snapshot.tys.push(data);
+    }
+
+    fn alloc(&mut self, id: AllocId) {
+        if !self.seen_allocs.insert(id) {
+            return;
+        }
+        let alloc = GlobalAlloc::from(id);
+        if let GlobalAlloc::Function(instance) = &alloc {
+            self.instances.push_back(*instance);
+        }
+        let data = AllocData { id, alloc };
+        self.walk(&data);
+        self.snapshot.allocs.push(data);
+    }
+
+    fn def(&mut self, def_id: DefId) {
+        if !self.seen_defs.insert(def_id) {
+            return;
+        }
+        let krate = with(|cx| cx.krate(def_id));
+        let data = DefData {
+            def_id,
+            name: with(|cx| cx.def_name(def_id, false)),
+            trimmed_name: with(|cx| cx.def_name(def_id, true)),
+            krate: krate.id,
+            span: with(|cx| cx.span_of_an_item(def_id)),
+            is_foreign_item: with(|cx| cx.is_foreign_item(def_id)),
+            requires_monomorphization: with(|cx| cx.requires_monomorphization(def_id)),
+        };
+        if !self.snapshot.crates.contains(&krate) {
+            self.snapshot.crates.push(krate);
+        }
+        self.walk(&data);
+        self.snapshot.defs.push(data);
+    }
+
+    fn span(&mut self, span: Span) {
+        if !self.seen_spans.insert(span) {
+            return;
+        }
+        self.snapshot.spans.push(SpanData {
+            span,
+            filename: span.get_filename(),
+            lines: span.get_lines(),
+            pretty: with(|cx| cx.span_to_string(span)),
+        });
+    }
+}
+
+/// Pretty-prints the constants of bodies and types, whose ids mean nothing outside the compiler.
+#[derive(Default)]
+struct Consts {
+    mir: Vec<MirConstData>,
+    ty: Vec<TyConstData>,
+    seen_mir: HashSet<usize>,
+    seen_ty: HashSet<usize>,
+}
+
+impl Consts {
+    fn visit_ty_kind(&mut self, kind: &TyKind) {
+        let _ = match kind {
+            TyKind::RigidTy(ty) => ty.visit(self),
+            TyKind::Alias(_, alias) => alias.args.visit(self),
+            TyKind::Param(_) | TyKind::Bound(..) => ControlFlow::Continue(()),
+        };
+    }
+}
+
+impl MirVisitor for Consts {
+    fn visit_mir_const(&mut self, constant: &MirConst, _: Location) {
+        if self.seen_mir.insert(constant.id.to_index()) {
+            let pretty = with(|cx| cx.mir_const_pretty(constant));
+            self.mir.push(MirConstData { id: constant.id, pretty });
+        }
+        let _ = Visitable::visit(constant, self);
+    }
+
+    fn visit_ty_const(&mut self, constant: &TyConst, _: Location) {
+        let _ = Visitable::visit(constant, self);
+    }
+}
+
+impl Visitor for Consts {
+    type Break = ();
+
+    fn visit_ty(&mut self, _: &Ty) -> ControlFlow<()> {
+        // The constants of the types are pretty-printed when the types are added.
+        ControlFlow::Continue(())
+    }
+
+    fn visit_const(&mut self, constant: &TyConst) -> ControlFlow<()> {
+        if self.seen_ty.insert(constant.id.to_index()) {
+            let pretty = with(|cx| cx.ty_const_pretty(constant.id));
+            self.ty.push(TyConstData { id: constant.id, pretty });
+        }
+        constant.super_visit(self)
+    }
+}
+
+/// The ids that values refer to, which are found by serializing them, since they are the newtype
+/// structs named after their types, like `Ty(3)`.
+#[derive(Default)]
+struct Ids {
+    tys: Vec<Ty>,
+    defs: Vec<DefId>,
+    allocs: Vec<AllocId>,
+    spans: Vec<Span>,
+    /// The name of the newtype struct whose index is serialized next.
+    newtype: Option<&'static str>,
+}
+
+#[derive(Debug)]
+struct IdsError(String);
+
+impl Display for IdsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl std::error::Error for IdsError {}
+
+impl ser::Error for IdsError {
+    fn custom<T: Display>(msg: T) -> Self {
+        IdsError(msg.to_string())
+    }
+}
+
+macro_rules! ignore_primitives {
+    ($($method:ident($ty:ty)),* $(,)?) => {
+        $(
+            fn $method(self, _: $ty) -> Result<(), IdsError> {
+                Ok(())
+            }
+        )*
+    };
+}
+
+impl Serializer for &mut Ids {
+    type Ok = ();
+    type Error = IdsError;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    ignore_primitives! {
+        serialize_bool(bool),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_i128(i128),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u128(u128),
+        serialize_f32(f32),
+        serialize_f64(f64),
+        serialize_char(char),
+        serialize_str(&str),
+        serialize_bytes(&[u8]),
+        serialize_unit_struct(&'static str),
+    }
+
+    fn serialize_u64(self, index: u64) -> Result<(), IdsError> {
+        let index = index as usize;
+        match self.newtype.take() {
+            Some("Ty") => self.tys.push(Ty::to_val(index)),
+            Some("DefId") => self.defs.push(DefId::to_val(index)),
+            Some("AllocId") => self.allocs.push(AllocId::to_val(index)),
+            Some("Span") => self.spans.push(Span::to_val(index)),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<(), IdsError> {
+        Ok(())
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<(), IdsError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), IdsError> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+    ) -> Result<(), IdsError> {
+        Ok(())
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<(), IdsError> {
+        self.newtype = Some(name);
+        value.serialize(&mut *self)?;
+        self.newtype = None;
+        Ok(())
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        value: &T,
+    ) -> Result<(), IdsError> {
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self, IdsError> {
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self, IdsError> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(self, _: &'static str, _: usize) -> Result<Self, IdsError> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self, IdsError> {
+        Ok(self)
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self, IdsError> {
+        Ok(self)
+    }
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self, IdsError> {
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self, IdsError> {
+        Ok(self)
+    }
+}
+
+macro_rules! serialize_compound {
+    ($($trait:ident::$method:ident($($name:ty)?)),* $(,)?) => {
+        $(
+            impl ser::$trait for &mut Ids {
+                type Ok = ();
+                type Error = IdsError;
+
+                fn $method<T: ?Sized + Serialize>(
+                    &mut self,
+                    $(_: $name,)?
+                    value: &T,
+                ) -> Result<(), IdsError> {
+                    value.serialize(&mut **self)
+                }
+
+                fn end(self) -> Result<(), IdsError> {
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+serialize_compound! {
+    SerializeSeq::serialize_element(),
+    SerializeTuple::serialize_element(),
+    SerializeTupleStruct::serialize_field(),
+    SerializeTupleVariant::serialize_field(),
+    SerializeStruct::serialize_field(&'static str),
+    SerializeStructVariant::serialize_field(&'static str),
+}
+
+impl ser::SerializeMap for &mut Ids {
+    type Ok = ();
+    type Error = IdsError;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), IdsError> {
+        key.serialize(&mut **self)
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), IdsError> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), IdsError> {
+        Ok(())
+    }
+}
@@ -0,0 +1,633 @@
+//! Loads the snapshots of the stable MIR of crates, so that tools written against StableMIR can
+//! run without linking against rustc.
+//!
+//! Snapshots are taken while the compiler runs with
+//! [`CrateSnapshot::capture`](stable_mir::snapshot::CrateSnapshot::capture), and saved with
+//! [`CrateSnapshot::write`](stable_mir::snapshot::CrateSnapshot::write). A loaded snapshot then
+//! answers the queries of StableMIR, like the compiler does in `rustc_smir::run!`:
+//!
+//! ```ignore (needs-snapshot)
+//! let snapshot = stable_mir_loader::load("input.smir")?;
+//! stable_mir_loader::run(&snapshot, || {
+//!     for item in stable_mir::all_local_items() {
+//!         println!("{}: {} blocks", item.name(), item.body().blocks.len());
+//!     }
+//! })?;
+//! ```
+//!
+//! Snapshots answer the queries about their items, instances, types, definitions, allocations
+//! and spans, like `CrateItem::body`, `Instance::resolve` or `Ty::kind`. The queries that need
+//! the compiler, like the layouts of types, the evaluation of constants, or the traits and ADTs of
+//! crates, panic, or return an error when they can fail.
+
+#[cfg(test)]
+mod tests;
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+use stable_mir::abi::{FnAbi, Layout, LayoutShape};
+use stable_mir::compiler_interface::{self, Context};
+use stable_mir::crate_def::Attribute;
+use stable_mir::diagnostic::Diagnostic;
+use stable_mir::mir::alloc::{AllocId, GlobalAlloc};
+use stable_mir::mir::mono::{Instance, InstanceDef, InstanceKind, MonoItemGraph, StaticDef};
+use stable_mir::mir::{BinOp, Body, Place, ProjectionElem, UnOp};
+use stable_mir::snapshot::{CrateSnapshot, DefData, InstanceData, ItemData, SpanData};
+use stable_mir::target::MachineInfo;
+use stable_mir::ty::{
+    AdtDef, AdtKind, Allocation, ClosureDef, ClosureKind, FieldDef, FnDef, ForeignDef,
+    ForeignItemKind, ForeignModule, ForeignModuleDef, GenericArgs, GenericPredicates, Generics,
+    ImplDef, ImplTrait, IndexedVal, IntrinsicDef, LineInfo, MirConst, PolyFnSig, RigidTy, Span,
+    TraitDecl, TraitDef, Ty, TyConst, TyConstId, TyKind, UintTy, VariantDef,
+};
+use stable_mir::{
+    Crate, CrateItem, CrateItems, CrateNum, DefId, Error, Filename, ImplTraitDecls, ItemKind,
+    Symbol, TraitDecls,
+};
+
+/// Loads the snapshot saved at `path`, in any format.
+pub fn load(path: impl AsRef<Path>) -> Result<CrateSnapshot, Error> {
+    CrateSnapshot::read(BufReader::new(File::open(path)?))
+}
+
+/// Runs `f` with StableMIR answering its queries from `snapshot`.
+pub fn run<F, T>(snapshot: &CrateSnapshot, f: F) -> Result<T, Error>
+where
+    F: FnOnce() -> T,
+{
+    compiler_interface::run(&SnapshotContext::new(snapshot), f)
+}
+
+/// Panics for a query that a snapshot can't answer.
+macro_rules! unavailable {
+    ($query:literal) => {
+        panic!(concat!("`", $query, "` is not available in a StableMIR snapshot"))
+    };
+}
+
+/// Returns the error of a query that a snapshot can't answer.
+macro_rules! unavailable_error {
+    ($query:literal) => {
+        Err(Error::from(concat!("`", $query, "` is not available in a StableMIR snapshot")))
+    };
+}
+
+/// The [Context] of StableMIR backed by a snapshot.
+struct SnapshotContext<'a> {
+    snapshot: &'a CrateSnapshot,
+    items: HashMap<DefId, &'a ItemData>,
+    instances: HashMap<InstanceDef, &'a InstanceData>,
+    resolutions: HashMap<FnDef, Vec<(&'a GenericArgs, Instance)>>,
+    defs: HashMap<DefId, &'a DefData>,
+    tys: HashMap<Ty, &'a TyKind>,
+    /// The types created after the snapshot was loaded, like with `Ty::new_tuple`, whose ids
+    /// follow those of the types of the snapshot.
+    new_tys: RefCell<Vec<(Ty, TyKind)>>,
+    ty_prettys: HashMap<Ty, &'a str>,
+    mir_consts: HashMap<usize, &'a str>,
+    ty_consts: HashMap<usize, &'a str>,
+    allocs: HashMap<AllocId, &'a GlobalAlloc>,
+    spans: HashMap<Span, &'a SpanData>,
+}
+
+impl<'a> SnapshotContext<'a> {
+    fn new(snapshot: &'a CrateSnapshot) -> Self {
+        let mut resolutions: HashMap<_, Vec<_>> = HashMap::new();
+        for resolution in &snapshot.resolutions {
+            resolutions
+                .entry(resolution.def)
+                .or_default()
+                .push((&resolution.args, resolution.instance));
+        }
+        SnapshotContext {
+            snapshot,
+            items: snapshot.items.iter().map(|data| (data.item.0, data)).collect(),
+            instances: snapshot.instances.iter().map(|data| (data.instance.def, data)).collect(),
+            resolutions,
+            defs: snapshot.defs.iter().map(|data| (data.def_id, data)).collect(),
+            tys: snapshot.tys.iter().map(|data| (data.ty, &data.kind)).collect(),
+            new_tys: RefCell::new(Vec::new()),
+            ty_prettys: snapshot.tys.iter().map(|data| (data.ty, &*data.pretty)).collect(),
+            mir_consts: snapshot
+                .mir_consts
+                .iter()
+                .map(|data| (data.id.to_index(), &*data.pretty))
+                .collect(),
+            ty_consts: snapshot
+                .ty_consts
+                .iter()
+                .map(|data| (data.id.to_index(), &*data.pretty))
+                .collect(),
+            allocs: snapshot.allocs.iter().map(|data| (data.id, &data.alloc)).collect(),
+            spans: snapshot.spans.iter().map(|data| (data.span, data)).collect(),
+        }
+    }
+
+    fn item(&self, def_id: DefId) -> &'a ItemData {
+        self.items.get(&def_id).copied().unwrap_or_else(|| {
+            panic!("item {} is not in the snapshot of the local crate", def_id.to_index())
+        })
+    }
+
+    fn instance(&self, def: InstanceDef) -> &'a InstanceData {
+        self.instances
+            .get(&def)
+            .copied()
+            .unwrap_or_else(|| panic!("instance {} is not in the snapshot", def.to_index()))
+    }
+
+    fn def(&self, def_id: DefId) -> &'a DefData {
+        self.defs
+            .get(&def_id)
+            .copied()
+            .unwrap_or_else(|| panic!("definition {} is not in the snapshot", def_id.to_index()))
+    }
+
+    fn span(&self, span: Span) -> &'a SpanData {
+        self.spans
+            .get(&span)
+            .copied()
+            .unwrap_or_else(|| panic!("span {} is not in the snapshot", span.to_index()))
+    }
+
+    fn krate_of(&self, id: CrateNum) -> Crate {
+        self.snapshot
+            .crates
+            .iter()
+            .chain([&self.snapshot.local_crate])
+            .find(|krate| krate.id == id)
+            .cloned()
+            .unwrap_or_else(|| panic!("crate {id} is not in the snapshot"))
+    }
+}
+
+impl Context for SnapshotContext<'_> {
+    fn entry_fn(&self) -> Option<CrateItem> {
+        self.snapshot.entry_fn
+    }
+
+    fn all_local_items(&self) -> CrateItems {
+        self.snapshot.items.iter().map(|data| data.item).collect()
+    }
+
+    fn mono_item_graph(&self) -> MonoItemGraph {
+        unavailable!("mono_item_graph")
+    }
+
+    fn mir_body(&self, item: DefId) -> Body {
+        self.item(item).body.clone().expect("item has no body")
+    }
+
+    fn has_body(&self, item: DefId) -> bool {
+        self.items.get(&item).is_some_and(|data| data.body.is_some())
+            || self.instances.values().any(|data| data.def_id == item && data.body.is_some())
+    }
+
+    fn foreign_modules(&self, _: CrateNum) -> Vec<ForeignModuleDef> {
+        unavailable!("foreign_modules")
+    }
+
+    fn foreign_module(&self, _: ForeignModuleDef) -> ForeignModule {
+        unavailable!("foreign_module")
+    }
+
+    fn foreign_items(&self, _: ForeignModuleDef) -> Vec<ForeignDef> {
+        unavailable!("foreign_items")
+    }
+
+    fn all_trait_decls(&self) -> TraitDecls {
+        unavailable!("all_trait_decls")
+    }
+
+    fn trait_decls(&self, _: CrateNum) -> TraitDecls {
+        unavailable!("trait_decls")
+    }
+
+    fn trait_decl(&self, _: &TraitDef) -> TraitDecl {
+        unavailable!("trait_decl")
+    }
+
+    fn all_trait_impls(&self) -> ImplTraitDecls {
+        unavailable!("all_trait_impls")
+    }
+
+    fn trait_impls(&self, _: CrateNum) -> ImplTraitDecls {
+        unavailable!("trait_impls")
+    }
+
+    fn trait_impl(&self, _: &ImplDef) -> ImplTrait {
+        unavailable!("trait_impl")
+    }
+
+    fn generics_of(&self, _: DefId) -> Generics {
+        unavailable!("generics_of")
+    }
+
+    fn predicates_of(&self, _: DefId) -> GenericPredicates {
+        unavailable!("predicates_of")
+    }
+
+    fn explicit_predicates_of(&self, _: DefId) -> GenericPredicates {
+        unavailable!("explicit_predicates_of")
+    }
+
+    fn local_crate(&self) -> Crate {
+        self.snapshot.local_crate.clone()
+    }
+
+    fn external_crates(&self) -> Vec<Crate> {
+        self.snapshot.crates.iter().filter(|krate| !krate.is_local).cloned().collect()
+    }
+
+    fn find_crates(&self, name: &str) -> Vec<Crate> {
+        self.snapshot.crates.iter().filter(|krate| krate.name == name).cloned().collect()
+    }
+
+    fn def_name(&self, def_id: DefId, trimmed: bool) -> Symbol {
+        let def = self.def(def_id);
+        if trimmed { def.trimmed_name.clone() } else { def.name.clone() }
+    }
+
+    fn get_attrs_by_path(&self, _: DefId, _: &[Symbol]) -> Vec<Attribute> {
+        unavailable!("get_attrs_by_path")
+    }
+
+    fn get_all_attrs(&self, _: DefId) -> Vec<Attribute> {
+        unavailable!("get_all_attrs")
+    }
+
+    fn span_to_string(&self, span: Span) -> String {
+        self.span(span).pretty.clone()
+    }
+
+    fn get_filename(&self, span: &Span) -> Filename {
+        self.span(*span).filename.clone()
+    }
+
+    fn get_lines(&self, span: &Span) -> LineInfo {
+        self.span(*span).lines
+    }
+
+    fn item_kind(&self, item: CrateItem) -> ItemKind {
+        self.item(item.0).kind
+    }
+
+    fn is_foreign_item(&self, item: DefId) -> bool {
+        self.def(item).is_foreign_item
+    }
+
+    fn foreign_item_kind(&self, _: ForeignDef) -> ForeignItemKind {
+        unavailable!("foreign_item_kind")
+    }
+
+    fn adt_kind(&self, _: AdtDef) -> AdtKind {
+        unavailable!("adt_kind")
+    }
+
+    fn adt_is_box(&self, _: AdtDef) -> bool {
+        unavailable!("adt_is_box")
+    }
+
+    fn adt_is_simd(&self, _: AdtDef) -> bool {
+        unavailable!("adt_is_simd")
+    }
+
+    fn adt_is_cstr(&self, _: AdtDef) -> bool {
+        unavailable!("adt_is_cstr")
+    }
+
+    fn fn_sig(&self, _: FnDef, _: &GenericArgs) -> PolyFnSig {
+        unavailable!("fn_sig")
+    }
+
+    fn intrinsic(&self, item: DefId) -> Option<IntrinsicDef> {
+        self.snapshot
+            .instances
+            .iter()
+            .any(|data| data.def_id == item && data.intrinsic_name.is_some())
+            .then_some(IntrinsicDef(item))
+    }
+
+    fn intrinsic_name(&self, def: IntrinsicDef) -> Symbol {
+        self.snapshot
+            .instances
+            .iter()
+            .find(|data| data.def_id == def.0)
+            .and_then(|data| data.intrinsic_name.clone())
+            .expect("intrinsic is not in the snapshot")
+    }
+
+    fn closure_sig(&self, _: &GenericArgs) -> PolyFnSig {
+        unavailable!("closure_sig")
+    }
+
+    fn adt_variants_len(&self, _: AdtDef) -> usize {
+        unavailable!("adt_variants_len")
+    }
+
+    fn variant_name(&self, _: VariantDef) -> Symbol {
+        unavailable!("variant_name")
+    }
+
+    fn variant_fields(&self, _: VariantDef) -> Vec<FieldDef> {
+        unavailable!("variant_fields")
+    }
+
+    fn eval_target_usize(&self, _: &MirConst) -> Result<u64, Error> {
+        unavailable_error!("eval_target_usize")
+    }
+
+    fn eval_target_usize_ty(&self, _: &TyConst) -> Result<u64, Error> {
+        unavailable_error!("eval_target_usize_ty")
+    }
+
+    fn try_new_const_zst(&self, _: Ty) -> Result<MirConst, Error> {
+        unavailable_error!("try_new_const_zst")
+    }
+
+    fn new_const_str(&self, _: &str) -> MirConst {
+        unavailable!("new_const_str")
+    }
+
+    fn new_const_bool(&self, _: bool) -> MirConst {
+        unavailable!("new_const_bool")
+    }
+
+    fn try_new_const_uint(&self, _: u128, _: UintTy) -> Result<MirConst, Error> {
+        unavailable_error!("try_new_const_uint")
+    }
+
+    fn try_new_ty_const_uint(&self, _: u128, _: UintTy) -> Result<TyConst, Error> {
+        unavailable_error!("try_new_ty_const_uint")
+    }
+
+    fn new_rigid_ty(&self, kind: RigidTy) -> Ty {
+        let kind = TyKind::RigidTy(kind);
+        if let Some(data) = self.snapshot.tys.iter().find(|data| data.kind == kind) {
+            return data.ty;
+        }
+        let mut new_tys = self.new_tys.borrow_mut();
+        if let Some((ty, _)) = new_tys.iter().find(|(_, new_kind)| *new_kind == kind) {
+            return *ty;
+        }
+        let next = self.snapshot.tys.iter().map(|data| data.ty.to_index() + 1).max().unwrap_or(0);
+        let ty = Ty::to_val(next + new_tys.len());
+        new_tys.push((ty, kind));
+        ty
+    }
+
+    fn new_box_ty(&self, _: Ty) -> Ty {
+        unavailable!("new_box_ty")
+    }
+
+    fn def_ty(&self, item: DefId) -> Ty {
+        self.item(item).ty
+    }
+
+    fn def_ty_with_args(&self, _: DefId, _: &GenericArgs) -> Ty {
+        unavailable!("def_ty_with_args")
+    }
+
+    fn mir_const_pretty(&self, cnst: &MirConst) -> String {
+        match self.mir_consts.get(&cnst.id.to_index()) {
+            Some(pretty) => pretty.to_string(),
+            None => format!("{:?}", cnst.kind()),
+        }
+    }
+
+    fn span_of_an_item(&self, def_id: DefId) -> Span {
+        self.def(def_id).span
+    }
+
+    fn ty_const_pretty(&self, ct: TyConstId) -> String {
+        self.ty_consts.get(&ct.to_index()).expect("constant is not in the snapshot").to_string()
+    }
+
+    fn ty_pretty(&self, ty: Ty) -> String {
+        match self.ty_prettys.get(&ty) {
+            Some(pretty) => pretty.to_string(),
+            None => format!("{:?}", self.ty_kind(ty)),
+        }
+    }
+
+    fn ty_kind(&self, ty: Ty) -> TyKind {
+        if let Some(kind) = self.tys.get(&ty) {
+            return (*kind).clone();
+        }
+        self.new_tys
+            .borrow()
+            .iter()
+            .find(|(new_ty, _)| *new_ty == ty)
+            .map(|(_, kind)| kind.clone())
+            .unwrap_or_else(|| panic!("type {} is not in the snapshot", ty.to_index()))
+    }
+
+    fn rigid_ty_discriminant_ty(&self, _: &RigidTy) -> Ty {
+        unavailable!("rigid_ty_discriminant_ty")
+    }
+
+    fn instance_body(&self, instance: InstanceDef) -> Option<Body> {
+        self.instance(instance).body.clone()
+    }
+
+    fn instance_ty(&self, instance: InstanceDef) -> Ty {
+        self.instance(instance).ty
+    }
+
+    fn instance_args(&self, def: InstanceDef) -> GenericArgs {
+        self.instance(def).args.clone()
+    }
+
+    fn instance_def_id(&self, instance: InstanceDef) -> DefId {
+        self.instance(instance).def_id
+    }
+
+    fn instance_mangled_name(&self, instance: InstanceDef) -> Symbol {
+        self.instance(instance).mangled_name.clone()
+    }
+
+    fn is_empty_drop_shim(&self, _: InstanceDef) -> bool {
+        unavailable!("is_empty_drop_shim")
+    }
+
+    fn is_empty_async_drop_ctor_shim(&self, _: InstanceDef) -> bool {
+        unavailable!("is_empty_async_drop_ctor_shim")
+    }
+
+    fn mono_instance(&self, def_id: DefId) -> Instance {
+        self.snapshot
+            .instances
+            .iter()
+            .find(|data| {
+                data.def_id == def_id
+                    && data.instance.kind == InstanceKind::Item
+                    && data.args.0.is_empty()
+            })
+            .map(|data| data.instance)
+            .expect("instance is not in the snapshot")
+    }
+
+    fn requires_monomorphization(&self, def_id: DefId) -> bool {
+        self.def(def_id).requires_monomorphization
+    }
+
+    fn resolve_instance(&self, def: FnDef, args: &GenericArgs) -> Option<Instance> {
+        self.resolutions
+            .get(&def)?
+            .iter()
+            .find(|(resolved_args, _)| *resolved_args == args)
+            .map(|(_, instance)| *instance)
+    }
+
+    fn resolve_drop_in_place(&self, _: Ty) -> Instance {
+        unavailable!("resolve_drop_in_place")
+    }
+
+    fn resolve_for_fn_ptr(&self, _: FnDef, _: &GenericArgs) -> Option<Instance> {
+        unavailable!("resolve_for_fn_ptr")
+    }
+
+    fn resolve_closure(&self, _: ClosureDef, _: &GenericArgs, _: ClosureKind) -> Option<Instance> {
+        unavailable!("resolve_closure")
+    }
+
+    fn eval_static_initializer(&self, _: StaticDef) -> Result<Allocation, Error> {
+        unavailable_error!("eval_static_initializer")
+    }
+
+    fn eval_instance(&self, _: InstanceDef, _: Ty) -> Result<Allocation, Error> {
+        unavailable_error!("eval_instance")
+    }
+
+    fn global_alloc(&self, id: AllocId) -> GlobalAlloc {
+        self.allocs.get(&id).copied().expect("allocation is not in the snapshot").clone()
+    }
+
+    fn vtable_allocation(&self, _: &GlobalAlloc) -> Option<AllocId> {
+        unavailable!("vtable_allocation")
+    }
+
+    fn krate(&self, def_id: DefId) -> Crate {
+        self.krate_of(self.def(def_id).krate)
+    }
+
+    fn instance_name(&self, def: InstanceDef, trimmed: bool) -> Symbol {
+        let instance = self.instance(def);
+        if trimmed { instance.trimmed_name.clone() } else { instance.name.clone() }
+    }
+
+    fn target_info(&self) -> MachineInfo {
+        self.snapshot.target.clone()
+    }
+
+    fn emit_diagnostic(&self, _: &Diagnostic) {
+        unavailable!("emit_diagnostic")
+    }
+
+    fn instance_abi(&self, _: InstanceDef) -> Result<FnAbi, Error> {
+        unavailable_error!("instance_abi")
+    }
+
+    fn fn_ptr_abi(&self, _: PolyFnSig) -> Result<FnAbi, Error> {
+        unavailable_error!("fn_ptr_abi")
+    }
+
+    fn ty_layout(&self, _: Ty) -> Result<Layout, Error> {
+        unavailable_error!("ty_layout")
+    }
+
+    fn layout_shape(&self, _: Layout) -> LayoutShape {
+        unavailable!("layout_shape")
+    }
+
+    fn place_pretty(&self, place: &Place) -> String {
+        // Like the `Debug` output of the places of the compiler.
+        let mut pretty = String::new();
+        for elem in place.projection.iter().rev() {
+            match elem {
+                ProjectionElem::Deref => pretty.push_str("(*"),
+                ProjectionElem::Field(..)
+                | ProjectionElem::Downcast(_)
+                | ProjectionElem::OpaqueCast(_)
+                | ProjectionElem::Subtype(_) => pretty.push('('),
+                ProjectionElem::Index(_)
+                | ProjectionElem::ConstantIndex { .. }
+                | ProjectionElem::Subslice { .. } => {}
+            }
+        }
+        let _ = write!(pretty, "_{}", place.local);
+        for elem in &place.projection {
+            let _ = match elem {
+                ProjectionElem::Deref => write!(pretty, ")"),
+                ProjectionElem::Field(field, ty) => {
+                    write!(pretty, ".{field}: {})", self.ty_pretty(*ty))
+                }
+                ProjectionElem::Index(local) => write!(pretty, "[_{local}]"),
+                ProjectionElem::ConstantIndex { offset, min_length, from_end: false } => {
+                    write!(pretty, "[{offset} of {min_length}]")
+                }
+                ProjectionElem::ConstantIndex { offset, min_length, from_end: true } => {
+                    write!(pretty, "[-{offset} of {min_length}]")
+                }
+                ProjectionElem::Subslice { from, to: 0, from_end: true } => {
+                    write!(pretty, "[{from}:]")
+                }
+                ProjectionElem::Subslice { from: 0, to, from_end: true } => {
+                    write!(pretty, "[:-{to}]")
+                }
+                ProjectionElem::Subslice { from, to, from_end: true } => {
+                    write!(pretty, "[{from}:-{to}]")
+                }
+                ProjectionElem::Subslice { from, to, from_end: false } => {
+                    write!(pretty, "[{from}..{to}]")
+                }
+                ProjectionElem::Downcast(variant) => {
+                    write!(pretty, " as variant#{})", variant.to_index())
+                }
+                ProjectionElem::OpaqueCast(ty) => write!(pretty, " as {})", self.ty_pretty(*ty)),
+                ProjectionElem::Subtype(ty) => {
+                    write!(pretty, " as subtype {})", self.ty_pretty(*ty))
+                }
+            };
+        }
+        pretty
+    }
+
+    fn binop_ty(&self, bin_op: BinOp, lhs: Ty, _: Ty) -> Ty {
+        match bin_op {
+            BinOp::Add
+            | BinOp::AddUnchecked
+            | BinOp::Sub
+            | BinOp::SubUnchecked
+            | BinOp::Mul
+            | BinOp::MulUnchecked
+            | BinOp::Div
+            | BinOp::Rem
+            | BinOp::BitXor
+            | BinOp::BitAnd
+            | BinOp::BitOr
+            | BinOp::Shl
+            | BinOp::ShlUnchecked
+            | BinOp::Shr
+            | BinOp::ShrUnchecked
+            | BinOp::Offset => lhs,
+            BinOp::Eq | BinOp::Lt | BinOp::Le | BinOp::Ne | BinOp::Ge | BinOp::Gt => {
+                self.new_rigid_ty(RigidTy::Bool)
+            }
+            // The type of `Ordering` is a lang item of `core`.
+            BinOp::Cmp => unavailable!("binop_ty"),
+        }
+    }
+
+    fn unop_ty(&self, un_op: UnOp, arg: Ty) -> Ty {
+        match un_op {
+            UnOp::Not | UnOp::Neg => arg,
+            UnOp::PtrMetadata => unavailable!("unop_ty"),
+        }
+    }
+}
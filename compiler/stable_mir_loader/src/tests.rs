@@ -0,0 +1,117 @@
+use stable_mir::mir::{
+    BasicBlock, Body, LocalDecl, Mutability, Operand, Place, Rvalue, Statement, StatementKind,
+    Terminator, TerminatorKind,
+};
+use stable_mir::snapshot::{CrateSnapshot, DefData, Format, ItemData, TyData, FORMAT_VERSION};
+use stable_mir::target::{Endian, MachineInfo, MachineSize};
+use stable_mir::ty::{FnDef, GenericArgs, IndexedVal, RigidTy, Span, Ty, TyKind, UintTy};
+use stable_mir::{Crate, CrateDef, CrateItem, DefId, ItemKind};
+
+/// Returns the snapshot of a crate with `fn identity(x: u32) -> u32 { x }`.
+fn identity_snapshot() -> CrateSnapshot {
+    let def_id = DefId::to_val(0);
+    let u32_ty = Ty::to_val(0);
+    let fn_ty = Ty::to_val(1);
+    let span = Span::to_val(0);
+    let local = |ty| LocalDecl { ty, span, mutability: Mutability::Not };
+    let body = Body::new(
+        vec![BasicBlock {
+            statements: vec![Statement {
+                kind: StatementKind::Assign(
+                    Place::from(0),
+                    Rvalue::Use(Operand::Copy(Place::from(1))),
+                ),
+                span,
+            }],
+            terminator: Terminator { kind: TerminatorKind::Return, span },
+        }],
+        vec![local(u32_ty), local(u32_ty)],
+        1,
+        vec![],
+        None,
+        span,
+    );
+    let local_crate = Crate { id: 0, name: "input".to_owned(), is_local: true };
+    CrateSnapshot {
+        format_version: FORMAT_VERSION,
+        local_crate,
+        crates: vec![],
+        target: MachineInfo { endian: Endian::Little, pointer_width: MachineSize::from_bits(64) },
+        entry_fn: None,
+        items: vec![ItemData {
+            item: CrateItem(def_id),
+            kind: ItemKind::Fn,
+            ty: fn_ty,
+            body: Some(body),
+        }],
+        instances: vec![],
+        resolutions: vec![],
+        defs: vec![DefData {
+            def_id,
+            name: "identity".to_owned(),
+            trimmed_name: "identity".to_owned(),
+            krate: 0,
+            span,
+            is_foreign_item: false,
+            requires_monomorphization: false,
+        }],
+        tys: vec![
+            TyData {
+                ty: u32_ty,
+                kind: TyKind::RigidTy(RigidTy::Uint(UintTy::U32)),
+                pretty: "u32".to_owned(),
+            },
+            TyData {
+                ty: fn_ty,
+                kind: TyKind::RigidTy(RigidTy::FnDef(FnDef(def_id), GenericArgs(vec![]))),
+                pretty: "fn(u32) -> u32 {identity}".to_owned(),
+            },
+        ],
+        mir_consts: vec![],
+        ty_consts: vec![],
+        allocs: vec![],
+        spans: vec![],
+    }
+}
+
+/// Queries the items of `identity_snapshot`, while StableMIR runs.
+fn check_identity() {
+    let items = stable_mir::all_local_items();
+    assert_eq!(items.len(), 1);
+    let item = items[0];
+    assert_eq!(item.name(), "identity");
+    assert_eq!(item.kind(), ItemKind::Fn);
+    assert!(matches!(item.ty().kind(), TyKind::RigidTy(RigidTy::FnDef(def, _)) if def.0 == item.0));
+
+    let body = item.body();
+    assert_eq!(body.arg_locals().len(), 1);
+    assert_eq!(body.ret_local().ty.kind(), TyKind::RigidTy(RigidTy::Uint(UintTy::U32)));
+    assert_eq!(body.blocks.len(), 1);
+    assert_eq!(body.blocks[0].terminator.kind, TerminatorKind::Return);
+    assert_eq!(body.ret_local().ty.to_string(), "u32");
+}
+
+#[test]
+fn run_snapshot() {
+    crate::run(&identity_snapshot(), check_identity).unwrap();
+}
+
+#[test]
+fn run_read_snapshot() {
+    for format in [Format::Json, Format::Binary] {
+        let mut bytes = Vec::new();
+        identity_snapshot().write(&mut bytes, format).unwrap();
+        let snapshot = CrateSnapshot::read(&bytes[..]).unwrap();
+        crate::run(&snapshot, check_identity).unwrap();
+    }
+}
+
+#[test]
+fn read_oversized_length() {
+    // A binary snapshot of format version 1 with a local crate whose name claims to be far longer
+    // than the snapshot, in the 8 bytes following the marker 253.
+    let mut bytes = b"SMIRSNAP".to_vec();
+    bytes.extend([1, 0, 253]);
+    bytes.extend((1u64 << 40).to_le_bytes());
+    assert!(CrateSnapshot::read(&bytes[..]).is_err());
+}
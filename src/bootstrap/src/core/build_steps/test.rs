@@ -1385,6 +1385,52 @@ fn run(self, builder: &Builder<'_>) {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CrateStableMirLoader {
+    host: TargetSelection,
+}
+
+impl Step for CrateStableMirLoader {
+    type Output = ();
+    const ONLY_HOSTS: bool = true;
+
+    fn should_run(run: ShouldRun<'_>) -> ShouldRun<'_> {
+        run.path("compiler/stable_mir_loader")
+    }
+
+    fn make_run(run: RunConfig<'_>) {
+        run.builder.ensure(CrateStableMirLoader { host: run.target });
+    }
+
+    /// Runs `cargo test` for stable_mir_loader, which doesn't depend on the compiler.
+    fn run(self, builder: &Builder<'_>) {
+        let host = self.host;
+        let compiler = builder.compiler(0, host);
+
+        let mut cargo = tool::prepare_tool_cargo(
+            builder,
+            compiler,
+            Mode::ToolBootstrap,
+            host,
+            Kind::Test,
+            "compiler/stable_mir_loader",
+            SourceType::InTree,
+            &[],
+        );
+        cargo.allow_features("test");
+        run_cargo_test(
+            cargo,
+            &[],
+            &[],
+            "stable_mir_loader",
+            "stable_mir_loader self test",
+            compiler,
+            host,
+            builder,
+        );
+    }
+}
+
 default_test!(Ui { path: "tests/ui", mode: "ui", suite: "ui" });
 
 default_test!(Crashes { path: "tests/crashes", mode: "crashes", suite: "crashes" });
@@ -846,6 +846,7 @@ macro_rules! describe {
                 check::Rustfmt,
                 check::RustAnalyzer,
                 check::TestFloatParse,
+                check::StableMirLoader,
                 check::Bootstrap,
             ),
             Kind::Test => describe!(
@@ -899,6 +900,7 @@ macro_rules! describe {
                 test::CompiletestTest,
                 test::CrateRunMakeSupport,
                 test::CrateBuildHelper,
+                test::CrateStableMirLoader,
                 test::RustdocJSStd,
                 test::RustdocJSNotStd,
                 test::RustdocGUI,
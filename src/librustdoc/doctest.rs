@@ -122,7 +122,7 @@ pub(crate) fn run(dcx: DiagCtxtHandle<'_>, options: RustdocOptions) -> Result<()
         edition: options.edition,
         target_triple: options.target.clone(),
         crate_name: options.crate_name.clone(),
-        remap_path_prefix: options.remap_path_prefix.clone(),
+        remap_path_prefix: config::RemapPathPrefix(options.remap_path_prefix.clone()),
         ..config::Options::default()
     };
 
@@ -250,6 +250,7 @@
     "ar_archive_writer",
     "arrayvec",
     "autocfg",
+    "bincode",
     "bitflags",
     "block-buffer",
     "byteorder", // via ruzstd in object in thorin-dwp
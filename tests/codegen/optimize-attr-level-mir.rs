@@ -0,0 +1,28 @@
+// The optimization level of a function sets the MIR optimizations run on it: the MIR inliner
+// doesn't run at `level = "1"`.
+
+//@ compile-flags: -Copt-level=3 -Cno-prepopulate-passes
+
+#![feature(optimize_attribute)]
+#![crate_type = "lib"]
+
+#[inline]
+fn helper(x: i32) -> i32 {
+    x.wrapping_add(1)
+}
+
+// CHECK-LABEL: define{{.*}}i32 @inlined
+// CHECK-NOT: call{{.*}}helper
+// CHECK: ret i32
+#[no_mangle]
+pub fn inlined(x: i32) -> i32 {
+    helper(x)
+}
+
+// CHECK-LABEL: define{{.*}}i32 @less
+// CHECK: call{{.*}}helper
+#[optimize(level = "1")]
+#[no_mangle]
+pub fn less(x: i32) -> i32 {
+    helper(x)
+}
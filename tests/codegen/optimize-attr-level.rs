@@ -0,0 +1,55 @@
+//@ compile-flags: -Copt-level=3 -Ccodegen-units=1
+
+#![feature(optimize_attribute)]
+#![crate_type = "rlib"]
+
+#[no_mangle]
+pub fn helper(x: i32) -> i32 {
+    x + 1
+}
+
+// CHECK-LABEL: define{{.*}}i32 @nothing
+// CHECK-SAME: [[NOTHING_ATTRS:#[0-9]+]]
+#[no_mangle]
+pub fn nothing(x: i32) -> i32 {
+    helper(x) ^ 1
+}
+
+// Neither the MIR inliner nor LLVM inline into functions that are not optimized.
+// CHECK-LABEL: define{{.*}}i32 @no
+// CHECK-SAME: [[NO_ATTRS:#[0-9]+]]
+// CHECK: call{{.*}}@helper
+#[optimize(level = "0")]
+#[no_mangle]
+pub fn no(x: i32) -> i32 {
+    helper(x)
+}
+
+// CHECK-LABEL: define{{.*}}i32 @aggressive
+// CHECK-SAME: [[NOTHING_ATTRS]]
+#[optimize(level = "3")]
+#[no_mangle]
+pub fn aggressive(x: i32) -> i32 {
+    helper(x) ^ 3
+}
+
+// CHECK-LABEL: define{{.*}}i32 @size
+// CHECK-SAME: [[SIZE_ATTRS:#[0-9]+]]
+#[optimize(level = "s")]
+#[no_mangle]
+pub fn size(x: i32) -> i32 {
+    helper(x) ^ 5
+}
+
+// CHECK-LABEL: define{{.*}}i32 @size_min
+// CHECK-SAME: [[SIZE_MIN_ATTRS:#[0-9]+]]
+#[optimize(level = "z")]
+#[no_mangle]
+pub fn size_min(x: i32) -> i32 {
+    helper(x) ^ 7
+}
+
+// CHECK-DAG: attributes [[NOTHING_ATTRS]] = {{.*}}
+// CHECK-DAG: attributes [[NO_ATTRS]] = {{.*}}noinline{{.*}}optnone{{.*}}
+// CHECK-DAG: attributes [[SIZE_ATTRS]] = {{.*}}optsize{{.*}}
+// CHECK-DAG: attributes [[SIZE_MIN_ATTRS]] = {{.*}}minsize{{.*}}optsize{{.*}}
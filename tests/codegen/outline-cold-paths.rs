@@ -0,0 +1,44 @@
+// The formatting of a panic message is outlined from the function which panics, with the call to
+// the panic function, into a cold path, and so is the failure of a bounds check.
+
+//@ compile-flags: -O -C no-prepopulate-passes -Z outline-cold-paths
+
+#![crate_type = "lib"]
+
+// CHECK: define {{.*}}@{{[^(]*}}panic_fmt{{[^(]*}}cold{{[^(]*}}path{{.*}} #[[ATTRS:[0-9]+]]
+// CHECK: call void @{{.*}}panic_fmt
+
+// CHECK-LABEL: define {{.*}}i32 @{{.*}}check_positive
+// CHECK-NOT: panic_fmt
+// CHECK: call void @{{[^(]*}}panic_fmt{{[^(]*}}cold{{[^(]*}}path{{[^(]*}}(i32
+// CHECK-NOT: panic_fmt
+// CHECK: ret i32
+#[inline(never)]
+pub fn check_positive(x: i32) -> i32 {
+    if x <= 0 {
+        panic!("{x} is out of range");
+    }
+    x
+}
+
+// CHECK-LABEL: define {{.*}}i32 @{{.*}}check_small
+// CHECK-NOT: panic_fmt
+// CHECK: call void @{{[^(]*}}panic_fmt{{[^(]*}}cold{{[^(]*}}path{{[^(]*}}(i32
+// CHECK-NOT: panic_fmt
+// CHECK: ret i32
+#[inline(never)]
+pub fn check_small(x: i32) -> i32 {
+    if x >= 100 {
+        panic!("{x} is out of range");
+    }
+    x
+}
+
+// CHECK-LABEL: define {{.*}}i32 @{{.*}}nth
+// CHECK: call void @{{.*}}panic_bounds_check{{[^(]*}}cold{{[^(]*}}path{{[^(]*}}(i64
+#[inline(never)]
+pub fn nth(values: &[u32], n: usize) -> u32 {
+    values[n]
+}
+
+// CHECK: attributes #[[ATTRS]] = { {{.*}}cold{{.*}}noinline
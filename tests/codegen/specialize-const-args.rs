@@ -0,0 +1,35 @@
+// Calls passing constants that decide branches of a local function call a clone of the function
+// specialized for these constants, without the branches that are never taken.
+
+//@ compile-flags: -O -C no-prepopulate-passes
+
+#![crate_type = "lib"]
+
+pub enum Mode {
+    Strict,
+    Lenient,
+}
+
+extern "Rust" {
+    fn strict(input: &[u8]) -> usize;
+    fn lenient(input: &[u8]) -> usize;
+}
+
+#[inline(never)]
+fn parse(input: &[u8], mode: Mode) -> usize {
+    match mode {
+        Mode::Strict => unsafe { strict(input) },
+        Mode::Lenient => unsafe { lenient(input) },
+    }
+}
+
+#[no_mangle]
+pub fn parse_strict(input: &[u8]) -> usize {
+    parse(input, Mode::Strict)
+}
+
+// CHECK: define {{.*}}parse{{.*}}const{{.*}}args
+// CHECK-NOT: lenient
+// CHECK: call {{.*}}strict
+// CHECK-NOT: lenient
+// CHECK: ret i{{[0-9]+}}
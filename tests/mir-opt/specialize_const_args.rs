@@ -0,0 +1,52 @@
+//@ test-mir-pass: SpecializeConstArgs
+//@ compile-flags: --crate-type=lib -C panic=abort
+
+// Check that calls passing constants for the `bool` and enum arguments deciding branches of their
+// callee are redirected to clones of the callee specialized for these constants, unless the
+// callee could have too many clones.
+
+// EMIT_MIR specialize_const_args.specialized.SpecializeConstArgs.diff
+pub fn specialized(x: u32) -> u32 {
+    // CHECK-LABEL: fn specialized(
+    // CHECK: = const parse as fn(u32, bool) -> u32(move {{_.*}}, const true)
+    parse(x, true)
+}
+
+#[inline(never)]
+fn parse(input: u32, strict: bool) -> u32 {
+    if strict { input } else { 0 }
+}
+
+// EMIT_MIR specialize_const_args.too_many_clones.SpecializeConstArgs.diff
+pub fn too_many_clones(x: u32) -> u32 {
+    // CHECK-LABEL: fn too_many_clones(
+    // CHECK: = flags(move {{_.*}}, const true, const true, const true, const true)
+    flags(x, true, true, true, true)
+}
+
+#[inline(never)]
+fn flags(x: u32, a: bool, b: bool, c: bool, d: bool) -> u32 {
+    if a {
+        x
+    } else if b {
+        1
+    } else if c {
+        2
+    } else if d {
+        3
+    } else {
+        4
+    }
+}
+
+// EMIT_MIR specialize_const_args.integer.SpecializeConstArgs.diff
+pub fn integer(x: u32) -> u32 {
+    // CHECK-LABEL: fn integer(
+    // CHECK: = level(move {{_.*}}, const 0_u32)
+    level(x, 0)
+}
+
+#[inline(never)]
+fn level(x: u32, level: u32) -> u32 {
+    if level == 0 { x } else { 0 }
+}
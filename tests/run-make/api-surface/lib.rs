@@ -0,0 +1,28 @@
+pub struct Point {
+    pub x: i32,
+    y: i32,
+}
+
+impl Point {
+    pub fn new(x: i32) -> Point {
+        Point { x, y: 0 }
+    }
+
+    fn private(&self) -> i32 {
+        self.y
+    }
+}
+
+pub trait Shape: Clone {
+    fn area(&self) -> f64;
+}
+
+mod private {
+    pub struct Hidden;
+}
+
+pub fn leak() -> private::Hidden {
+    private::Hidden
+}
+
+fn internal() {}
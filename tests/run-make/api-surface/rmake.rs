@@ -0,0 +1,34 @@
+// `--emit=api-surface` lists the items other crates can reach, with their signatures, one per
+// line.
+
+//@ ignore-cross-compile
+
+use run_make_support::{rfs, rustc};
+
+fn main() {
+    rustc()
+        .input("lib.rs")
+        .crate_type("lib")
+        .emit("api-surface")
+        .run_fail()
+        .assert_stderr_contains("api-surface requested but -Zunstable-options not specified");
+
+    rustc().input("lib.rs").crate_type("lib").emit("api-surface").arg("-Zunstable-options").run();
+    let surface = rfs::read_to_string("lib.api-surface.txt");
+    let items: Vec<&str> = surface.lines().collect();
+    let mut sorted = items.clone();
+    sorted.sort();
+    assert_eq!(items, sorted);
+
+    assert!(items.contains(&"struct Point"), "{surface}");
+    assert!(items.contains(&"field Point::x: i32"), "{surface}");
+    assert!(items.contains(&"fn Point::new: fn(i32) -> Point"), "{surface}");
+    assert!(items.iter().any(|item| item.starts_with("trait Shape where ")), "{surface}");
+    // Reachable through the signature of `leak`, even though it can't be named.
+    assert!(items.contains(&"struct private::Hidden"), "{surface}");
+    assert!(items.contains(&"fn leak: fn() -> private::Hidden"), "{surface}");
+
+    assert!(!surface.contains("Point::y"), "{surface}");
+    assert!(!surface.contains("Point::private"), "{surface}");
+    assert!(!surface.contains("internal"), "{surface}");
+}
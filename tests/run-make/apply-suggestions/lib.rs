@@ -0,0 +1,6 @@
+use std::collections::HashMap;
+
+pub fn answer() -> u32 {
+    let mut answer = 42;
+    answer
+}
@@ -0,0 +1,30 @@
+// `-Z apply-suggestions` applies the machine-applicable suggestions of the listed lints, or of
+// all diagnostics, to the source files. With `-Z apply-suggestions-dry-run`, the changes are
+// written to a file as a diff instead.
+
+use run_make_support::{rfs, rustc};
+
+fn main() {
+    let original = rfs::read_to_string("lib.rs");
+
+    rustc()
+        .input("lib.rs")
+        .crate_type("lib")
+        .arg("-Zapply-suggestions=unused-mut")
+        .arg("-Zapply-suggestions-dry-run=lib.diff")
+        .run()
+        .assert_stdout_not_contains("lib.rs");
+    let diff = rfs::read_to_string("lib.diff");
+    assert!(diff.starts_with("--- a/lib.rs\n+++ b/lib.rs\n"), "{diff}");
+    assert!(diff.contains("-    let mut answer = 42;\n+    let answer = 42;\n"), "{diff}");
+    assert!(!diff.contains("HashMap"), "{diff}");
+    assert_eq!(rfs::read_to_string("lib.rs"), original);
+
+    rustc().input("lib.rs").crate_type("lib").arg("-Zapply-suggestions").run();
+    let fixed = rfs::read_to_string("lib.rs");
+    assert!(!fixed.contains("HashMap"), "{fixed}");
+    assert!(fixed.contains("    let answer = 42;\n"), "{fixed}");
+
+    // Nothing is left to fix.
+    rustc().input("lib.rs").crate_type("lib").arg("-Dwarnings").run();
+}
@@ -0,0 +1,40 @@
+// `-Z export-dep-graph` writes the dependency graphs of the previous and of the current
+// incremental session as JSON, with the reason each re-executed node was re-executed.
+
+//@ ignore-cross-compile
+
+use run_make_support::{rfs, rustc};
+
+fn main() {
+    rfs::create_dir("src");
+    rfs::create_dir("incr");
+    rfs::copy("a.rs", "src/lib.rs");
+    rustc().incremental("incr").input("src/lib.rs").crate_type("lib").run();
+
+    // Compiling again without changes marks the nodes green, nothing is re-executed because of
+    // a changed dependency.
+    rustc()
+        .incremental("incr")
+        .input("src/lib.rs")
+        .crate_type("lib")
+        .arg("-Zexport-dep-graph=unchanged.json")
+        .run();
+    let graph = rfs::read_to_string("unchanged.json");
+    assert!(graph.starts_with("{\"version\":1,\"previous\":[\n"), "{graph}");
+    assert!(graph.contains("\n],\"current\":[\n"), "{graph}");
+    assert!(graph.contains("\"status\":\"green\""), "{graph}");
+    assert!(!graph.contains("\"dependency_changed\""), "{graph}");
+
+    rfs::copy("b.rs", "src/lib.rs");
+    rustc()
+        .incremental("incr")
+        .input("src/lib.rs")
+        .crate_type("lib")
+        .arg("-Zexport-dep-graph=changed.json")
+        .run();
+    let graph = rfs::read_to_string("changed.json");
+    assert!(graph.contains("\"status\":\"green\""), "{graph}");
+    assert!(graph.contains("\"status\":\"changed\""), "{graph}");
+    assert!(graph.contains("\"kind\":\"dependency_changed\""), "{graph}");
+    assert!(graph.contains("\"kind\":\"eval_always\""), "{graph}");
+}
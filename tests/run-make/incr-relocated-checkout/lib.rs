@@ -0,0 +1,6 @@
+#![feature(rustc_attrs)]
+#![rustc_partition_reused(module = "reloc", cfg = "second")]
+
+pub fn answer() -> u32 {
+    42
+}
@@ -0,0 +1,34 @@
+// The incremental compilation cache of a crate can be reused from another checkout location, as
+// long as the checkout directory is remapped to the same prefix with `--remap-path-prefix`.
+
+//@ ignore-cross-compile
+
+use run_make_support::{cwd, rfs, rustc};
+
+fn compile(checkout: &str) -> String {
+    let checkout_dir = cwd().join(checkout);
+    rustc()
+        .current_dir(&checkout_dir)
+        .input("src/lib.rs")
+        .crate_type("lib")
+        .crate_name("reloc")
+        .incremental(cwd().join("incr"))
+        .remap_path_prefix(&checkout_dir, "/checkout")
+        .cfg(checkout)
+        .arg("-Zquery-dep-graph")
+        .arg("-Zincremental-info")
+        .run()
+        .stderr_utf8()
+}
+
+fn main() {
+    for checkout in ["first", "second"] {
+        rfs::create_dir_all(format!("{checkout}/src"));
+        rfs::copy("lib.rs", format!("{checkout}/src/lib.rs"));
+    }
+
+    compile("first");
+    // `rustc_partition_reused` makes sure that the code generated in the first checkout is reused.
+    let stderr = compile("second");
+    assert!(!stderr.contains("completely ignoring cache"), "{stderr}");
+}
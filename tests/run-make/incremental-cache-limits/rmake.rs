@@ -0,0 +1,45 @@
+// `--print incremental-cache-usage` reports the disk usage of the incremental caches of all
+// crates in an incremental directory, and `-Z incremental-cache-max-size` deletes the least
+// recently used ones, except the one of the crate being compiled, to enforce a limit.
+
+//@ ignore-cross-compile
+
+use run_make_support::rustc;
+
+fn usage() -> Vec<String> {
+    rustc()
+        .incremental("incr")
+        .print("incremental-cache-usage")
+        .arg("-Zunstable-options")
+        .run()
+        .stdout_utf8()
+        .lines()
+        .map(|line| line.split('\t').last().unwrap().to_owned())
+        .collect()
+}
+
+fn main() {
+    // An incremental directory that does not exist yet is empty.
+    assert_eq!(usage(), ["total"]);
+
+    for crate_name in ["first", "second", "third"] {
+        rustc().incremental("incr").input("lib.rs").crate_type("lib").crate_name(crate_name).run();
+    }
+    let crates = usage();
+    assert_eq!(crates.len(), 4, "{crates:?}");
+    // The most recently used crate comes first.
+    assert!(crates[0].starts_with("third-"), "{crates:?}");
+    assert!(crates[1].starts_with("second-"), "{crates:?}");
+    assert!(crates[2].starts_with("first-"), "{crates:?}");
+
+    rustc()
+        .incremental("incr")
+        .input("lib.rs")
+        .crate_type("lib")
+        .crate_name("fourth")
+        .arg("-Zincremental-cache-max-size=1")
+        .run();
+    let crates = usage();
+    assert_eq!(crates.len(), 2, "{crates:?}");
+    assert!(crates[0].starts_with("fourth-"), "{crates:?}");
+}
@@ -0,0 +1,11 @@
+pub fn hot(values: &[u32]) -> u32 {
+    values.iter().map(|value| value.wrapping_mul(3)).fold(0, u32::wrapping_add)
+}
+
+pub fn cold(values: &[u32]) -> u32 {
+    values.iter().map(|value| value.wrapping_mul(5)).fold(0, u32::wrapping_add)
+}
+
+pub fn caller(values: &[u32]) -> u32 {
+    hot(values) ^ cold(values)
+}
@@ -0,0 +1,43 @@
+// `-Z inline-mir-profile` makes the MIR inliner inline the functions that are hot according to the
+// given profile, even when their cost is above the usual threshold.
+
+//@ ignore-cross-compile
+
+use run_make_support::{rfs, rustc};
+
+fn compile(profile: Option<&str>) -> String {
+    let mut rustc = rustc();
+    rustc
+        .input("lib.rs")
+        .crate_type("lib")
+        .emit("obj,opt-remarks")
+        .opt_level("3")
+        .arg("-Zinline-mir-threshold=0")
+        .arg("-Zinline-mir-hint-threshold=0");
+    if let Some(profile) = profile {
+        rustc.arg(format!("-Zinline-mir-profile={profile}"));
+    }
+    rustc.run();
+    rfs::read_to_string("lib.opt-remarks.json")
+}
+
+fn main() {
+    let remarks = compile(None);
+    assert!(remarks.contains("`hot` not inlined into `caller`: cost above threshold"), "{remarks}");
+
+    let remarks = compile(Some("profile.txt"));
+    assert!(remarks.contains("`hot` inlined into `caller`"), "{remarks}");
+    assert!(
+        remarks.contains("`cold` not inlined into `caller`: cost above threshold"),
+        "{remarks}"
+    );
+
+    rfs::write("malformed.txt", "1000 lib::hot\nlib::cold\n");
+    rustc()
+        .input("lib.rs")
+        .crate_type("lib")
+        .opt_level("3")
+        .arg("-Zinline-mir-profile=malformed.txt")
+        .run_fail()
+        .assert_stderr_contains("malformed line 2 in the MIR inliner profile `malformed.txt`");
+}
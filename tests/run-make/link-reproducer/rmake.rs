@@ -0,0 +1,35 @@
+// Check that `-Z link-reproducer` writes a directory from which the linker invocation can be
+// replayed, after rustc removed its temporary files, that it is pointed to when linking fails, and
+// that directories it didn't write are not replaced.
+
+//@ ignore-windows
+//@ ignore-cross-compile
+
+use run_make_support::{cmd, rfs, rustc};
+
+fn main() {
+    rustc().input("main.rs").arg("-Zlink-reproducer=repro").run();
+    assert!(rfs::read_dir("repro/main.link-reproducer/inputs").count() > 0);
+
+    rfs::remove_file("main");
+    cmd("repro/main.link-reproducer/link.sh").run();
+    cmd("repro/main.link-reproducer/output/main").run().assert_stdout_contains("replayed");
+
+    rustc()
+        .input("main.rs")
+        .arg("-Zlink-reproducer=repro")
+        .arg("-Clink-arg=-lthis_library_does_not_exist")
+        .run_fail()
+        .assert_stderr_contains("the linker invocation can be replayed by running `link.sh`");
+    cmd("repro/main.link-reproducer/link.sh").run_fail();
+
+    // A directory which isn't a reproducer is not replaced.
+    rfs::create_dir_all("not-a-reproducer/main.link-reproducer");
+    rfs::write("not-a-reproducer/main.link-reproducer/file", "kept");
+    rustc()
+        .input("main.rs")
+        .arg("-Zlink-reproducer=not-a-reproducer")
+        .run_fail()
+        .assert_stderr_contains("failed to write the reproducer of the linker invocation");
+    assert_eq!(rfs::read_to_string("not-a-reproducer/main.link-reproducer/file"), "kept");
+}
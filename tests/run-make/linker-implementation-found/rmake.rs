@@ -0,0 +1,34 @@
+// Check that a linker implementation selected with `-C linker-implementation` is found in the
+// `PATH`, and that the C/C++ compiler invoking the linker is asked to use it.
+
+//@ only-linux
+//@ ignore-cross-compile
+
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+
+use run_make_support::{cwd, rfs, rustc};
+
+fn write_script(path: &Path, contents: &str) {
+    rfs::write(path, contents);
+    rfs::set_permissions(path, std::fs::Permissions::from_mode(0o755));
+}
+
+fn main() {
+    // The linker implementation is never run by the fake C/C++ compiler, which only records the
+    // arguments it is given.
+    rfs::create_dir("bin");
+    let bin = cwd().join("bin");
+    let args = cwd().join("args.txt");
+    write_script(&bin.join("ld.mold"), "#!/bin/sh\nexit 1\n");
+    write_script(&bin.join("fake-cc"), &format!("#!/bin/sh\necho \"$@\" > {}\n", args.display()));
+
+    rustc()
+        .input("main.rs")
+        .arg("-Zunstable-options")
+        .arg("-Clinker-implementation=mold")
+        .linker(bin.join("fake-cc").to_str().unwrap())
+        .env("PATH", &bin)
+        .run();
+    assert!(rfs::read_to_string(&args).contains("-fuse-ld=mold"));
+}
@@ -0,0 +1,22 @@
+// Check that selecting a linker implementation with `-C linker-implementation` reports a clear
+// error when it is not installed, instead of failing in the linker.
+
+//@ only-linux
+//@ ignore-cross-compile
+
+use run_make_support::{rfs, rustc};
+
+fn main() {
+    // Nothing can be found in an empty `PATH`.
+    rfs::create_dir("empty");
+    let empty = std::env::current_dir().unwrap().join("empty");
+    rustc()
+        .input("main.rs")
+        .arg("-Zunstable-options")
+        .arg("-Clinker-implementation=mold")
+        .env("PATH", &empty)
+        .run_fail()
+        .assert_stderr_contains(
+            "the `mold` linker implementation was selected, but `ld.mold` was not found",
+        );
+}
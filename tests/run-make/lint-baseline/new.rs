@@ -0,0 +1,9 @@
+// The baselined warning moved and a new one was added.
+
+pub fn new() {
+    let fresh = 2;
+}
+
+pub fn old() {
+        let unused = 1;
+}
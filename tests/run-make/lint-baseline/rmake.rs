@@ -0,0 +1,46 @@
+// `--lint-baseline` suppresses the lint warnings recorded in a baseline file with
+// `-Z update-lint-baseline`, independently of where they are in the file, while new
+// warnings are still reported.
+
+use run_make_support::{rfs, rustc};
+
+fn main() {
+    rustc()
+        .input("old.rs")
+        .crate_type("lib")
+        .crate_name("baseline")
+        .arg("-Zunstable-options")
+        .arg("--lint-baseline=baseline.txt")
+        .arg("-Zupdate-lint-baseline")
+        .run()
+        .assert_stderr_not_contains("unused variable");
+    let baseline = rfs::read_to_string("baseline.txt");
+    assert!(baseline.contains("unused_variables\t"), "{baseline}");
+    assert!(baseline.ends_with("\t1\told.rs\n"), "{baseline}");
+
+    // Keep the path of the baselined file.
+    rfs::copy("new.rs", "old.rs");
+    rustc()
+        .input("old.rs")
+        .crate_type("lib")
+        .crate_name("baseline")
+        .arg("-Zunstable-options")
+        .arg("--lint-baseline=baseline.txt")
+        .arg("-Dwarnings")
+        .run_fail()
+        .assert_stderr_contains("unused variable: `fresh`")
+        .assert_stderr_not_contains("unused variable: `unused`");
+
+    // The lints replayed by incremental compilation would be missing from the baseline.
+    rustc()
+        .input("old.rs")
+        .crate_type("lib")
+        .arg("-Zunstable-options")
+        .arg("--lint-baseline=baseline.txt")
+        .arg("-Zupdate-lint-baseline")
+        .incremental("incr")
+        .run_fail()
+        .assert_stderr_contains(
+            "`-Z update-lint-baseline` is incompatible with incremental compilation",
+        );
+}
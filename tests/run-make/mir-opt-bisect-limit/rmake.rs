@@ -0,0 +1,21 @@
+// `-Z mir-opt-bisect-limit` runs only the first optimization passes and prints which ones are run.
+
+//@ ignore-cross-compile
+
+use run_make_support::{run, rustc};
+
+fn main() {
+    let output = rustc().input("main.rs").opt_level("2").arg("-Zmir-opt-bisect-limit=0").run();
+    output.assert_stderr_contains("BISECT: NOT running pass (1) ");
+    output.assert_stderr_not_contains("BISECT: running pass");
+    run("main").assert_stdout_contains("3");
+
+    let output = rustc().input("main.rs").opt_level("2").arg("-Zmir-opt-bisect-limit=2").run();
+    output.assert_stderr_contains("BISECT: running pass (1) ");
+    output.assert_stderr_contains("BISECT: running pass (2) ");
+    output.assert_stderr_contains("BISECT: NOT running pass (3) ");
+    run("main").assert_stdout_contains("3");
+
+    // Without the flag, nothing is printed.
+    rustc().input("main.rs").opt_level("2").run().assert_stderr_not_contains("BISECT");
+}
@@ -0,0 +1,8 @@
+fn generic<T: std::fmt::Debug>(value: T) -> String {
+    format!("{value:?}")
+}
+
+fn main() {
+    generic(1u8);
+    generic("two");
+}
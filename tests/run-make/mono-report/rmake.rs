@@ -0,0 +1,31 @@
+// `--emit=mono-report` lists the instantiations of each generic function as JSON, with the
+// items that requested them and their estimated size.
+
+//@ ignore-cross-compile
+
+use run_make_support::{rfs, rustc};
+
+fn main() {
+    rustc()
+        .input("main.rs")
+        .emit("mono-report")
+        .run_fail()
+        .assert_stderr_contains("mono-report requested but -Zunstable-options not specified");
+
+    // Codegen isn't needed to collect the mono items.
+    rustc().input("main.rs").emit("mono-report,metadata").arg("-Zunstable-options").run();
+    let report = rfs::read_to_string("main.mono-report.json");
+    assert!(report.starts_with("{\"crate_name\":\"main\",\"functions\":["), "{report}");
+    assert!(
+        report.contains("{\"name\":\"generic\",\"crate\":\"main\",\"instantiation_count\":2,"),
+        "{report}"
+    );
+    assert!(report.contains("\"name\":\"generic::<u8>\""), "{report}");
+    assert!(report.contains("\"name\":\"generic::<&str>\""), "{report}");
+    assert!(
+        report.contains(
+            "\"requested_by\":[{\"item\":\"main\",\"crate\":\"main\",\"span\":\"main.rs:6:5: "
+        ),
+        "{report}"
+    );
+}
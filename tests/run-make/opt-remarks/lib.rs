@@ -0,0 +1,17 @@
+#[inline]
+fn add_one(x: u32) -> u32 {
+    x + 1
+}
+
+#[inline(never)]
+fn opaque(x: u32) -> u32 {
+    x.rotate_left(3)
+}
+
+pub fn caller(x: u32) -> u32 {
+    opaque(add_one(x))
+}
+
+pub fn sum(values: &[u32]) -> u32 {
+    values.iter().sum()
+}
@@ -0,0 +1,66 @@
+// `--emit=opt-remarks` writes the remarks of the MIR inliner and of LLVM to a single JSON file.
+
+//@ ignore-cross-compile
+
+use run_make_support::{rfs, rustc};
+
+fn main() {
+    rustc()
+        .input("lib.rs")
+        .crate_type("lib")
+        .emit("obj,opt-remarks")
+        .arg("-Copt-level=3")
+        .arg("-Cdebuginfo=line-tables-only")
+        .arg("-Ccodegen-units=4")
+        .run();
+    let remarks = rfs::read_to_string("lib.opt-remarks.json");
+    assert!(remarks.starts_with("{\"crate_name\":\"lib\",\"remarks\":["), "{remarks}");
+    assert!(
+        remarks.contains(
+            "\"origin\":\"mir\",\"pass\":\"inline\",\"kind\":\"success\",\"function\":\"caller\",\
+             \"message\":\"`add_one` inlined into `caller`\""
+        ),
+        "{remarks}"
+    );
+    assert!(
+        remarks.contains(
+            "\"kind\":\"missed\",\"function\":\"caller\",\
+             \"message\":\"`opaque` not inlined into `caller`: never inline hint\""
+        ),
+        "{remarks}"
+    );
+    assert!(remarks.contains("{\"file\":\"lib.rs\",\"line\":12,\"column\":12}"), "{remarks}");
+    assert!(remarks.contains("\"origin\":\"llvm\""), "{remarks}");
+
+    // The remarks of LLVM on the code reused from the incremental compilation cache are missing.
+    rustc()
+        .input("lib.rs")
+        .crate_type("lib")
+        .emit("obj,opt-remarks")
+        .incremental("incr")
+        .run()
+        .assert_stderr_contains("`--emit=opt-remarks` is incomplete when compiling incrementally");
+
+    // The remarks of the MIR reused from the incremental compilation cache are kept.
+    for _ in 0..2 {
+        rustc()
+            .input("lib.rs")
+            .crate_type("lib")
+            .emit("obj,opt-remarks")
+            .arg("-Copt-level=3")
+            .arg("-Zinline-mir")
+            .incremental("incr-mir")
+            .run();
+        let remarks = rfs::read_to_string("lib.opt-remarks.json");
+        assert!(remarks.contains("\"message\":\"`add_one` inlined into `caller`\""), "{remarks}");
+    }
+
+    rustc()
+        .input("lib.rs")
+        .crate_type("lib")
+        .emit("obj,opt-remarks")
+        .arg("-Cremark=all")
+        .arg("-Zremark-dir=remarks")
+        .run_fail()
+        .assert_stderr_contains("`--emit=opt-remarks` and `-Z remark-dir` are incompatible");
+}
@@ -0,0 +1,18 @@
+// `--error-format=sarif` collects all diagnostics of a session into a single SARIF log,
+// reporting error codes and lint names as rules and primary spans as result locations.
+
+use run_make_support::rustc;
+
+fn main() {
+    let output =
+        rustc().input("main.rs").error_format("sarif").arg("-Zunstable-options").run_fail();
+    output
+        .assert_stderr_contains(r#""version": "2.1.0""#)
+        .assert_stderr_contains(r#""ruleId": "E0308""#)
+        .assert_stderr_contains(r#""ruleId": "unused_variables""#)
+        .assert_stderr_contains(r#""startLine": 2"#)
+        .assert_stderr_contains(r#""uri": "main.rs""#)
+        .assert_stderr_not_contains("aborting due to");
+    // Only one log is written per compilation.
+    assert_eq!(output.stderr_utf8().matches(r#""$schema""#).count(), 1);
+}
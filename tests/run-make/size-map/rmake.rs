@@ -0,0 +1,23 @@
+// `--emit=size-map` attributes the symbols of the linked binary to the crates they come from.
+
+//@ ignore-cross-compile
+//@ ignore-windows
+// Windows executables don't have a symbol table, the symbols are in the PDB.
+
+use run_make_support::{rfs, rustc};
+
+fn main() {
+    rustc()
+        .input("main.rs")
+        .emit("link,size-map")
+        .run_fail()
+        .assert_stderr_contains("size-map requested but -Zunstable-options not specified");
+
+    rustc().input("main.rs").emit("link,size-map").arg("-Zunstable-options").run();
+    let size_map = rfs::read_to_string("main.size-map.json");
+    assert!(size_map.starts_with("{\"artifacts\":[{\"path\":"), "{size_map}");
+    assert!(size_map.contains("\"crate_type\":\"bin\""), "{size_map}");
+    assert!(size_map.contains("\"demangled\":\"main::answer\",\"crate\":\"main\""), "{size_map}");
+    assert!(size_map.contains("\"crates\":[{\"name\":"), "{size_map}");
+    assert!(size_map.contains("{\"name\":\"std\",\"size\":"), "{size_map}");
+}
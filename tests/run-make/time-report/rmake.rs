@@ -0,0 +1,14 @@
+// `-Z time-report` writes the time spent per query, MIR pass and LLVM pass to a JSON file,
+// attributing it to the items the queries and passes were run for.
+
+use run_make_support::{rfs, rustc};
+
+fn main() {
+    rustc().crate_type("lib").input("foo.rs").opt_level("1").arg("-Ztime-report=report.json").run();
+    let report = rfs::read_to_string("report.json");
+    assert!(report.starts_with(r#"{"entries":["#));
+    assert!(report.contains(r#"{"activity":"typeck","item":"bar","count":1,"#));
+    assert!(report.contains(r#"{"activity":"mir_pass_abort_unwinding_calls","item":"bar","#));
+    assert!(report.contains(r#"{"activity":"codegen_module","item":"foo."#));
+    assert!(report.contains(r#"{"activity":"LLVM_pass","item":"#));
+}
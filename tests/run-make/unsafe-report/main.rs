@@ -0,0 +1,44 @@
+extern "C" {
+    fn abs(value: i32) -> i32;
+}
+
+static mut COUNTER: u32 = 0;
+
+union Bits {
+    int: u32,
+    float: f32,
+}
+
+struct Handle(*const u8);
+
+// SAFETY: the pointer of a `Handle` is only read, by one thread at a time.
+unsafe impl Send for Handle {}
+
+/// # Safety
+///
+/// `ptr` must be valid for reads.
+unsafe fn read(ptr: *const u8) -> u8 {
+    *ptr
+}
+
+fn main() {
+    let value = 1u8;
+    // SAFETY: `value` is valid for reads.
+    let byte = unsafe { read(&value) };
+    let mut bits = Bits { float: 1.0 };
+    bits.int = 2;
+    let int = unsafe {
+        COUNTER += 1;
+        abs(-1) as u32 + bits.int
+    };
+    std::hint::black_box((byte, int, Handle(&value)));
+}
+
+fn write(value: &mut u8, ptr: *mut u8) {
+    // SAFETY: this is about the line below, not about the `unsafe` block.
+    *value = 1;
+    unsafe { *ptr = 2 };
+    /* SAFETY: `ptr` is valid for writes,
+     * as it comes from a reference. */
+    unsafe { *ptr = 3 };
+}
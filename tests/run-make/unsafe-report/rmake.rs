@@ -0,0 +1,57 @@
+// `--emit=unsafe-report` lists the `unsafe` blocks, unsafe functions, unsafe impls and `extern`
+// blocks of the crate as JSON, with the unsafe operations they perform and whether they have a
+// `SAFETY:` comment.
+
+//@ ignore-cross-compile
+
+use run_make_support::{rfs, rustc};
+
+fn main() {
+    rustc()
+        .input("main.rs")
+        .emit("unsafe-report")
+        .run_fail()
+        .assert_stderr_contains("unsafe-report requested but -Zunstable-options not specified");
+
+    rustc().input("main.rs").emit("unsafe-report,metadata").arg("-Zunstable-options").run();
+    let report = rfs::read_to_string("main.unsafe-report.json");
+    assert!(report.starts_with("{\"crate_name\":\"main\",\"items\":["), "{report}");
+    let contains = |part: &str| assert!(report.contains(part), "{part} in {report}");
+
+    contains("{\"kind\":\"extern block\",");
+    contains("\"foreign_items\":[\"abs\"]}");
+    contains("{\"kind\":\"unsafe impl\",");
+    contains(
+        "{\"kind\":\"unsafe fn\",\"item\":\"read\",\"span\":\"main.rs:20:1: 20:37\",\
+        \"safety_comment\":true,\"operations\":[{\"kind\":\"dereference of raw pointer\",\
+        \"span\":\"main.rs:21:5: 21:9\"}]}",
+    );
+    contains(
+        "{\"kind\":\"unsafe block\",\"item\":\"main\",\"span\":\"main.rs:27:16: 27:39\",\
+        \"safety_comment\":true,\"operations\":[{\"kind\":\"call to unsafe function\",\
+        \"span\":\"main.rs:27:25: 27:37\",\"callee\":\"read\"}]}",
+    );
+    contains(
+        "{\"kind\":\"unsafe block\",\"item\":\"main\",\"span\":\"main.rs:30:15: 33:6\",\
+        \"safety_comment\":false,\"operations\":[",
+    );
+    contains("{\"kind\":\"use of mutable static\",\"span\":\"main.rs:31:9: 31:16\"}");
+    contains(
+        "{\"kind\":\"call to foreign function\",\"span\":\"main.rs:32:9: 32:16\",\
+        \"callee\":\"abs\"}",
+    );
+    contains("{\"kind\":\"access to union field\",\"span\":\"main.rs:32:26: 32:34\"}");
+    // Code starting with `*` is not a comment, unlike the lines of block comments.
+    contains(
+        "{\"kind\":\"unsafe block\",\"item\":\"write\",\"span\":\"main.rs:40:5: 40:24\",\
+        \"safety_comment\":false,",
+    );
+    contains(
+        "{\"kind\":\"unsafe block\",\"item\":\"write\",\"span\":\"main.rs:43:5: 43:24\",\
+        \"safety_comment\":true,",
+    );
+    // Writing to a union field is safe.
+    assert_eq!(report.matches("access to union field").count(), 1, "{report}");
+    // The impl and the extern block are listed in the order of the source.
+    assert!(report.find("extern block") < report.find("unsafe impl"), "{report}");
+}
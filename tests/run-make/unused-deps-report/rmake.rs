@@ -0,0 +1,34 @@
+// `--emit=unused-deps` writes the `--extern` crates that were never referenced, and those that
+// were, as JSON, regardless of the level of the `unused_crate_dependencies` lint.
+
+//@ ignore-cross-compile
+
+use run_make_support::{rfs, rust_lib_name, rustc};
+
+fn main() {
+    rustc().input("used.rs").crate_type("rlib").run();
+    rustc().input("unused.rs").crate_type("rlib").run();
+
+    // The report is unstable.
+    rustc()
+        .input("main.rs")
+        .extern_("used", rust_lib_name("used"))
+        .extern_("unused", rust_lib_name("unused"))
+        .emit("unused-deps")
+        .run_fail()
+        .assert_stderr_contains("unused-deps requested but -Zunstable-options not specified");
+
+    let output = rustc()
+        .input("main.rs")
+        .extern_("used", rust_lib_name("used"))
+        .extern_("unused", rust_lib_name("unused"))
+        .emit("unused-deps,metadata")
+        .arg("-Zunstable-options")
+        .run();
+    // The lint is allowed by default, the report doesn't depend on it.
+    output.assert_stderr_not_contains("unused");
+    assert_eq!(
+        rfs::read_to_string("main.unused-deps.json"),
+        "{\"crate_name\":\"main\",\"unused\":[\"unused\"],\"used\":[\"used\"]}\n"
+    );
+}
@@ -0,0 +1,119 @@
+//@ run-pass
+//! Test that the graph of mono items has the uses of the items of the crate, with their kinds.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::mono::{Instance, MonoItem, MonoItemGraph, MonoItemUseKind};
+use stable_mir::CrateDef;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to check the mono item graph of the crate.
+fn test_mono_item_graph() -> ControlFlow<()> {
+    let graph = stable_mir::mono_item_graph();
+    let main = MonoItem::Fn(Instance::try_from(stable_mir::entry_fn().unwrap()).unwrap());
+    assert!(graph.items.contains(&main));
+
+    let uses_of_main = |kind: MonoItemUseKind| -> Vec<MonoItem> {
+        graph.uses(&main).filter(|edge| edge.kind == kind).map(|edge| edge.used.clone()).collect()
+    };
+    let calls = uses_of_main(MonoItemUseKind::Call);
+    assert!(calls.contains(&find_fn(&graph, "generic::<u32>")), "{calls:?}");
+    let fn_ptrs = uses_of_main(MonoItemUseKind::FnPtr);
+    assert!(fn_ptrs.contains(&find_fn(&graph, "callback")), "{fn_ptrs:?}");
+    let vtable = uses_of_main(MonoItemUseKind::Vtable);
+    assert!(vtable.contains(&find_fn(&graph, "<Dog as Speak>::speak")), "{vtable:?}");
+    assert!(!uses_of_main(MonoItemUseKind::DropGlue).is_empty());
+    let statics = uses_of_main(MonoItemUseKind::Static);
+    assert!(
+        matches!(&statics[..], [MonoItem::Static(def)] if def.name() == "GREETING"),
+        "{statics:?}"
+    );
+
+    // `callback` is only used through its pointer, and all items are reachable from the roots.
+    let callback = find_fn(&graph, "callback");
+    assert!(graph.users(&callback).all(|edge| edge.kind == MonoItemUseKind::FnPtr));
+    let reachable = graph.reachable_from(&main);
+    assert_eq!(reachable[0], main);
+    assert!(reachable.contains(&callback));
+    assert!(graph.instances().any(|instance| MonoItem::Fn(*instance) == callback));
+    ControlFlow::Continue(())
+}
+
+fn find_fn(graph: &MonoItemGraph, name: &str) -> MonoItem {
+    graph
+        .instances()
+        .find(|instance| instance.trimmed_name() == name)
+        .map(|instance| MonoItem::Fn(*instance))
+        .unwrap_or_else(|| panic!("Expected `{name}` in the graph"))
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "mono_item_graph_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=bin".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_mono_item_graph).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+        trait Speak {{
+            fn speak(&self) -> u32;
+        }}
+
+        struct Dog(Vec<u8>);
+
+        impl Speak for Dog {{
+            fn speak(&self) -> u32 {{
+                self.0.len() as u32
+            }}
+        }}
+
+        static GREETING: &str = "hello";
+
+        fn generic<T: Copy>(value: T) -> T {{
+            value
+        }}
+
+        fn callback() -> u32 {{
+            1
+        }}
+
+        fn main() {{
+            let dog: Box<dyn Speak> = Box::new(Dog(vec![1]));
+            let callback: fn() -> u32 = callback;
+            let sum = generic(dog.speak()) + callback();
+            std::hint::black_box(sum as usize + GREETING.len());
+        }}
+        "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,98 @@
+//@ run-pass
+//! Test that plugins are called back at the phases of the compilation, in order, and that the
+//! errors they emit make the compilation fail.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use stable_mir::diagnostic::{Diagnostic, Level};
+use stable_mir::mir::mono::Instance;
+use stable_mir::plugin::Plugin;
+use stable_mir::{CompilerError, CrateDef, CrateItem};
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// Records the phases it's called back at, and emits a diagnostic of `level` about `foo`.
+struct Recorder {
+    phases: Vec<&'static str>,
+    level: Level,
+}
+
+impl Plugin for &mut Recorder {
+    fn after_parsing(&mut self) -> ControlFlow<()> {
+        self.phases.push("parsing");
+        assert!(stable_mir::all_local_items().iter().any(|item| item.name() == "foo"));
+        ControlFlow::Continue(())
+    }
+
+    fn after_type_checking(&mut self) -> ControlFlow<()> {
+        self.phases.push("type checking");
+        let foo = find_fn("foo");
+        Diagnostic::new(self.level, "function named `foo`")
+            .with_span(foo.span())
+            .with_help("rename it")
+            .emit();
+        ControlFlow::Continue(())
+    }
+
+    fn after_mir_optimization(&mut self) -> ControlFlow<()> {
+        self.phases.push("MIR optimization");
+        let foo = Instance::try_from(find_fn("foo")).unwrap();
+        assert!(!foo.body().unwrap().blocks.is_empty());
+        ControlFlow::Break(())
+    }
+}
+
+fn find_fn(name: &str) -> CrateItem {
+    stable_mir::all_local_items().into_iter().find(|item| item.name() == name).unwrap()
+}
+
+/// This test will generate a dummy crate, and run the compiler on it with a plugin, first one
+/// which stops the compilation after its last callback, then one which emits an error.
+fn main() {
+    let path = "plugin_input.rs";
+    generate_input(&path).unwrap();
+    let args = || {
+        vec![
+            "rustc".to_string(),
+            "--crate-type=lib".to_string(),
+            "--crate-name".to_string(),
+            CRATE_NAME.to_string(),
+            path.to_string(),
+        ]
+    };
+
+    let mut recorder = Recorder { phases: vec![], level: Level::Warning };
+    assert_eq!(run_plugin!(args(), &mut recorder), Err(CompilerError::Interrupted(())));
+    assert_eq!(recorder.phases, ["parsing", "type checking", "MIR optimization"]);
+
+    let mut recorder = Recorder { phases: vec![], level: Level::Error };
+    assert_eq!(run_plugin!(args(), &mut recorder), Err(CompilerError::Failed));
+    assert_eq!(recorder.phases, ["parsing", "type checking", "MIR optimization"]);
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+        pub fn foo(value: u32) -> u32 {{
+            value + 1
+        }}
+        "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,142 @@
+//@ run-pass
+//! Test that the MIR transformations registered with StableMIR are applied to the optimized MIR
+//! of the functions of the crate.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::mono::Instance;
+use stable_mir::mir::visit::Location;
+use stable_mir::mir::{
+    BasicBlock, BinOp, Body, ConstOperand, Mutability, MutMirVisitor, Operand, Place, Rvalue,
+    Terminator, TerminatorKind, UnwindAction,
+};
+use stable_mir::ty::{MirConst, RigidTy, Ty, TyKind};
+use stable_mir::{CrateDef, CrateItem, ItemKind};
+use std::assert_matches::assert_matches;
+use std::convert::TryFrom;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// Instruments `add` with a call to `probe` on entry, and turns its `^` into `&`.
+fn instrument(item: CrateItem, body: &mut Body) {
+    if item.name() != "add" {
+        return;
+    }
+    BitXorToBitAnd.visit_body(body);
+
+    let probe = find_fn("probe");
+    let destination = body.new_local(Ty::new_tuple(&[]), body.span, Mutability::Not);
+    let entry = body.blocks.len();
+    let call = BasicBlock {
+        statements: vec![],
+        terminator: Terminator {
+            kind: TerminatorKind::Call {
+                func: Operand::Constant(ConstOperand {
+                    span: body.span,
+                    user_ty: None,
+                    const_: MirConst::try_new_zero_sized(probe.ty()).unwrap(),
+                }),
+                args: vec![],
+                destination: Place::from(destination),
+                target: Some(entry),
+                unwind: UnwindAction::Continue,
+            },
+            span: body.span,
+        },
+    };
+    let original_entry = std::mem::replace(&mut body.blocks[0], call);
+    body.blocks.push(original_entry);
+}
+
+struct BitXorToBitAnd;
+
+impl MutMirVisitor for BitXorToBitAnd {
+    fn visit_rvalue(&mut self, rvalue: &mut Rvalue, location: Location) {
+        if let Rvalue::BinaryOp(op @ BinOp::BitXor, ..) = rvalue {
+            *op = BinOp::BitAnd;
+        }
+        self.super_rvalue(rvalue, location);
+    }
+}
+
+/// This function checks that the optimized MIR of `add` is the transformed one.
+fn test_registered_transform() -> ControlFlow<()> {
+    let add = Instance::try_from(find_fn("add")).unwrap();
+    let body = add.body().unwrap();
+
+    let TerminatorKind::Call { func, target: Some(target), .. } = &body.blocks[0].terminator.kind
+    else {
+        panic!("Expected a call to `probe`, but found {:?}", body.blocks[0]);
+    };
+    let TyKind::RigidTy(RigidTy::FnDef(def, _)) = func.ty(body.locals()).unwrap().kind() else {
+        unreachable!()
+    };
+    assert_eq!(def.name(), "probe");
+    assert!(
+        body.blocks[*target].statements.iter().any(|statement| matches!(
+            &statement.kind,
+            stable_mir::mir::StatementKind::Assign(_, Rvalue::BinaryOp(BinOp::BitAnd, ..))
+        )),
+        "Expected `^` to be replaced by `&`"
+    );
+
+    // The other functions are not transformed.
+    let probe = Instance::try_from(find_fn("probe")).unwrap();
+    assert_matches!(probe.body().unwrap().blocks[0].terminator.kind, TerminatorKind::Return);
+    ControlFlow::Continue(())
+}
+
+fn find_fn(name: &str) -> CrateItem {
+    stable_mir::all_local_items()
+        .into_iter()
+        .find(|item| item.kind() == ItemKind::Fn && item.name() == name)
+        .unwrap()
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will register the transformation and run the compiler.
+fn main() {
+    let path = "registered_transform_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    rustc_internal::transform::register("instrument", instrument);
+    run!(args, test_registered_transform).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+        pub fn probe() {{}}
+
+        pub fn add(a: u32, b: u32) -> u32 {{
+            a ^ b
+        }}
+        "#
+    )?;
+    Ok(())
+}
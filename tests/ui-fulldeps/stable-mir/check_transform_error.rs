@@ -0,0 +1,83 @@
+//@ run-pass
+//! Test that a MIR transformation registered with StableMIR which adds inline assembly to a
+//! function is reported as an error, instead of crashing the compiler.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::mono::Instance;
+use stable_mir::mir::{Body, TerminatorKind, UnwindAction};
+use stable_mir::{CompilerError, CrateItem, ItemKind};
+use std::assert_matches::assert_matches;
+use std::convert::TryFrom;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// Replaces the return of `nop` with inline assembly.
+fn add_inline_asm(item: CrateItem, body: &mut Body) {
+    if item.name() != "nop" {
+        return;
+    }
+    body.blocks[0].terminator.kind = TerminatorKind::InlineAsm {
+        template: "nop".to_string(),
+        operands: vec![],
+        options: String::new(),
+        line_spans: String::new(),
+        destination: None,
+        unwind: UnwindAction::Unreachable,
+    };
+}
+
+/// This function checks that the optimized MIR of `nop` is the original one.
+fn test_transform_error() -> ControlFlow<()> {
+    let nop = stable_mir::all_local_items()
+        .into_iter()
+        .find(|item| item.kind() == ItemKind::Fn && item.name() == "nop")
+        .unwrap();
+    let body = Instance::try_from(nop).unwrap().body().unwrap();
+    assert_matches!(body.blocks[0].terminator.kind, TerminatorKind::Return);
+    ControlFlow::Continue(())
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will register the transformation and run the compiler, which must fail.
+fn main() {
+    let path = "transform_error_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    rustc_internal::transform::register("add_inline_asm", add_inline_asm);
+    assert_eq!(run!(args, test_transform_error), Err(CompilerError::Failed));
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+        pub fn nop() {{}}
+        "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,136 @@
+//@ run-pass
+//! Test that snapshots of the stable MIR of a crate have what its bodies refer to, and that they
+//! can be saved and read back in both formats.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::alloc::GlobalAlloc;
+use stable_mir::mir::visit::Location;
+use stable_mir::mir::MirVisitor;
+use stable_mir::snapshot::{CrateSnapshot, Format};
+use stable_mir::ty::Ty;
+use stable_mir::CrateDef;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+fn test_snapshot() -> ControlFlow<()> {
+    let snapshot = CrateSnapshot::capture();
+    assert_eq!(snapshot.local_crate.name, CRATE_NAME);
+
+    let mut names: Vec<_> = snapshot.items.iter().map(|data| data.item.name()).collect();
+    names.sort();
+    assert_eq!(names, ["entry", "greeting", "helper"]);
+
+    // The generic `helper` has no instance of its own, but the one that `entry` calls.
+    let helper = snapshot
+        .resolutions
+        .iter()
+        .find(|resolution| resolution.def.name() == "helper")
+        .expect("Expected the call to `helper` to be resolved");
+    let helper = snapshot
+        .instances
+        .iter()
+        .find(|data| data.instance == helper.instance)
+        .expect("Expected the instance of `helper` to be in the snapshot");
+    assert_eq!(helper.name, "helper::<u32>");
+    assert!(helper.body.is_some());
+
+    // The snapshot has the kinds of all the types of the bodies.
+    let mut tys = Tys(Vec::new());
+    for data in &snapshot.instances {
+        if let Some(body) = &data.body {
+            tys.visit_body(body);
+        }
+    }
+    for ty in tys.0 {
+        let data = snapshot.tys.iter().find(|data| data.ty == ty).expect("Missing type");
+        assert_eq!(data.kind, ty.kind());
+    }
+
+    // And the allocation of the string constant of `greeting`.
+    assert!(snapshot.allocs.iter().any(|data| matches!(
+        &data.alloc,
+        GlobalAlloc::Memory(alloc) if alloc.bytes.iter().map(|byte| byte.unwrap()).eq(*b"hello")
+    )));
+
+    for format in [Format::Json, Format::Binary] {
+        let mut bytes = Vec::new();
+        snapshot.write(&mut bytes, format).unwrap();
+        let read = CrateSnapshot::read(&bytes[..]).unwrap();
+        assert_eq!(read.items.len(), snapshot.items.len());
+        assert_eq!(read.instances.len(), snapshot.instances.len());
+        assert_eq!(read.tys.len(), snapshot.tys.len());
+        for (read, data) in read.tys.iter().zip(&snapshot.tys) {
+            assert_eq!((read.ty, &read.kind), (data.ty, &data.kind));
+        }
+    }
+
+    let mut outdated = snapshot.clone();
+    outdated.format_version += 1;
+    let mut bytes = Vec::new();
+    outdated.write(&mut bytes, Format::Json).unwrap();
+    assert!(CrateSnapshot::read(&bytes[..]).is_err());
+
+    ControlFlow::Continue(())
+}
+
+struct Tys(Vec<Ty>);
+
+impl MirVisitor for Tys {
+    fn visit_ty(&mut self, ty: &Ty, _: Location) {
+        self.0.push(*ty);
+    }
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "snapshot_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_snapshot).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+        fn helper<T: Copy>(value: T) -> T {{
+            value
+        }}
+
+        pub fn entry(value: u32) -> u32 {{
+            helper(value)
+        }}
+
+        pub fn greeting() -> &'static str {{
+            "hello"
+        }}
+        "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,30 @@
+#![feature(optimize_attribute)]
+
+#[optimize(level = "4")] //~ ERROR E0722
+fn too_high() {}
+
+#[optimize(level = "fast")] //~ ERROR E0722
+fn unknown() {}
+
+#[optimize(level)] //~ ERROR E0722
+fn missing() {}
+
+#[optimize(level = "0")]
+fn no() {}
+
+#[optimize(level = "1")]
+fn less() {}
+
+#[optimize(level = "2")]
+fn default() {}
+
+#[optimize(level = "3")]
+fn aggressive() {}
+
+#[optimize(level = "s")]
+fn size() {}
+
+#[optimize(level = "z")]
+fn size_min() {}
+
+fn main() {}
@@ -0,0 +1,3 @@
+//@ compile-flags: --lint-baseline=lint-baseline.txt
+
+fn main() {}
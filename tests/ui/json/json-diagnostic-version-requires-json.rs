@@ -0,0 +1,3 @@
+//@ compile-flags: -Zjson-diagnostic-version=1 --error-format=short
+
+fn main() {}
@@ -0,0 +1,3 @@
+//@ compile-flags: -Zjson-diagnostic-version=2 --error-format=json
+
+fn main() {}
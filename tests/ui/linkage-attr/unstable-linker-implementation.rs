@@ -0,0 +1,7 @@
+// Check that `-C linker-implementation` requires `-Z unstable-options`.
+//
+//@ compile-flags: -C linker-implementation=mold --crate-type=rlib
+//@ error-pattern: `-C linker-implementation` is unstable, the `-Z unstable-options` flag
+
+#![feature(no_core)]
+#![no_core]
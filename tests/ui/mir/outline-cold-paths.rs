@@ -0,0 +1,82 @@
+// The panics of the cold paths outlined from functions keep their messages and locations, also
+// when the cold paths are called by the functions they are inlined into.
+
+//@ run-pass
+//@ compile-flags: -O -Z outline-cold-paths
+//@ needs-unwind
+
+use std::panic;
+use std::sync::Mutex;
+
+static PANIC_LINE: Mutex<u32> = Mutex::new(0);
+
+#[inline(never)]
+fn check_positive(x: i32) -> i32 {
+    if x <= 0 {
+        panic!("{x} is out of range");
+    }
+    x
+}
+
+#[inline(never)]
+fn check_small(x: i32) -> i32 {
+    if x >= 100 {
+        panic!("{x} is out of range");
+    }
+    x
+}
+
+#[inline]
+fn half(x: u32) -> u32 {
+    if x % 2 != 0 {
+        panic!("{x} is odd");
+    }
+    x / 2
+}
+
+#[inline(never)]
+fn quarter(x: u32) -> u32 {
+    half(half(x))
+}
+
+#[inline(never)]
+fn nth(values: &[u64], n: usize) -> u64 {
+    match values.get(n) {
+        Some(&value) => value,
+        None => panic!("no value {n} among {}", values.len()),
+    }
+}
+
+#[inline(never)]
+fn index(values: &[u64], n: usize) -> u64 {
+    values[n]
+}
+
+fn panic_of(f: impl FnOnce() + panic::UnwindSafe) -> (String, u32) {
+    let payload = panic::catch_unwind(f).unwrap_err();
+    (*payload.downcast::<String>().unwrap(), *PANIC_LINE.lock().unwrap())
+}
+
+fn main() {
+    panic::set_hook(Box::new(|info| {
+        *PANIC_LINE.lock().unwrap() = info.location().unwrap().line();
+    }));
+
+    assert_eq!(check_positive(3), 3);
+    assert_eq!(panic_of(|| drop(check_positive(-2))), ("-2 is out of range".to_owned(), 16));
+
+    assert_eq!(check_small(3), 3);
+    assert_eq!(panic_of(|| drop(check_small(200))), ("200 is out of range".to_owned(), 24));
+
+    assert_eq!(quarter(12), 3);
+    assert_eq!(panic_of(|| drop(quarter(6))), ("3 is odd".to_owned(), 32));
+
+    assert_eq!(nth(&[1, 2], 1), 2);
+    assert_eq!(panic_of(|| drop(nth(&[1, 2], 5))), ("no value 5 among 2".to_owned(), 46));
+
+    assert_eq!(index(&[1, 2], 1), 2);
+    assert_eq!(
+        panic_of(|| drop(index(&[1, 2], 5))),
+        ("index out of bounds: the len is 2 but the index is 5".to_owned(), 52)
+    );
+}
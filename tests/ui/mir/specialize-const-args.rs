@@ -0,0 +1,53 @@
+// Specializing functions for the constant arguments of their calls keeps their behavior.
+
+//@ run-pass
+//@ compile-flags: -O
+
+#[derive(Clone, Copy)]
+enum Mode {
+    Strict,
+    Lenient,
+}
+
+#[inline(never)]
+fn parse(input: &str, mode: Mode) -> Option<u32> {
+    let input = match mode {
+        Mode::Strict => input,
+        Mode::Lenient => input.trim(),
+    };
+    input.parse().ok()
+}
+
+#[inline(never)]
+fn scale(x: i64, factor: i64, negate: bool) -> i64 {
+    let y = if factor == 0 { x } else { x * factor };
+    if negate { -y } else { y }
+}
+
+#[inline(never)]
+fn countdown(n: u8, verbose: bool) -> u32 {
+    if n == 0 {
+        return 0;
+    }
+    if verbose {
+        println!("{n}")
+    }
+    1 + countdown(n - 1, verbose)
+}
+
+fn main() {
+    assert_eq!(parse("12", Mode::Strict), Some(12));
+    assert_eq!(parse(" 12 ", Mode::Strict), None);
+    assert_eq!(parse(" 12 ", Mode::Lenient), Some(12));
+
+    let mode = if std::env::args().count() > 10 { Mode::Strict } else { Mode::Lenient };
+    assert_eq!(parse(" 7", mode), Some(7));
+
+    assert_eq!(scale(3, 0, false), 3);
+    assert_eq!(scale(3, 2, false), 6);
+    assert_eq!(scale(3, 2, true), -6);
+    assert_eq!(scale(-3, 0, true), 3);
+
+    assert_eq!(countdown(5, false), 5);
+    assert_eq!(countdown(2, true), 2);
+}